@@ -0,0 +1,127 @@
+use crate::credentials;
+use crate::restarts;
+use futures::stream::{self, StreamExt};
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Set once at startup for viewer builds; every write to a miner checks it.
+static READ_ONLY: AtomicBool = AtomicBool::new(false);
+
+pub fn set_read_only(read_only: bool) {
+    READ_ONLY.store(read_only, Ordering::Relaxed);
+}
+
+pub fn is_read_only() -> bool {
+    READ_ONLY.load(Ordering::Relaxed)
+}
+
+/// Refuses a write to `ip` in read-only mode, before anything connects to it.
+pub fn ensure_writable(ip: &str) -> Result<(), String> {
+    if is_read_only() {
+        Err(format!("Read-only mode: not changing {ip}"))
+    } else {
+        Ok(())
+    }
+}
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub enum MinerAction {
+    Pause,
+    Resume,
+    Restart,
+}
+
+impl MinerAction {
+    pub fn label(self) -> &'static str {
+        match self {
+            MinerAction::Pause => "Pause",
+            MinerAction::Resume => "Resume",
+            MinerAction::Restart => "Restart",
+        }
+    }
+}
+
+pub async fn run_action(ip: &str, action: MinerAction) -> Result<(), String> {
+    ensure_writable(ip)?;
+    let result = run_action_on_miner(ip, action).await;
+    credentials::record_outcome(ip, &result);
+    if action == MinerAction::Restart && result.is_ok() {
+        restarts::track(ip);
+    }
+    result
+}
+
+async fn run_action_on_miner(ip: &str, action: MinerAction) -> Result<(), String> {
+    let miner = credentials::connect(ip).await?;
+    let result = match action {
+        MinerAction::Pause => miner.pause(None).await,
+        MinerAction::Resume => miner.resume(None).await,
+        MinerAction::Restart => {
+            if !miner.supports_restart() {
+                return Err(format!("Restart is not supported on {ip}"));
+            }
+            miner.restart().await
+        }
+    };
+    result
+        .map(|_| ())
+        .map_err(|e| format!("{} failed on {ip}: {e}", action.label()))
+}
+
+pub async fn set_fault_light(ip: &str, on: bool) -> Result<(), String> {
+    ensure_writable(ip)?;
+    let result = match credentials::connect(ip).await {
+        Ok(miner) => miner
+            .set_fault_light(on)
+            .await
+            .map(|_| ())
+            .map_err(|e| format!("Fault light failed on {ip}: {e}")),
+        Err(err) => Err(err),
+    };
+    credentials::record_outcome(ip, &result);
+    result
+}
+
+/// Flips each miner's fault light from the state it last reported.
+pub async fn toggle_fault_lights(
+    current_states: Vec<(String, bool)>,
+) -> Vec<(String, Result<(), String>)> {
+    let concurrency = current_states.len().clamp(1, 24);
+
+    stream::iter(current_states)
+        .map(|(ip, current)| async move {
+            let result = set_fault_light(&ip, !current).await;
+            match &result {
+                Ok(()) => println!(
+                    "✓ Set fault light to {} on: {}",
+                    if current { "OFF" } else { "ON" },
+                    ip
+                ),
+                Err(err) => eprintln!("✗ {err}"),
+            }
+            (ip, result)
+        })
+        .buffer_unordered(concurrency)
+        .collect()
+        .await
+}
+
+pub async fn run_action_on_many(
+    ips: Vec<String>,
+    action: MinerAction,
+) -> Vec<(String, Result<(), String>)> {
+    let concurrency = ips.len().clamp(1, 24);
+
+    stream::iter(ips)
+        .map(|ip| async move {
+            let result = run_action(&ip, action).await;
+            match &result {
+                Ok(()) => println!("✓ {} {}", action.label(), ip),
+                Err(err) => eprintln!("✗ {err}"),
+            }
+            (ip, result)
+        })
+        .buffer_unordered(concurrency)
+        .collect()
+        .await
+}
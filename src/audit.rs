@@ -0,0 +1,228 @@
+use crate::export::csv_field;
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::sync::{Arc, Mutex, OnceLock};
+
+const AUDIT_LOG_FILE: &str = "audit_log.json";
+const MAX_AUDIT_ENTRIES: usize = 5000;
+
+/// Login name of whoever is running the app.
+pub fn operator() -> String {
+    std::env::var("USER")
+        .or_else(|_| std::env::var("USERNAME"))
+        .unwrap_or_else(|_| "unknown".to_string())
+}
+
+/// Name of the machine the app runs on, looked up once.
+pub fn host() -> String {
+    static HOST: OnceLock<String> = OnceLock::new();
+    HOST.get_or_init(|| {
+        std::env::var("HOSTNAME")
+            .or_else(|_| std::env::var("COMPUTERNAME"))
+            .ok()
+            .or_else(|| {
+                fs::read_to_string("/etc/hostname")
+                    .ok()
+                    .map(|name| name.trim().to_string())
+            })
+            .filter(|name| !name.is_empty())
+            .unwrap_or_else(|| "unknown".to_string())
+    })
+    .clone()
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct AuditEntry {
+    pub at: i64,
+    pub operator: String,
+    pub host: String,
+    /// Where the action was triggered, e.g. "Table" or "Detail".
+    pub source: String,
+    pub action: String,
+    pub targets: Vec<String>,
+    #[serde(default)]
+    pub parameters: String,
+    #[serde(default)]
+    pub failures: Vec<String>,
+    /// Handed off to a job that reports its own progress, such as a staged rollout.
+    #[serde(default)]
+    pub dispatched: bool,
+}
+
+impl AuditEntry {
+    pub fn new(source: &str, action: &str, targets: Vec<String>, parameters: String) -> Self {
+        Self {
+            at: Local::now().timestamp(),
+            operator: operator(),
+            host: host(),
+            source: source.to_string(),
+            action: action.to_string(),
+            targets,
+            parameters,
+            failures: Vec::new(),
+            dispatched: false,
+        }
+    }
+
+    /// Keeps one "ip: error" line per failed target.
+    pub fn with_results<T>(mut self, results: &[(String, Result<T, String>)]) -> Self {
+        self.failures = results
+            .iter()
+            .filter_map(|(ip, result)| result.as_ref().err().map(|err| format!("{ip}: {err}")))
+            .collect();
+        self
+    }
+
+    pub fn dispatched(mut self) -> Self {
+        self.dispatched = true;
+        self
+    }
+
+    pub fn result_label(&self) -> String {
+        if self.dispatched {
+            "Dispatched".to_string()
+        } else if self.failures.is_empty() {
+            "OK".to_string()
+        } else {
+            format!("{} of {} failed", self.failures.len(), self.targets.len())
+        }
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct AuditLog {
+    pub entries: Vec<AuditEntry>,
+}
+
+impl AuditLog {
+    pub fn load() -> Self {
+        crate::config::app_data_path(AUDIT_LOG_FILE)
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+            .unwrap_or_default()
+    }
+
+    fn save(&self) {
+        if let Some(path) = crate::config::app_data_path(AUDIT_LOG_FILE) {
+            if let Ok(json) = serde_json::to_string(self) {
+                if let Err(e) = fs::write(path, json) {
+                    eprintln!("✗ Failed to save audit log: {e}");
+                }
+            }
+        }
+    }
+
+    pub fn record(&mut self, entry: AuditEntry) {
+        self.entries.push(entry);
+        if self.entries.len() > MAX_AUDIT_ENTRIES {
+            self.entries
+                .drain(0..self.entries.len() - MAX_AUDIT_ENTRIES);
+        }
+        self.save();
+    }
+}
+
+/// Records an entry from UI code or a background task.
+pub fn record(log: &Arc<Mutex<AuditLog>>, entry: AuditEntry) {
+    log.lock().unwrap().record(entry);
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct AuditFilter {
+    /// Matched against action, target IPs, parameters and failures.
+    pub text: String,
+    pub source: Option<String>,
+    pub failures_only: bool,
+}
+
+impl AuditFilter {
+    pub fn matches(&self, entry: &AuditEntry) -> bool {
+        if self.failures_only && entry.failures.is_empty() {
+            return false;
+        }
+        if self
+            .source
+            .as_ref()
+            .is_some_and(|source| *source != entry.source)
+        {
+            return false;
+        }
+        let text = self.text.trim().to_lowercase();
+        text.is_empty()
+            || entry.action.to_lowercase().contains(&text)
+            || entry.parameters.to_lowercase().contains(&text)
+            || entry.operator.to_lowercase().contains(&text)
+            || entry.targets.iter().any(|ip| ip.contains(&text))
+            || entry
+                .failures
+                .iter()
+                .any(|failure| failure.to_lowercase().contains(&text))
+    }
+}
+
+/// Newest first.
+pub fn filtered_entries<'a>(log: &'a AuditLog, filter: &AuditFilter) -> Vec<&'a AuditEntry> {
+    log.entries
+        .iter()
+        .rev()
+        .filter(|entry| filter.matches(entry))
+        .collect()
+}
+
+pub fn audit_csv(entries: &[&AuditEntry]) -> String {
+    let mut csv =
+        String::from("Time,Operator,Host,Source,Action,Targets,Parameters,Result,Failures\n");
+    for entry in entries {
+        csv.push_str(&format!(
+            "{},{},{},{},{},{},{},{},{}\n",
+            crate::snapshots::format_timestamp(entry.at),
+            csv_field(&entry.operator),
+            csv_field(&entry.host),
+            csv_field(&entry.source),
+            csv_field(&entry.action),
+            csv_field(&entry.targets.join(" ")),
+            csv_field(&entry.parameters),
+            csv_field(&entry.result_label()),
+            csv_field(&entry.failures.join("; "))
+        ));
+    }
+    csv
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AuditEntry, AuditFilter};
+
+    #[test]
+    fn filter_matches_text_source_and_failures() {
+        let ok = AuditEntry::new("Table", "Stop", vec!["10.0.0.5".to_string()], String::new())
+            .with_results::<()>(&[("10.0.0.5".to_string(), Ok(()))]);
+        let failed = AuditEntry::new(
+            "Detail",
+            "Fault light",
+            vec!["10.0.0.9".to_string()],
+            "On".to_string(),
+        )
+        .with_results::<()>(&[("10.0.0.9".to_string(), Err("timed out".to_string()))]);
+
+        let by_ip = AuditFilter {
+            text: "10.0.0.9".to_string(),
+            ..Default::default()
+        };
+        assert!(!by_ip.matches(&ok) && by_ip.matches(&failed));
+
+        let by_source = AuditFilter {
+            source: Some("Table".to_string()),
+            ..Default::default()
+        };
+        assert!(by_source.matches(&ok) && !by_source.matches(&failed));
+
+        let failures = AuditFilter {
+            failures_only: true,
+            ..Default::default()
+        };
+        assert!(!failures.matches(&ok) && failures.matches(&failed));
+        assert_eq!(failed.result_label(), "1 of 1 failed");
+    }
+}
@@ -0,0 +1,506 @@
+use crate::export::csv_field;
+use crate::models::{MinerInfo, SavedRange};
+use crate::scanner;
+use crate::tenants;
+use chrono::{Local, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+
+const AVAILABILITY_LOG_FILE: &str = "availability_log.json";
+/// Spans and reboots older than this are dropped so the log stays small.
+const RETAIN_SECS: i64 = 800 * 86_400;
+/// A miner unseen for this long is treated as retired and no longer counted as offline.
+const RETIRE_AFTER_SECS: i64 = 7 * 86_400;
+/// Boot times closer than this are the same boot, allowing for clock jitter.
+const BOOT_TOLERANCE_SECS: i64 = 120;
+/// The whole log is rewritten on save, so scans only save this often.
+const SAVE_INTERVAL_SECS: i64 = 10 * 60;
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct SlaSettings {
+    pub target_percent: f64,
+    /// Leave deliberately paused time (curtailment, sleep) out of the SLA.
+    pub exclude_paused: bool,
+}
+
+impl Default for SlaSettings {
+    fn default() -> Self {
+        Self {
+            target_percent: 99.0,
+            exclude_paused: true,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub enum MinerState {
+    Hashing,
+    /// Answering the scan but not producing hashrate.
+    Online,
+    /// Mining stopped on purpose.
+    Paused,
+    Offline,
+}
+
+impl MinerState {
+    pub fn of(miner: &MinerInfo) -> Self {
+        match &miner.full_data {
+            Some(data) if !data.is_mining => MinerState::Paused,
+            _ if miner.hashrate_th.is_some_and(|th| th > 0.0) => MinerState::Hashing,
+            _ => MinerState::Online,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            MinerState::Hashing => "Hashing",
+            MinerState::Online => "Not hashing",
+            MinerState::Paused => "Paused",
+            MinerState::Offline => "Offline",
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct StateSpan {
+    pub state: MinerState,
+    pub start: i64,
+    pub end: i64,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct MinerAvailability {
+    pub ip: String,
+    pub model: String,
+    pub last_recorded_at: i64,
+    pub last_online_at: i64,
+    /// Derived from the reported uptime; a later boot time means a reboot.
+    pub boot_at: Option<i64>,
+    pub spans: Vec<StateSpan>,
+    pub reboots: Vec<i64>,
+}
+
+impl MinerAvailability {
+    /// Attributes the time since the previous scan to `state`. Intervals longer
+    /// than `max_gap_secs` (the app was closed) are left unaccounted.
+    pub fn record(
+        &mut self,
+        now: i64,
+        state: MinerState,
+        uptime_secs: Option<u64>,
+        max_gap_secs: i64,
+    ) {
+        let elapsed = now - self.last_recorded_at;
+        if self.last_recorded_at > 0 && elapsed > 0 && elapsed <= max_gap_secs {
+            match self.spans.last_mut() {
+                Some(last) if last.state == state && last.end == self.last_recorded_at => {
+                    last.end = now;
+                }
+                _ => self.spans.push(StateSpan {
+                    state,
+                    start: self.last_recorded_at,
+                    end: now,
+                }),
+            }
+        }
+
+        if let Some(uptime) = uptime_secs {
+            let boot_at = now - uptime as i64;
+            if self
+                .boot_at
+                .is_some_and(|previous| boot_at > previous + BOOT_TOLERANCE_SECS)
+            {
+                self.reboots.push(boot_at);
+            }
+            self.boot_at = Some(boot_at);
+        }
+        if state != MinerState::Offline {
+            self.last_online_at = now;
+        }
+        if self.last_recorded_at == 0 || elapsed > 0 {
+            self.last_recorded_at = now;
+        }
+    }
+
+    /// Time per state within `[from, to)`, plus reboots in that window.
+    pub fn totals(&self, from: i64, to: i64) -> AvailabilityTotals {
+        let mut totals = AvailabilityTotals {
+            reboots: self
+                .reboots
+                .iter()
+                .filter(|at| (from..to).contains(*at))
+                .count(),
+            ..Default::default()
+        };
+        for span in &self.spans {
+            let secs = span.end.min(to) - span.start.max(from);
+            if secs <= 0 {
+                continue;
+            }
+            match span.state {
+                MinerState::Hashing => totals.hashing_secs += secs,
+                MinerState::Online => totals.online_secs += secs,
+                MinerState::Paused => totals.paused_secs += secs,
+                MinerState::Offline => totals.offline_secs += secs,
+            }
+        }
+        totals
+    }
+
+    /// Runs of offline or not-hashing time within `[from, to)`. Back-to-back
+    /// runs, such as a reboot followed by warm-up, are one incident.
+    pub fn incidents(&self, identity: &str, from: i64, to: i64) -> Vec<Incident> {
+        let mut incidents: Vec<Incident> = Vec::new();
+        for span in &self.spans {
+            if matches!(span.state, MinerState::Hashing | MinerState::Paused) {
+                continue;
+            }
+            let (start, end) = (span.start.max(from), span.end.min(to));
+            if end <= start {
+                continue;
+            }
+            match incidents.last_mut() {
+                Some(last) if last.end == start => {
+                    last.end = end;
+                    if span.state == MinerState::Offline {
+                        last.state = MinerState::Offline;
+                    }
+                }
+                _ => incidents.push(Incident {
+                    identity: identity.to_string(),
+                    ip: self.ip.clone(),
+                    model: self.model.clone(),
+                    state: span.state,
+                    start,
+                    end,
+                    rebooted: false,
+                }),
+            }
+        }
+        for incident in &mut incidents {
+            incident.rebooted = self
+                .reboots
+                .iter()
+                .any(|at| (incident.start - BOOT_TOLERANCE_SECS..=incident.end).contains(at));
+        }
+        incidents
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct AvailabilityTotals {
+    pub hashing_secs: i64,
+    pub online_secs: i64,
+    pub paused_secs: i64,
+    pub offline_secs: i64,
+    pub reboots: usize,
+}
+
+impl AvailabilityTotals {
+    fn add(&mut self, other: &AvailabilityTotals) {
+        self.hashing_secs += other.hashing_secs;
+        self.online_secs += other.online_secs;
+        self.paused_secs += other.paused_secs;
+        self.offline_secs += other.offline_secs;
+        self.reboots += other.reboots;
+    }
+
+    pub fn monitored_secs(&self) -> i64 {
+        self.hashing_secs + self.online_secs + self.paused_secs + self.offline_secs
+    }
+
+    pub fn downtime_secs(&self) -> i64 {
+        self.online_secs + self.offline_secs
+    }
+
+    /// Share of the monitored time spent hashing; `None` with nothing monitored.
+    pub fn availability_percent(&self, exclude_paused: bool) -> Option<f64> {
+        let base = if exclude_paused {
+            self.monitored_secs() - self.paused_secs
+        } else {
+            self.monitored_secs()
+        };
+        (base > 0).then(|| self.hashing_secs as f64 / base as f64 * 100.0)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Incident {
+    pub identity: String,
+    pub ip: String,
+    pub model: String,
+    /// `Offline` if the miner dropped off the network at any point.
+    pub state: MinerState,
+    pub start: i64,
+    pub end: i64,
+    pub rebooted: bool,
+}
+
+impl Incident {
+    pub fn duration_secs(&self) -> i64 {
+        self.end - self.start
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct AvailabilityLog {
+    /// Keyed by `MinerInfo::identity()`.
+    pub miners: HashMap<String, MinerAvailability>,
+    #[serde(skip)]
+    saved_at: i64,
+    #[serde(skip)]
+    unsaved: bool,
+}
+
+impl AvailabilityLog {
+    pub fn load() -> Self {
+        crate::config::app_data_path(AVAILABILITY_LOG_FILE)
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+            .unwrap_or_default()
+    }
+
+    fn save(&self) {
+        if let Some(path) = crate::config::app_data_path(AVAILABILITY_LOG_FILE) {
+            if let Ok(json) = serde_json::to_string(self) {
+                if let Err(e) = fs::write(path, json) {
+                    eprintln!("✗ Failed to save availability log: {e}");
+                }
+            }
+        }
+    }
+
+    /// Writes at most every `SAVE_INTERVAL_SECS`; `flush` writes the rest on exit.
+    fn save_if_due(&mut self, now: i64) {
+        self.unsaved = true;
+        if now - self.saved_at >= SAVE_INTERVAL_SECS {
+            self.saved_at = now;
+            self.flush();
+        }
+    }
+
+    pub fn flush(&mut self) {
+        if self.unsaved {
+            self.save();
+            self.unsaved = false;
+        }
+    }
+
+    /// Records one full scan. Known miners missing from it count as offline
+    /// while their last address is still inside a saved range.
+    pub fn record_scan(
+        &mut self,
+        miners: &[MinerInfo],
+        saved_ranges: &[SavedRange],
+        now: i64,
+        max_gap_secs: i64,
+    ) {
+        let mut seen = HashSet::new();
+        for miner in miners {
+            let identity = miner.identity();
+            let entry = self.miners.entry(identity.clone()).or_default();
+            entry.ip = miner.ip.clone();
+            entry.model = miner.model.clone();
+            let uptime = miner
+                .full_data
+                .as_ref()
+                .and_then(|data| data.uptime)
+                .map(|uptime| uptime.as_secs());
+            entry.record(now, MinerState::of(miner), uptime, max_gap_secs);
+            seen.insert(identity);
+        }
+
+        for (identity, entry) in self.miners.iter_mut() {
+            let still_expected = now - entry.last_online_at <= RETIRE_AFTER_SECS
+                && saved_ranges
+                    .iter()
+                    .any(|range| scanner::range_contains_ip(&range.range, &entry.ip));
+            if !seen.contains(identity) && still_expected {
+                entry.record(now, MinerState::Offline, None, max_gap_secs);
+            }
+        }
+
+        let cutoff = now - RETAIN_SECS;
+        for entry in self.miners.values_mut() {
+            entry.spans.retain(|span| span.end >= cutoff);
+            entry.reboots.retain(|at| *at >= cutoff);
+        }
+        self.save_if_due(now);
+    }
+}
+
+/// Unix bounds `[from, to)` of an inclusive `YYYY-MM-DD` period in local time.
+pub fn period_bounds(from: &str, to: &str) -> Option<(i64, i64)> {
+    let local_midnight = |day: NaiveDate| {
+        day.and_hms_opt(0, 0, 0)?
+            .and_local_timezone(Local)
+            .earliest()
+            .map(|at| at.timestamp())
+    };
+    let from = NaiveDate::parse_from_str(from.trim(), "%Y-%m-%d").ok()?;
+    let to = NaiveDate::parse_from_str(to.trim(), "%Y-%m-%d").ok()?;
+    Some((local_midnight(from)?, local_midnight(to.succ_opt()?)?))
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SlaGrouping {
+    Miner,
+    Range,
+    Tenant,
+}
+
+impl SlaGrouping {
+    pub fn label(self) -> &'static str {
+        match self {
+            SlaGrouping::Miner => "Miner",
+            SlaGrouping::Range => "Range",
+            SlaGrouping::Tenant => "Tenant",
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SlaRow {
+    pub name: String,
+    pub miners: usize,
+    pub totals: AvailabilityTotals,
+    /// Sorted by start time.
+    pub incidents: Vec<Incident>,
+}
+
+pub fn sla_report(
+    log: &AvailabilityLog,
+    from: i64,
+    to: i64,
+    grouping: SlaGrouping,
+    saved_ranges: &[SavedRange],
+    tenant_of: &HashMap<String, String>,
+) -> Vec<SlaRow> {
+    let mut rows: BTreeMap<String, SlaRow> = BTreeMap::new();
+
+    for (identity, entry) in &log.miners {
+        let totals = entry.totals(from, to);
+        if totals.monitored_secs() == 0 {
+            continue;
+        }
+        let name = match grouping {
+            SlaGrouping::Miner => format!("{} ({})", entry.ip, identity),
+            SlaGrouping::Range => saved_ranges
+                .iter()
+                .find(|range| scanner::range_contains_ip(&range.range, &entry.ip))
+                .map(|range| range.name.clone())
+                .unwrap_or_else(|| tenants::UNASSIGNED.to_string()),
+            SlaGrouping::Tenant => tenant_of
+                .get(identity)
+                .cloned()
+                .unwrap_or_else(|| tenants::UNASSIGNED.to_string()),
+        };
+
+        let row = rows.entry(name.clone()).or_insert_with(|| SlaRow {
+            name,
+            ..Default::default()
+        });
+        row.miners += 1;
+        row.totals.add(&totals);
+        row.incidents.extend(entry.incidents(identity, from, to));
+    }
+
+    let mut rows: Vec<SlaRow> = rows.into_values().collect();
+    for row in &mut rows {
+        row.incidents.sort_by_key(|incident| incident.start);
+    }
+    rows
+}
+
+pub fn sla_report_csv(
+    from: &str,
+    to: &str,
+    grouping: SlaGrouping,
+    settings: &SlaSettings,
+    rows: &[SlaRow],
+) -> String {
+    let mut csv = format!(
+        "From,To,{},Miners,Availability (%),Target (%),Met,Downtime Hours,Paused Hours,Reboots,Incidents\n",
+        grouping.label()
+    );
+    for row in rows {
+        let availability = row.totals.availability_percent(settings.exclude_paused);
+        csv.push_str(&format!(
+            "{},{},{},{},{},{:.2},{},{:.2},{:.2},{},{}\n",
+            from,
+            to,
+            csv_field(&row.name),
+            row.miners,
+            availability.map_or(String::new(), |a| format!("{a:.3}")),
+            settings.target_percent,
+            availability.map_or("", |a| if a >= settings.target_percent {
+                "Yes"
+            } else {
+                "No"
+            }),
+            row.totals.downtime_secs() as f64 / 3600.0,
+            row.totals.paused_secs as f64 / 3600.0,
+            row.totals.reboots,
+            row.incidents.len()
+        ));
+    }
+
+    csv.push_str(&format!(
+        "\n{},IP,MAC/ID,Model,State,Start,End,Minutes,Rebooted\n",
+        grouping.label()
+    ));
+    for row in rows {
+        for incident in &row.incidents {
+            csv.push_str(&format!(
+                "{},{},{},{},{},{},{},{:.1},{}\n",
+                csv_field(&row.name),
+                incident.ip,
+                csv_field(&incident.identity),
+                csv_field(&incident.model),
+                incident.state.label(),
+                crate::snapshots::format_timestamp(incident.start),
+                crate::snapshots::format_timestamp(incident.end),
+                incident.duration_secs() as f64 / 60.0,
+                if incident.rebooted { "Yes" } else { "No" }
+            ));
+        }
+    }
+    csv
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MinerAvailability, MinerState};
+
+    #[test]
+    fn record_tracks_states_reboots_and_merges_incidents() {
+        let mut miner = MinerAvailability::default();
+        let t0 = 1_000_000;
+
+        miner.record(t0, MinerState::Hashing, Some(50_000), 300);
+        miner.record(t0 + 120, MinerState::Hashing, Some(50_120), 300);
+        // Dropped off, then came back freshly booted and warming up
+        miner.record(t0 + 240, MinerState::Offline, None, 300);
+        miner.record(t0 + 360, MinerState::Online, Some(60), 300);
+        miner.record(t0 + 480, MinerState::Hashing, Some(180), 300);
+        // App closed for an hour: not accounted
+        miner.record(t0 + 4080, MinerState::Paused, Some(3780), 300);
+        miner.record(t0 + 4200, MinerState::Paused, Some(3900), 300);
+
+        let totals = miner.totals(t0, t0 + 4200);
+        assert_eq!(totals.hashing_secs, 240);
+        assert_eq!(totals.offline_secs, 120);
+        assert_eq!(totals.online_secs, 120);
+        assert_eq!(totals.paused_secs, 120);
+        assert_eq!(totals.reboots, 1);
+        assert_eq!(totals.availability_percent(true), Some(50.0));
+
+        let incidents = miner.incidents("id", t0, t0 + 4200);
+        assert_eq!(incidents.len(), 1);
+        assert_eq!(incidents[0].state, MinerState::Offline);
+        assert_eq!((incidents[0].start, incidents[0].end), (t0 + 120, t0 + 360));
+        assert!(incidents[0].rebooted);
+    }
+}
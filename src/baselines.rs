@@ -0,0 +1,267 @@
+use crate::models::{MinerInfo, MiningModeSelection};
+use crate::watchdog;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+
+const BASELINES_FILE: &str = "model_baselines.json";
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct ModelBaseline {
+    pub model: String,
+    /// Operating mode the figures apply to, e.g. "Normal" or "Low Power".
+    #[serde(default)]
+    pub mode: String,
+    pub nominal_hashrate_th: f64,
+    pub nominal_power_w: f64,
+}
+
+impl ModelBaseline {
+    fn new(model: &str, mode: &str, hashrate_th: f64, power_w: f64) -> Self {
+        Self {
+            model: model.to_string(),
+            mode: mode.to_string(),
+            nominal_hashrate_th: hashrate_th,
+            nominal_power_w: power_w,
+        }
+    }
+
+    pub fn efficiency_w_th(&self) -> Option<f64> {
+        (self.nominal_hashrate_th > 0.0).then(|| self.nominal_power_w / self.nominal_hashrate_th)
+    }
+
+    /// The mining mode preset the entry's mode name refers to. Blank means Normal.
+    fn mining_mode(&self) -> Option<MiningModeSelection> {
+        let mode: String = self
+            .mode
+            .chars()
+            .filter(|c| c.is_ascii_alphanumeric())
+            .map(|c| c.to_ascii_lowercase())
+            .collect();
+        match mode.as_str() {
+            "" | "normal" | "default" | "stock" => Some(MiningModeSelection::Normal),
+            "low" | "lowpower" | "eco" => Some(MiningModeSelection::Low),
+            "high" | "highpower" | "performance" => Some(MiningModeSelection::High),
+            _ => None,
+        }
+    }
+}
+
+/// Manufacturer ratings, written out the first time so operators have a file to edit.
+fn default_baselines() -> Vec<ModelBaseline> {
+    vec![
+        ModelBaseline::new("S19", "Normal", 95.0, 3250.0),
+        ModelBaseline::new("S19 Pro", "Normal", 110.0, 3250.0),
+        ModelBaseline::new("S19j Pro", "Normal", 104.0, 3068.0),
+        ModelBaseline::new("S19 XP", "Normal", 140.0, 3010.0),
+        ModelBaseline::new("S21", "Normal", 200.0, 3500.0),
+        ModelBaseline::new("S21 Pro", "Normal", 234.0, 3510.0),
+        ModelBaseline::new("M30S++", "Normal", 112.0, 3472.0),
+        ModelBaseline::new("M50S", "Normal", 126.0, 3276.0),
+        ModelBaseline::new("M60S", "Normal", 186.0, 3422.0),
+    ]
+}
+
+/// Model names differ in case and spacing between firmwares ("S19 Pro" vs "s19pro").
+pub fn normalize_model(model: &str) -> String {
+    model
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric() || *c == '+')
+        .map(|c| c.to_ascii_lowercase())
+        .collect()
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct BaselineDatabase {
+    pub models: Vec<ModelBaseline>,
+}
+
+impl Default for BaselineDatabase {
+    fn default() -> Self {
+        Self {
+            models: default_baselines(),
+        }
+    }
+}
+
+impl BaselineDatabase {
+    pub fn load() -> Self {
+        let Some(path) = crate::config::app_data_path(BASELINES_FILE) else {
+            return Self::default();
+        };
+        if !path.exists() {
+            let database = Self::default();
+            database.save();
+            return database;
+        }
+        match fs::read_to_string(&path)
+            .map_err(|e| e.to_string())
+            .and_then(|contents| serde_json::from_str(&contents).map_err(|e| e.to_string()))
+        {
+            Ok(database) => database,
+            Err(e) => {
+                eprintln!("✗ Failed to read model baselines: {e}");
+                Self::default()
+            }
+        }
+    }
+
+    pub fn save(&self) {
+        if let Some(path) = crate::config::app_data_path(BASELINES_FILE) {
+            if let Ok(json) = serde_json::to_string_pretty(self) {
+                if let Err(e) = fs::write(path, json) {
+                    eprintln!("✗ Failed to save model baselines: {e}");
+                }
+            }
+        }
+    }
+
+    /// Normalized names of models with an entry for a mode other than Normal.
+    /// Only these need their mining mode read during a scan.
+    pub fn mode_specific_models(&self) -> HashSet<String> {
+        self.models
+            .iter()
+            .filter(|baseline| baseline.mining_mode() != Some(MiningModeSelection::Normal))
+            .map(|baseline| normalize_model(&baseline.model))
+            .collect()
+    }
+
+    /// The configured baseline for a model in the given mining mode, falling
+    /// back to its Normal entry, then to any entry for the model.
+    pub fn find(&self, model: &str, mode: Option<MiningModeSelection>) -> Option<&ModelBaseline> {
+        let key = normalize_model(model);
+        let matches: Vec<&ModelBaseline> = self
+            .models
+            .iter()
+            .filter(|baseline| normalize_model(&baseline.model) == key)
+            .collect();
+        let in_mode = |mode| matches.iter().find(|b| b.mining_mode() == Some(mode));
+        mode.and_then(in_mode)
+            .or_else(|| in_mode(MiningModeSelection::Normal))
+            .or_else(|| matches.first())
+            .copied()
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BaselineSource {
+    Configured,
+    /// Median of our own hashing miners of that model.
+    Learned,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Nominal {
+    pub hashrate_th: f64,
+    pub power_w: Option<f64>,
+    pub source: BaselineSource,
+}
+
+fn median_power_by_model(miners: &[MinerInfo]) -> HashMap<String, f64> {
+    let mut by_model: HashMap<&str, Vec<f64>> = HashMap::new();
+    for miner in miners {
+        if let Some(watts) = miner.wattage_w.filter(|w| *w > 0.0) {
+            by_model.entry(&miner.model).or_default().push(watts);
+        }
+    }
+    by_model
+        .into_iter()
+        .filter_map(|(model, mut values)| {
+            watchdog::median(&mut values).map(|m| (model.to_string(), m))
+        })
+        .collect()
+}
+
+/// Learned fleet medians per model, shown next to the configured figures.
+pub fn learned_baselines(miners: &[MinerInfo]) -> HashMap<String, Nominal> {
+    let power = median_power_by_model(miners);
+    watchdog::median_hashrate_by_model(miners)
+        .into_iter()
+        .map(|(model, hashrate_th)| {
+            let power_w = power.get(&model).copied();
+            (
+                model,
+                Nominal {
+                    hashrate_th,
+                    power_w,
+                    source: BaselineSource::Learned,
+                },
+            )
+        })
+        .collect()
+}
+
+/// Nominal figures for a miner: the configured entry for its model and mining
+/// mode wins, the learned median of its model fills in otherwise.
+pub fn nominal_for(
+    database: &BaselineDatabase,
+    learned: &HashMap<String, Nominal>,
+    miner: &MinerInfo,
+) -> Option<Nominal> {
+    match database
+        .find(&miner.model, miner.mining_mode)
+        .filter(|b| b.nominal_hashrate_th > 0.0)
+    {
+        Some(baseline) => Some(Nominal {
+            hashrate_th: baseline.nominal_hashrate_th,
+            power_w: Some(baseline.nominal_power_w).filter(|w| *w > 0.0),
+            source: BaselineSource::Configured,
+        }),
+        None => learned.get(&miner.model).copied(),
+    }
+}
+
+/// Hashrate as a percentage of `nominal_for` for every miner, keyed by IP.
+pub fn percent_of_nominal_by_ip(
+    database: &BaselineDatabase,
+    miners: &[MinerInfo],
+) -> HashMap<String, f64> {
+    let learned = learned_baselines(miners);
+    miners
+        .iter()
+        .filter_map(|miner| {
+            let nominal = nominal_for(database, &learned, miner)?;
+            let percent = miner.hashrate_th.unwrap_or(0.0) / nominal.hashrate_th * 100.0;
+            Some((miner.ip.clone(), percent))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BaselineDatabase, ModelBaseline};
+    use crate::models::MiningModeSelection;
+
+    #[test]
+    fn find_matches_loosely_and_by_mining_mode() {
+        let database = BaselineDatabase {
+            models: vec![
+                ModelBaseline::new("S21", "Low Power", 170.0, 2800.0),
+                ModelBaseline::new("S21", "Normal", 200.0, 3500.0),
+                ModelBaseline::new("S19 Pro", "", 110.0, 3250.0),
+            ],
+        };
+
+        let hashrate = |model, mode| database.find(model, mode).map(|b| b.nominal_hashrate_th);
+        assert_eq!(hashrate("s21", None), Some(200.0));
+        assert_eq!(hashrate("S21", Some(MiningModeSelection::Low)), Some(170.0));
+        // No High entry, so the Normal one applies
+        assert_eq!(
+            hashrate("S21", Some(MiningModeSelection::High)),
+            Some(200.0)
+        );
+        assert_eq!(database.find("Antminer S19Pro", None), None);
+        assert_eq!(
+            database
+                .find("S19PRO", Some(MiningModeSelection::Low))
+                .unwrap()
+                .efficiency_w_th(),
+            Some(3250.0 / 110.0)
+        );
+        assert!(database.find("M60S", None).is_none());
+        assert_eq!(
+            database.mode_specific_models(),
+            ["s21".to_string()].into_iter().collect()
+        );
+    }
+}
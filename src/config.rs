@@ -1,5 +1,17 @@
-use crate::models::{MinerOptionSettings, SavedRange};
+use crate::availability::SlaSettings;
+use crate::credentials::MinerCredential;
+use crate::health::HealthThresholds;
+use crate::models::{MinerLocation, MinerOptionSettings, OptionProfile, SavedRange};
+use crate::power_cap::PowerCapSettings;
+use crate::profitability::ProfitabilitySettings;
+use crate::rollout::RolloutSettings;
+use crate::safety::SafetySettings;
+use crate::schedule::ScheduledAction;
+use crate::tenants::Tenant;
+use crate::thermal::ThermalSettings;
+use crate::watchdog::WatchdogSettings;
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fs::{self, create_dir_all};
 use std::path::PathBuf;
 
@@ -21,6 +33,41 @@ pub struct AppConfig {
     pub connectivity_retries: u32,
     #[serde(default)]
     pub global_options: MinerOptionSettings,
+    #[serde(default)]
+    pub rollout_settings: RolloutSettings,
+    #[serde(default)]
+    pub option_profiles: Vec<OptionProfile>,
+    #[serde(default)]
+    pub miner_locations: HashMap<String, MinerLocation>,
+    #[serde(default)]
+    pub scheduled_actions: Vec<ScheduledAction>,
+    #[serde(default)]
+    pub power_cap: PowerCapSettings,
+    #[serde(default)]
+    pub thermal: ThermalSettings,
+    #[serde(default)]
+    pub watchdog: WatchdogSettings,
+    #[serde(default)]
+    pub health_thresholds: HealthThresholds,
+    #[serde(default)]
+    pub profitability: ProfitabilitySettings,
+    #[serde(default)]
+    pub tenants: Vec<Tenant>,
+    #[serde(default)]
+    pub sla: SlaSettings,
+    #[serde(default)]
+    pub safety: SafetySettings,
+    /// `MinerInfo::identity()` of miners that bulk actions skip.
+    #[serde(default)]
+    pub protected_miners: HashSet<String>,
+    /// Viewer mode: scanning and monitoring only, no writes to miners. Also `--read-only`.
+    #[serde(default)]
+    pub read_only: bool,
+    #[serde(default)]
+    pub miner_credentials: Vec<MinerCredential>,
+    /// Model -> firmware version every miner of that model should run.
+    #[serde(default)]
+    pub approved_firmware: BTreeMap<String, String>,
 }
 
 fn default_refresh_interval() -> u64 {
@@ -43,20 +90,43 @@ fn default_connectivity_retries() -> u32 {
     2
 }
 
-fn get_config_path() -> Option<PathBuf> {
+pub fn app_data_path(file_name: &str) -> Option<PathBuf> {
     dirs::home_dir().map(|home| {
         let config_dir = home.join(CONFIG_DIR);
         let _ = create_dir_all(&config_dir);
-        config_dir.join(CONFIG_FILE)
+        config_dir.join(file_name)
     })
 }
 
+fn get_config_path() -> Option<PathBuf> {
+    app_data_path(CONFIG_FILE)
+}
+
+fn upgrade_legacy_settings(value: &mut serde_json::Value) {
+    if let Some(global_options) = value.get_mut("global_options") {
+        MinerOptionSettings::upgrade_legacy_json(global_options);
+    }
+    if let Some(profiles) = value
+        .get_mut("option_profiles")
+        .and_then(|p| p.as_array_mut())
+    {
+        for profile in profiles {
+            if let Some(settings) = profile.get_mut("settings") {
+                MinerOptionSettings::upgrade_legacy_json(settings);
+            }
+        }
+    }
+}
+
 pub fn load_config() -> AppConfig {
     if let Some(config_path) = get_config_path() {
         if let Ok(contents) = fs::read_to_string(config_path) {
             // Try loading as new config format
-            if let Ok(config) = serde_json::from_str::<AppConfig>(&contents) {
-                return config;
+            if let Ok(mut value) = serde_json::from_str::<serde_json::Value>(&contents) {
+                upgrade_legacy_settings(&mut value);
+                if let Ok(config) = serde_json::from_value::<AppConfig>(value) {
+                    return config;
+                }
             }
             // Fall back to old format (just Vec<SavedRange>)
             if let Ok(ranges) = serde_json::from_str::<Vec<SavedRange>>(&contents) {
@@ -68,6 +138,22 @@ pub fn load_config() -> AppConfig {
                     connectivity_timeout_secs: default_connectivity_timeout(),
                     connectivity_retries: default_connectivity_retries(),
                     global_options: MinerOptionSettings::default(),
+                    rollout_settings: RolloutSettings::default(),
+                    option_profiles: Vec::new(),
+                    miner_locations: HashMap::new(),
+                    scheduled_actions: Vec::new(),
+                    power_cap: PowerCapSettings::default(),
+                    thermal: ThermalSettings::default(),
+                    watchdog: WatchdogSettings::default(),
+                    health_thresholds: HealthThresholds::default(),
+                    profitability: ProfitabilitySettings::default(),
+                    tenants: Vec::new(),
+                    sla: SlaSettings::default(),
+                    safety: SafetySettings::default(),
+                    protected_miners: HashSet::new(),
+                    read_only: false,
+                    miner_credentials: Vec::new(),
+                    approved_firmware: BTreeMap::new(),
                 };
             }
         }
@@ -0,0 +1,332 @@
+use crate::models::{MinerInfo, SavedRange};
+use crate::scanner;
+use crate::secrets::{self, SecretKind, SecretStore};
+use asic_rs::{Miner, MinerAuth, MinerFactory};
+use asic_rs_core::data::miner::MinerData;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::net::IpAddr;
+use std::sync::{Mutex, OnceLock, RwLock};
+
+/// What a credential applies to. A miner entry beats a range entry, which
+/// beats a model entry.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub enum CredentialScope {
+    Model(String),
+    /// Name of a saved range.
+    Range(String),
+    /// `MinerInfo::identity()` or IP.
+    Miner(String),
+}
+
+impl CredentialScope {
+    pub fn kind_label(&self) -> &'static str {
+        match self {
+            CredentialScope::Model(_) => "Model",
+            CredentialScope::Range(_) => "Range",
+            CredentialScope::Miner(_) => "Miner",
+        }
+    }
+
+    pub fn value(&self) -> &str {
+        match self {
+            CredentialScope::Model(value)
+            | CredentialScope::Range(value)
+            | CredentialScope::Miner(value) => value,
+        }
+    }
+
+    fn precedence(&self) -> u8 {
+        match self {
+            CredentialScope::Model(_) => 0,
+            CredentialScope::Range(_) => 1,
+            CredentialScope::Miner(_) => 2,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct MinerCredential {
+    pub scope: CredentialScope,
+    pub username: String,
+    /// Plain text while in memory, a secret reference in the config once a
+    /// master passphrase is set.
+    pub password: String,
+}
+
+impl Default for MinerCredential {
+    fn default() -> Self {
+        Self {
+            scope: CredentialScope::Model(String::new()),
+            username: "root".to_string(),
+            password: String::new(),
+        }
+    }
+}
+
+impl MinerCredential {
+    fn secret_label(&self) -> String {
+        format!("{} {}", self.scope.kind_label(), self.scope.value())
+    }
+
+    /// The password is still a reference because the secret store is locked.
+    pub fn is_locked(&self) -> bool {
+        secrets::is_secret_ref(&self.password)
+    }
+
+    fn login(&self) -> Option<Login> {
+        (!self.is_locked()).then(|| Login {
+            username: self.username.clone(),
+            password: self.password.clone(),
+        })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Login {
+    pub username: String,
+    pub password: String,
+}
+
+/// Most specific usable credential for a miner, if any.
+pub fn resolve<'a>(
+    credentials: &'a [MinerCredential],
+    miner: &MinerInfo,
+    ranges: &[SavedRange],
+) -> Option<&'a MinerCredential> {
+    resolve_for(
+        credentials,
+        &miner.ip,
+        &miner.identity(),
+        Some(&miner.model),
+        ranges,
+    )
+}
+
+/// `resolve` for a miner known only by address, identity and, if reported
+/// yet, model.
+fn resolve_for<'a>(
+    credentials: &'a [MinerCredential],
+    ip: &str,
+    identity: &str,
+    model: Option<&str>,
+    ranges: &[SavedRange],
+) -> Option<&'a MinerCredential> {
+    credentials
+        .iter()
+        .filter(|credential| !credential.is_locked())
+        .filter(|credential| match &credential.scope {
+            CredentialScope::Model(name) => model.is_some_and(|m| name.eq_ignore_ascii_case(m)),
+            CredentialScope::Range(name) => ranges
+                .iter()
+                .any(|r| r.name == *name && scanner::range_contains_ip(&r.range, ip)),
+            CredentialScope::Miner(id) => id == identity || id == ip,
+        })
+        .max_by_key(|credential| credential.scope.precedence())
+}
+
+pub fn seal_passwords(
+    credentials: &mut [MinerCredential],
+    store: &mut SecretStore,
+) -> HashSet<String> {
+    let mut referenced = HashSet::new();
+    for credential in credentials {
+        let label = credential.secret_label();
+        referenced.extend(secrets::seal_value(
+            &mut credential.password,
+            SecretKind::MinerCredential,
+            &label,
+            store,
+        ));
+    }
+    referenced
+}
+
+/// Returns how many passwords are still locked.
+pub fn reveal_passwords(credentials: &mut [MinerCredential], store: &SecretStore) -> usize {
+    credentials
+        .iter_mut()
+        .map(|credential| secrets::reveal_value(&mut credential.password, store))
+        .filter(|revealed| !revealed)
+        .count()
+}
+
+/// Stored credentials, read by every connection.
+#[derive(Default)]
+struct Registry {
+    credentials: Vec<MinerCredential>,
+    ranges: Vec<SavedRange>,
+    /// IP -> identity and model from the last scan.
+    seen: HashMap<String, (String, String)>,
+}
+
+fn registry() -> &'static RwLock<Registry> {
+    static REGISTRY: OnceLock<RwLock<Registry>> = OnceLock::new();
+    REGISTRY.get_or_init(Default::default)
+}
+
+fn auth_failures() -> &'static Mutex<HashMap<String, String>> {
+    static FAILURES: OnceLock<Mutex<HashMap<String, String>>> = OnceLock::new();
+    FAILURES.get_or_init(Default::default)
+}
+
+/// Takes the credential list and ranges connections resolve against, and
+/// who the last scan found at each IP.
+pub fn update_registry(
+    credentials: &[MinerCredential],
+    miners: &[MinerInfo],
+    ranges: &[SavedRange],
+) {
+    let seen = miners
+        .iter()
+        .map(|miner| (miner.ip.clone(), (miner.identity(), miner.model.clone())))
+        .collect();
+    *registry().write().unwrap() = Registry {
+        credentials: credentials.to_vec(),
+        ranges: ranges.to_vec(),
+        seen,
+    };
+}
+
+/// Whether a credential can only be matched with what the miner reports,
+/// i.e. its model or MAC address rather than its IP.
+fn needs_miner_report(credential: &MinerCredential) -> bool {
+    match &credential.scope {
+        CredentialScope::Model(_) => true,
+        CredentialScope::Range(_) => false,
+        CredentialScope::Miner(id) => id.parse::<IpAddr>().is_err(),
+    }
+}
+
+/// Hands the most specific login to a miner from the factory. A miner the
+/// last scan saw is matched by its identity and model from that scan. One it
+/// has not seen, such as a new miner or one that changed IP, is asked for its
+/// data only when nothing matches its address and a model or MAC entry could.
+/// That data is returned so the caller does not fetch it again.
+pub async fn authenticate(miner: &mut Box<dyn Miner>) -> Option<MinerData> {
+    let ip = miner.get_ip().to_string();
+    let (credentials, ranges, seen) = {
+        let registry = registry().read().unwrap();
+        (
+            registry.credentials.clone(),
+            registry.ranges.clone(),
+            registry.seen.get(&ip).cloned(),
+        )
+    };
+    if credentials.is_empty() {
+        return None;
+    }
+
+    let mut fetched = None;
+    let credential = match &seen {
+        Some((identity, model)) => resolve_for(&credentials, &ip, identity, Some(model), &ranges),
+        None => match resolve_for(&credentials, &ip, &ip, None, &ranges) {
+            Some(credential) => Some(credential),
+            None if credentials.iter().any(needs_miner_report) => {
+                let data = miner.get_data().await;
+                let identity = data.mac.map_or_else(|| ip.clone(), |mac| mac.to_string());
+                let model = data.device_info.model.to_string();
+                fetched = Some(data);
+                resolve_for(&credentials, &ip, &identity, Some(&model), &ranges)
+            }
+            None => None,
+        },
+    };
+
+    if let Some(login) = credential.and_then(MinerCredential::login) {
+        miner.set_auth(MinerAuth::new(login.username, login.password));
+    }
+    fetched
+}
+
+/// Connects to `ip` with its registered credentials.
+pub async fn connect(ip: &str) -> Result<Box<dyn Miner>, String> {
+    let parsed_ip = ip
+        .parse()
+        .map_err(|e| format!("Invalid IP address {ip}: {e}"))?;
+    let Some(mut miner) = MinerFactory::new()
+        .get_miner(parsed_ip)
+        .await
+        .map_err(|e| format!("Failed to connect to {ip}: {e}"))?
+    else {
+        return Err(format!("No supported miner found at {ip}"));
+    };
+    authenticate(&mut miner).await;
+    Ok(miner)
+}
+
+fn is_auth_error(message: &str) -> bool {
+    let message = message.to_lowercase();
+    [
+        "401",
+        "403",
+        "unauthorized",
+        "forbidden",
+        "authentication",
+        "auth failed",
+        "invalid password",
+        "login failed",
+    ]
+    .iter()
+    .any(|needle| message.contains(needle))
+}
+
+/// Remembers whether the last call to a miner was rejected for its credentials.
+pub fn record_outcome<T>(ip: &str, result: &Result<T, String>) {
+    let mut failures = auth_failures().lock().unwrap();
+    match result {
+        Err(err) if is_auth_error(err) => {
+            failures.insert(ip.to_string(), err.clone());
+        }
+        Err(_) => {}
+        Ok(_) => {
+            failures.remove(ip);
+        }
+    }
+}
+
+/// IP -> error of miners whose last call failed authentication.
+pub fn auth_failures_snapshot() -> HashMap<String, String> {
+    auth_failures().lock().unwrap().clone()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{resolve, resolve_for, CredentialScope, MinerCredential};
+    use crate::models::{MinerInfo, SavedRange};
+
+    #[test]
+    fn most_specific_unlocked_credential_wins() {
+        let credential = |scope, password: &str| MinerCredential {
+            scope,
+            username: "root".to_string(),
+            password: password.to_string(),
+        };
+        let credentials = vec![
+            credential(CredentialScope::Model("s19".to_string()), "model"),
+            credential(CredentialScope::Range("Row A".to_string()), "range"),
+            credential(CredentialScope::Miner("10.0.0.7".to_string()), "secret:abc"),
+        ];
+        let ranges = vec![SavedRange {
+            name: "Row A".to_string(),
+            range: "10.0.0.1-50".to_string(),
+        }];
+        let miner = |ip: &str| MinerInfo {
+            ip: ip.to_string(),
+            model: "S19".to_string(),
+            ..Default::default()
+        };
+
+        let password = |ip| resolve(&credentials, &miner(ip), &ranges).map(|c| c.password.as_str());
+        // The locked miner entry is skipped in favour of the range
+        assert_eq!(password("10.0.0.7"), Some("range"));
+        assert_eq!(password("10.0.1.7"), Some("model"));
+        // Before the miner has reported a model only its address can match
+        assert!(resolve_for(&credentials, "10.0.1.7", "10.0.1.7", None, &ranges).is_none());
+        assert_eq!(
+            resolve_for(&credentials, "10.0.0.9", "10.0.0.9", None, &ranges)
+                .map(|c| c.password.as_str()),
+            Some("range")
+        );
+    }
+}
@@ -0,0 +1,363 @@
+use crate::export::csv_field;
+use crate::models::{MinerInfo, SavedRange};
+use crate::scanner;
+use crate::tenants;
+use chrono::{Local, NaiveDate, TimeZone};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+
+const ENERGY_LEDGER_FILE: &str = "energy_ledger.json";
+/// Days older than this are dropped so the ledger stays small.
+const RETAIN_DAYS: i64 = 800;
+/// Shortest interval between samples that is still bridged, whatever the scan interval.
+pub const MIN_BRIDGED_GAP_SECS: i64 = 600;
+/// The whole ledger is rewritten on save, so scans only save this often.
+const SAVE_INTERVAL_SECS: i64 = 10 * 60;
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct DayEnergy {
+    pub wh: f64,
+    /// Seconds backed by consecutive power samples.
+    pub covered_secs: i64,
+    /// Seconds the miner was unseen or reported no power.
+    pub gap_secs: i64,
+    /// Integrated hashrate, for average hashrate over a period.
+    #[serde(default)]
+    pub th_hours: f64,
+    /// Seconds the miner was hashing at both ends of a sampled interval.
+    #[serde(default)]
+    pub hashing_secs: i64,
+}
+
+impl DayEnergy {
+    fn add(&mut self, other: &DayEnergy) {
+        self.wh += other.wh;
+        self.covered_secs += other.covered_secs;
+        self.gap_secs += other.gap_secs;
+        self.th_hours += other.th_hours;
+        self.hashing_secs += other.hashing_secs;
+    }
+
+    pub fn tracked_secs(&self) -> i64 {
+        self.covered_secs + self.gap_secs
+    }
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct MinerEnergy {
+    pub ip: String,
+    pub model: String,
+    pub last_seen_at: i64,
+    pub last_watts: Option<f64>,
+    #[serde(default)]
+    pub last_hashrate_th: Option<f64>,
+    /// Keyed by local date, `YYYY-MM-DD`.
+    pub days: BTreeMap<String, DayEnergy>,
+}
+
+fn local_day(timestamp: i64) -> Option<NaiveDate> {
+    Local
+        .timestamp_opt(timestamp, 0)
+        .single()
+        .map(|at| at.date_naive())
+}
+
+fn next_local_midnight(day: NaiveDate) -> Option<i64> {
+    day.succ_opt()?
+        .and_hms_opt(0, 0, 0)?
+        .and_local_timezone(Local)
+        .earliest()
+        .map(|at| at.timestamp())
+}
+
+/// Splits `[start, end)` at local midnights, calling `f(day, seconds)` per piece.
+fn split_by_day(start: i64, end: i64, mut f: impl FnMut(String, i64)) {
+    let mut from = start;
+    while from < end {
+        let Some(day) = local_day(from) else {
+            return;
+        };
+        let until = next_local_midnight(day)
+            .filter(|midnight| *midnight > from)
+            .map_or(end, |midnight| midnight.min(end));
+        f(day.format("%Y-%m-%d").to_string(), until - from);
+        from = until;
+    }
+}
+
+impl MinerEnergy {
+    /// Integrates power between the previous sample and this one. Intervals
+    /// longer than `max_gap_secs`, or with a missing reading at either end,
+    /// are counted as gaps rather than guessed.
+    pub fn record(
+        &mut self,
+        now: i64,
+        watts: Option<f64>,
+        hashrate_th: Option<f64>,
+        max_gap_secs: i64,
+    ) {
+        let elapsed = now - self.last_seen_at;
+        if self.last_seen_at > 0 && elapsed > 0 {
+            match (self.last_watts, watts) {
+                (Some(previous), Some(current)) if elapsed <= max_gap_secs => {
+                    let average_watts = (previous + current) / 2.0;
+                    let previous_th = self.last_hashrate_th.unwrap_or(0.0);
+                    let current_th = hashrate_th.unwrap_or(0.0);
+                    let average_th = (previous_th + current_th) / 2.0;
+                    let hashing = previous_th > 0.0 && current_th > 0.0;
+                    split_by_day(self.last_seen_at, now, |day, secs| {
+                        let entry = self.days.entry(day).or_default();
+                        entry.wh += average_watts * secs as f64 / 3600.0;
+                        entry.covered_secs += secs;
+                        entry.th_hours += average_th * secs as f64 / 3600.0;
+                        if hashing {
+                            entry.hashing_secs += secs;
+                        }
+                    });
+                }
+                _ => split_by_day(self.last_seen_at, now, |day, secs| {
+                    self.days.entry(day).or_default().gap_secs += secs;
+                }),
+            }
+        }
+        if self.last_seen_at == 0 || elapsed > 0 {
+            self.last_seen_at = now;
+            self.last_watts = watts.filter(|w| w.is_finite() && *w >= 0.0);
+            self.last_hashrate_th = hashrate_th;
+        }
+    }
+
+    /// Totals for the inclusive `YYYY-MM-DD` range `from..=to`.
+    pub fn period_totals(&self, from: &str, to: &str) -> DayEnergy {
+        let mut total = DayEnergy::default();
+        for (_, day) in self.days.range(from.to_string()..=to.to_string()) {
+            total.add(day);
+        }
+        total
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct EnergyLedger {
+    /// Keyed by `MinerInfo::identity()`.
+    pub miners: HashMap<String, MinerEnergy>,
+    #[serde(skip)]
+    saved_at: i64,
+    #[serde(skip)]
+    unsaved: bool,
+}
+
+impl EnergyLedger {
+    pub fn load() -> Self {
+        crate::config::app_data_path(ENERGY_LEDGER_FILE)
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+            .unwrap_or_default()
+    }
+
+    fn save(&self) {
+        if let Some(path) = crate::config::app_data_path(ENERGY_LEDGER_FILE) {
+            if let Ok(json) = serde_json::to_string(self) {
+                if let Err(e) = fs::write(path, json) {
+                    eprintln!("✗ Failed to save energy ledger: {e}");
+                }
+            }
+        }
+    }
+
+    /// Writes at most every `SAVE_INTERVAL_SECS`; `flush` writes the rest on exit.
+    fn save_if_due(&mut self, now: i64) {
+        self.unsaved = true;
+        if now - self.saved_at >= SAVE_INTERVAL_SECS {
+            self.saved_at = now;
+            self.flush();
+        }
+    }
+
+    pub fn flush(&mut self) {
+        if self.unsaved {
+            self.save();
+            self.unsaved = false;
+        }
+    }
+
+    /// Adds one power sample per scanned miner.
+    pub fn record_scan(&mut self, miners: &[MinerInfo], now: i64, max_gap_secs: i64) {
+        for miner in miners {
+            let entry = self.miners.entry(miner.identity()).or_default();
+            entry.ip = miner.ip.clone();
+            entry.model = miner.model.clone();
+            entry.record(now, miner.wattage_w, miner.hashrate_th, max_gap_secs);
+        }
+
+        if let Some(cutoff) = local_day(now - RETAIN_DAYS * 86_400) {
+            let cutoff = cutoff.format("%Y-%m-%d").to_string();
+            for entry in self.miners.values_mut() {
+                entry.days.retain(|day, _| *day >= cutoff);
+            }
+        }
+        self.save_if_due(now);
+    }
+
+    /// Months with any recorded data, newest first, as `YYYY-MM`.
+    pub fn months(&self) -> Vec<String> {
+        let months: HashSet<String> = self
+            .miners
+            .values()
+            .flat_map(|entry| entry.days.keys().map(|day| day[..7].to_string()))
+            .collect();
+        let mut months: Vec<String> = months.into_iter().collect();
+        months.sort_by(|a, b| b.cmp(a));
+        months
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StatementGrouping {
+    Miner,
+    Range,
+    Tenant,
+}
+
+impl StatementGrouping {
+    pub fn label(self) -> &'static str {
+        match self {
+            StatementGrouping::Miner => "Miner",
+            StatementGrouping::Range => "Range",
+            StatementGrouping::Tenant => "Tenant",
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct StatementRow {
+    pub name: String,
+    pub miners: usize,
+    pub kwh: f64,
+    pub covered_secs: i64,
+    pub gap_secs: i64,
+}
+
+impl StatementRow {
+    /// Share of the tracked time that is backed by power samples.
+    pub fn coverage_percent(&self) -> f64 {
+        let tracked = self.covered_secs + self.gap_secs;
+        if tracked == 0 {
+            0.0
+        } else {
+            self.covered_secs as f64 / tracked as f64 * 100.0
+        }
+    }
+}
+
+pub fn monthly_statement(
+    ledger: &EnergyLedger,
+    month: &str,
+    grouping: StatementGrouping,
+    saved_ranges: &[SavedRange],
+    tenant_of: &HashMap<String, String>,
+) -> Vec<StatementRow> {
+    let mut rows: BTreeMap<String, StatementRow> = BTreeMap::new();
+
+    for (identity, entry) in &ledger.miners {
+        let totals = entry.period_totals(&format!("{month}-01"), &format!("{month}-31"));
+        if totals.covered_secs == 0 && totals.gap_secs == 0 {
+            continue;
+        }
+        let name = match grouping {
+            StatementGrouping::Miner => format!("{} ({})", entry.ip, identity),
+            StatementGrouping::Range => saved_ranges
+                .iter()
+                .find(|range| scanner::range_contains_ip(&range.range, &entry.ip))
+                .map(|range| range.name.clone())
+                .unwrap_or_else(|| tenants::UNASSIGNED.to_string()),
+            StatementGrouping::Tenant => tenant_of
+                .get(identity)
+                .cloned()
+                .unwrap_or_else(|| tenants::UNASSIGNED.to_string()),
+        };
+
+        let row = rows.entry(name.clone()).or_insert_with(|| StatementRow {
+            name,
+            ..Default::default()
+        });
+        row.miners += 1;
+        row.kwh += totals.wh / 1000.0;
+        row.covered_secs += totals.covered_secs;
+        row.gap_secs += totals.gap_secs;
+    }
+
+    rows.into_values().collect()
+}
+
+pub fn statement_csv(month: &str, grouping: StatementGrouping, rows: &[StatementRow]) -> String {
+    let mut csv = format!(
+        "Month,{},Miners,Energy (kWh),Covered Hours,Gap Hours,Coverage (%)\n",
+        grouping.label()
+    );
+    for row in rows {
+        csv.push_str(&format!(
+            "{},{},{},{:.3},{:.2},{:.2},{:.1}\n",
+            month,
+            csv_field(&row.name),
+            row.miners,
+            row.kwh,
+            row.covered_secs as f64 / 3600.0,
+            row.gap_secs as f64 / 3600.0,
+            row.coverage_percent()
+        ));
+    }
+    let total_kwh: f64 = rows.iter().map(|row| row.kwh).sum();
+    csv.push_str(&format!("{month},Total,,{total_kwh:.3},,,\n"));
+    csv
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{split_by_day, MinerEnergy};
+    use chrono::{Local, TimeZone};
+
+    #[test]
+    fn record_integrates_power_and_counts_gaps() {
+        let start = Local
+            .with_ymd_and_hms(2026, 10, 5, 12, 0, 0)
+            .unwrap()
+            .timestamp();
+        let mut energy = MinerEnergy::default();
+
+        energy.record(start, Some(3000.0), Some(100.0), 900);
+        energy.record(start + 600, Some(3600.0), Some(120.0), 900);
+        // Offline for an hour: not bridged
+        energy.record(start + 4200, Some(3600.0), Some(120.0), 900);
+        // No power reading: the next interval is a gap too
+        energy.record(start + 4800, None, None, 900);
+        energy.record(start + 5400, Some(3600.0), Some(120.0), 900);
+
+        let day = &energy.days["2026-10-05"];
+        assert!((day.wh - 3300.0 * 600.0 / 3600.0).abs() < 1e-9);
+        assert_eq!(day.covered_secs, 600);
+        assert_eq!(day.gap_secs, 3600 + 600 + 600);
+        assert_eq!(day.hashing_secs, 600);
+        assert!((day.th_hours - 110.0 * 600.0 / 3600.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn split_by_day_breaks_at_local_midnight() {
+        let before_midnight = Local
+            .with_ymd_and_hms(2026, 10, 5, 23, 30, 0)
+            .unwrap()
+            .timestamp();
+        let mut pieces = Vec::new();
+        split_by_day(before_midnight, before_midnight + 3600, |day, secs| {
+            pieces.push((day, secs))
+        });
+        assert_eq!(
+            pieces,
+            vec![
+                ("2026-10-05".to_string(), 1800),
+                ("2026-10-06".to_string(), 1800)
+            ]
+        );
+    }
+}
@@ -0,0 +1,22 @@
+/// Quotes a CSV field when it contains a comma, quote or line break, doubling
+/// any quotes inside. Every CSV export writes its text fields through this.
+pub fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::csv_field;
+
+    #[test]
+    fn quotes_only_fields_that_need_it() {
+        assert_eq!(csv_field("S19 Pro"), "S19 Pro");
+        assert_eq!(csv_field("Row A, left"), "\"Row A, left\"");
+        assert_eq!(csv_field("the \"big\" one"), "\"the \"\"big\"\" one\"");
+        assert_eq!(csv_field("two\nlines"), "\"two\nlines\"");
+    }
+}
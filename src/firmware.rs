@@ -0,0 +1,195 @@
+use crate::export::csv_field;
+use crate::models::{MinerInfo, MinerLocation};
+use std::collections::{BTreeMap, HashMap};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Compliance {
+    Approved,
+    NotApproved {
+        approved: String,
+    },
+    /// No approved version set for the model.
+    NoPolicy,
+}
+
+#[derive(Clone, Debug)]
+pub struct FirmwareGroup {
+    pub model: String,
+    pub firmware_version: String,
+    pub control_board: String,
+    pub ips: Vec<String>,
+    pub compliance: Compliance,
+}
+
+fn normalize_version(version: &str) -> String {
+    version.trim().to_ascii_lowercase()
+}
+
+/// Approved version for `model`, matched case-insensitively.
+fn approved_for<'a>(approved: &'a BTreeMap<String, String>, model: &str) -> Option<&'a str> {
+    approved
+        .iter()
+        .find(|(m, version)| m.eq_ignore_ascii_case(model) && !version.trim().is_empty())
+        .map(|(_, version)| version.trim())
+}
+
+pub fn check_compliance(miner: &MinerInfo, approved: &BTreeMap<String, String>) -> Compliance {
+    match approved_for(approved, &miner.model) {
+        None => Compliance::NoPolicy,
+        Some(version)
+            if normalize_version(version) == normalize_version(&miner.firmware_version) =>
+        {
+            Compliance::Approved
+        }
+        Some(version) => Compliance::NotApproved {
+            approved: version.to_string(),
+        },
+    }
+}
+
+/// Miners grouped by model × firmware version × control board, in that order.
+pub fn firmware_inventory(
+    miners: &[MinerInfo],
+    approved: &BTreeMap<String, String>,
+) -> Vec<FirmwareGroup> {
+    let mut groups: BTreeMap<(String, String, String), Vec<&MinerInfo>> = BTreeMap::new();
+    for miner in miners {
+        groups
+            .entry((
+                miner.model.clone(),
+                miner.firmware_version.clone(),
+                miner.control_board.clone(),
+            ))
+            .or_default()
+            .push(miner);
+    }
+
+    groups
+        .into_iter()
+        .map(
+            |((model, firmware_version, control_board), members)| FirmwareGroup {
+                compliance: check_compliance(members[0], approved),
+                ips: members.iter().map(|m| m.ip.clone()).collect(),
+                model,
+                firmware_version,
+                control_board,
+            },
+        )
+        .collect()
+}
+
+/// Miners whose firmware differs from the approved version of their model,
+/// sorted by model and current version so upgrade batches line up.
+pub fn non_compliant<'a>(
+    miners: &'a [MinerInfo],
+    approved: &BTreeMap<String, String>,
+) -> Vec<(&'a MinerInfo, String)> {
+    let mut entries: Vec<(&MinerInfo, String)> = miners
+        .iter()
+        .filter_map(|miner| match check_compliance(miner, approved) {
+            Compliance::NotApproved { approved } => Some((miner, approved)),
+            _ => None,
+        })
+        .collect();
+    entries.sort_by(|(a, _), (b, _)| {
+        (&a.model, &a.firmware_version, &a.control_board, &a.ip).cmp(&(
+            &b.model,
+            &b.firmware_version,
+            &b.control_board,
+            &b.ip,
+        ))
+    });
+    entries
+}
+
+/// The non-compliant list with rack and slot, for planning upgrade batches.
+pub fn non_compliant_csv(
+    entries: &[(&MinerInfo, String)],
+    locations: &HashMap<String, MinerLocation>,
+) -> String {
+    let mut csv = String::from(
+        "IP,Hostname,Model,Control Board,Firmware Version,Approved Version,Rack,Slot\n",
+    );
+    for (miner, approved) in entries {
+        let location = locations
+            .get(&miner.identity())
+            .cloned()
+            .unwrap_or_default();
+        let row = [
+            miner.ip.as_str(),
+            &miner.hostname,
+            &miner.model,
+            &miner.control_board,
+            &miner.firmware_version,
+            approved,
+            &location.rack,
+            &location.slot,
+        ];
+        let row: Vec<String> = row.iter().map(|field| csv_field(field)).collect();
+        csv.push_str(&row.join(","));
+        csv.push('\n');
+    }
+    csv
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{firmware_inventory, non_compliant, non_compliant_csv, Compliance};
+    use crate::models::MinerInfo;
+    use std::collections::{BTreeMap, HashMap};
+
+    fn miner(ip: &str, model: &str, firmware: &str, board: &str) -> MinerInfo {
+        MinerInfo {
+            ip: ip.to_string(),
+            model: model.to_string(),
+            firmware_version: firmware.to_string(),
+            control_board: board.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn groups_by_model_firmware_and_board_and_flags_unapproved() {
+        let miners = vec![
+            miner("10.0.0.1", "S19", "2024.01", "Xilinx"),
+            miner("10.0.0.2", "S19", "2024.01", "Xilinx"),
+            miner("10.0.0.3", "S19", "2023.06", "Xilinx"),
+            miner("10.0.0.4", "S19", "2024.01", "BeagleBone"),
+            miner("10.0.0.5", "M30S", "1.0", "H6"),
+        ];
+        let approved = BTreeMap::from([("s19".to_string(), " 2024.01 ".to_string())]);
+
+        let groups = firmware_inventory(&miners, &approved);
+        let summary: Vec<(&str, &str, &str, usize)> = groups
+            .iter()
+            .map(|g| {
+                (
+                    g.model.as_str(),
+                    g.firmware_version.as_str(),
+                    g.control_board.as_str(),
+                    g.ips.len(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("M30S", "1.0", "H6", 1),
+                ("S19", "2023.06", "Xilinx", 1),
+                ("S19", "2024.01", "BeagleBone", 1),
+                ("S19", "2024.01", "Xilinx", 2),
+            ]
+        );
+        assert_eq!(groups[0].compliance, Compliance::NoPolicy);
+        assert_eq!(groups[3].compliance, Compliance::Approved);
+
+        let outdated = non_compliant(&miners, &approved);
+        assert_eq!(outdated.len(), 1);
+        assert_eq!(outdated[0].0.ip, "10.0.0.3");
+        let csv = non_compliant_csv(&outdated, &HashMap::new());
+        assert_eq!(
+            csv.lines().nth(1),
+            Some("10.0.0.3,,S19,Xilinx,2023.06,2024.01,,")
+        );
+    }
+}
@@ -0,0 +1,345 @@
+use crate::export::csv_field;
+use crate::models::{MinerInfo, MinerLocation};
+use crate::watchdog;
+use asic_rs_core::data::board::BoardData;
+use asic_rs_core::data::hashrate::HashRateUnit;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct HealthThresholds {
+    pub min_missing_chips: u32,
+    pub max_temp_spread_c: f64,
+    /// Hashrate this far below the model median puts a miner on the repair list.
+    pub max_hashrate_shortfall_percent: f64,
+}
+
+impl Default for HealthThresholds {
+    fn default() -> Self {
+        Self {
+            min_missing_chips: 1,
+            max_temp_spread_c: 15.0,
+            max_hashrate_shortfall_percent: 15.0,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HealthSort {
+    MissingChips,
+    TempSpread,
+    HashrateDeviation,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct BoardHealth {
+    pub position: u8,
+    pub serial_number: Option<String>,
+    pub expected_chips: Option<u16>,
+    pub working_chips: Option<u16>,
+    pub missing_chips: u32,
+    pub temperature_c: Option<f64>,
+    pub hashrate_th: Option<f64>,
+}
+
+impl BoardHealth {
+    pub fn from_board(board: &BoardData, fallback_expected_chips: Option<u16>) -> Self {
+        let expected_chips = board.expected_chips.or(fallback_expected_chips);
+        // Prefer the board's own count; fall back to per-chip working flags
+        let working_chips = board.working_chips.or_else(|| {
+            (!board.chips.is_empty()).then(|| {
+                board
+                    .chips
+                    .iter()
+                    .filter(|chip| chip.working != Some(false))
+                    .count() as u16
+            })
+        });
+        let missing_chips = match (expected_chips, working_chips) {
+            (Some(expected), Some(working)) => expected.saturating_sub(working) as u32,
+            _ => board
+                .chips
+                .iter()
+                .filter(|chip| chip.working == Some(false))
+                .count() as u32,
+        };
+
+        Self {
+            position: board.position,
+            serial_number: board.serial_number.clone(),
+            expected_chips,
+            working_chips,
+            missing_chips,
+            temperature_c: board.board_temperature.map(|t| t.as_celsius()),
+            hashrate_th: board
+                .hashrate
+                .clone()
+                .map(|h| h.as_unit(HashRateUnit::TeraHash).value),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct MinerHealth {
+    pub ip: String,
+    pub identity: String,
+    pub hostname: String,
+    pub model: String,
+    pub serial_number: String,
+    pub boards: Vec<BoardHealth>,
+    pub missing_chips: u32,
+    /// Hottest minus coolest board.
+    pub temp_spread_c: Option<f64>,
+    /// Percent above (+) or below (-) the model median hashrate.
+    pub hashrate_deviation_percent: Option<f64>,
+}
+
+impl MinerHealth {
+    /// Missing chips on the miner's worst board, which decides whether a
+    /// board swap is needed; a few chips spread over every board is not.
+    pub fn worst_board_missing_chips(&self) -> u32 {
+        self.boards
+            .iter()
+            .map(|b| b.missing_chips)
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Why this miner belongs on the repair list, empty when it does not.
+    pub fn repair_reasons(&self, thresholds: &HealthThresholds) -> Vec<String> {
+        let mut reasons = Vec::new();
+        if self.missing_chips > 0 && self.missing_chips >= thresholds.min_missing_chips {
+            reasons.push(format!("{} missing chip(s)", self.missing_chips));
+        }
+        if let Some(spread) = self
+            .temp_spread_c
+            .filter(|s| *s > thresholds.max_temp_spread_c)
+        {
+            reasons.push(format!("{spread:.1}°C board spread"));
+        }
+        if let Some(deviation) = self
+            .hashrate_deviation_percent
+            .filter(|d| *d < -thresholds.max_hashrate_shortfall_percent)
+        {
+            reasons.push(format!("{deviation:.1}% vs model median"));
+        }
+        reasons
+    }
+}
+
+fn temp_spread(boards: &[BoardHealth]) -> Option<f64> {
+    let temps: Vec<f64> = boards.iter().filter_map(|b| b.temperature_c).collect();
+    if temps.len() < 2 {
+        return None;
+    }
+    let max = temps.iter().copied().fold(f64::MIN, f64::max);
+    let min = temps.iter().copied().fold(f64::MAX, f64::min);
+    Some(max - min)
+}
+
+pub fn fleet_health(miners: &[MinerInfo]) -> Vec<MinerHealth> {
+    let medians = watchdog::median_hashrate_by_model(miners);
+
+    miners
+        .iter()
+        .filter_map(|miner| {
+            let data = miner.full_data.as_ref()?;
+            // Miner-level expected chips split evenly when boards do not report their own
+            let per_board_expected = data
+                .expected_chips
+                .zip(data.expected_hashboards.filter(|n| *n > 0))
+                .map(|(chips, boards)| chips / boards as u16);
+            let boards: Vec<BoardHealth> = data
+                .hashboards
+                .iter()
+                .map(|board| BoardHealth::from_board(board, per_board_expected))
+                .collect();
+
+            let hashrate_deviation_percent = medians
+                .get(&miner.model)
+                .filter(|median| **median > 0.0)
+                .map(|median| (miner.hashrate_th.unwrap_or(0.0) - median) / median * 100.0);
+
+            Some(MinerHealth {
+                ip: miner.ip.clone(),
+                identity: miner.identity(),
+                hostname: miner.hostname.clone(),
+                model: miner.model.clone(),
+                serial_number: data.serial_number.clone().unwrap_or_default(),
+                missing_chips: boards.iter().map(|b| b.missing_chips).sum(),
+                temp_spread_c: temp_spread(&boards),
+                hashrate_deviation_percent,
+                boards,
+            })
+        })
+        .collect()
+}
+
+pub fn sort_health_report(entries: &mut [MinerHealth], sort: HealthSort) {
+    let spread = |e: &MinerHealth| e.temp_spread_c.unwrap_or(0.0);
+    let deviation = |e: &MinerHealth| e.hashrate_deviation_percent.unwrap_or(0.0);
+
+    entries.sort_by(|a, b| {
+        let order = match sort {
+            HealthSort::MissingChips => b
+                .worst_board_missing_chips()
+                .cmp(&a.worst_board_missing_chips())
+                .then_with(|| b.missing_chips.cmp(&a.missing_chips))
+                .then_with(|| deviation(a).total_cmp(&deviation(b))),
+            HealthSort::TempSpread => spread(b)
+                .total_cmp(&spread(a))
+                .then_with(|| deviation(a).total_cmp(&deviation(b))),
+            HealthSort::HashrateDeviation => deviation(a).total_cmp(&deviation(b)),
+        };
+        // Keep equal rows in a stable place between refreshes
+        order.then_with(|| a.ip.cmp(&b.ip))
+    });
+}
+
+/// One row per board of every miner on the repair list, with rack and slot
+/// so the floor team can find the unit.
+pub fn repair_list_csv(
+    entries: &[MinerHealth],
+    thresholds: &HealthThresholds,
+    locations: &HashMap<String, MinerLocation>,
+) -> String {
+    let mut csv = String::from(
+        "IP,Hostname,Model,Serial,Rack,Slot,Reasons,Board,Board Serial,Expected Chips,Working Chips,Missing Chips,Board Temp (°C),Board Hashrate (TH/s)\n",
+    );
+    let optional = |value: Option<String>| value.unwrap_or_default();
+
+    for entry in entries {
+        let reasons = entry.repair_reasons(thresholds);
+        if reasons.is_empty() {
+            continue;
+        }
+        let location = locations.get(&entry.identity).cloned().unwrap_or_default();
+
+        for board in &entry.boards {
+            let row = [
+                entry.ip.clone(),
+                entry.hostname.clone(),
+                entry.model.clone(),
+                entry.serial_number.clone(),
+                location.rack.clone(),
+                location.slot.clone(),
+                reasons.join("; "),
+                (board.position + 1).to_string(),
+                optional(board.serial_number.clone()),
+                optional(board.expected_chips.map(|c| c.to_string())),
+                optional(board.working_chips.map(|c| c.to_string())),
+                board.missing_chips.to_string(),
+                optional(board.temperature_c.map(|t| format!("{t:.1}"))),
+                optional(board.hashrate_th.map(|h| format!("{h:.2}"))),
+            ];
+            let row: Vec<String> = row.iter().map(|field| csv_field(field)).collect();
+            csv.push_str(&row.join(","));
+            csv.push('\n');
+        }
+    }
+
+    csv
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        repair_list_csv, sort_health_report, BoardHealth, HealthSort, HealthThresholds, MinerHealth,
+    };
+    use crate::models::MinerLocation;
+    use std::collections::HashMap;
+
+    fn board(position: u8, working: u16, temp: f64) -> BoardHealth {
+        BoardHealth {
+            position,
+            serial_number: None,
+            expected_chips: Some(76),
+            working_chips: Some(working),
+            missing_chips: 76 - working as u32,
+            temperature_c: Some(temp),
+            hashrate_th: Some(30.0),
+        }
+    }
+
+    fn entry(ip: &str, boards: Vec<BoardHealth>, deviation: f64) -> MinerHealth {
+        MinerHealth {
+            ip: ip.to_string(),
+            identity: ip.to_string(),
+            hostname: "miner".to_string(),
+            model: "S19".to_string(),
+            serial_number: String::new(),
+            missing_chips: boards.iter().map(|b| b.missing_chips).sum(),
+            temp_spread_c: Some(20.0),
+            hashrate_deviation_percent: Some(deviation),
+            boards,
+        }
+    }
+
+    #[test]
+    fn missing_chips_ranks_by_worst_board_before_total() {
+        // Three chips on each board, versus six gone from a single board
+        let mut entries = vec![
+            entry(
+                "10.0.0.1",
+                vec![board(0, 73, 60.0), board(1, 73, 60.0), board(2, 73, 60.0)],
+                0.0,
+            ),
+            entry(
+                "10.0.0.2",
+                vec![board(0, 70, 60.0), board(1, 76, 60.0), board(2, 76, 60.0)],
+                0.0,
+            ),
+        ];
+        assert!(entries[0].missing_chips > entries[1].missing_chips);
+
+        sort_health_report(&mut entries, HealthSort::MissingChips);
+        assert_eq!(entries[0].ip, "10.0.0.2");
+        assert_eq!(entries[0].worst_board_missing_chips(), 6);
+
+        // Equal spreads fall back to the larger hashrate shortfall
+        entries[0].hashrate_deviation_percent = Some(-1.0);
+        entries[1].hashrate_deviation_percent = Some(-5.0);
+        sort_health_report(&mut entries, HealthSort::TempSpread);
+        assert_eq!(entries[0].ip, "10.0.0.1");
+    }
+
+    #[test]
+    fn health_report_ranks_and_exports_repair_candidates() {
+        let mut entries = vec![
+            entry(
+                "10.0.0.1",
+                vec![board(0, 76, 60.0), board(1, 76, 62.0)],
+                -2.0,
+            ),
+            entry(
+                "10.0.0.2",
+                vec![board(0, 70, 60.0), board(1, 75, 80.0)],
+                -20.0,
+            ),
+        ];
+        entries[0].temp_spread_c = Some(2.0);
+
+        sort_health_report(&mut entries, HealthSort::MissingChips);
+        assert_eq!(entries[0].ip, "10.0.0.2");
+        assert_eq!(entries[0].missing_chips, 7);
+
+        let thresholds = HealthThresholds::default();
+        assert!(entries[1].repair_reasons(&thresholds).is_empty());
+        assert_eq!(entries[0].repair_reasons(&thresholds).len(), 3);
+
+        let locations = HashMap::from([(
+            "10.0.0.2".to_string(),
+            MinerLocation {
+                rack: "R4".to_string(),
+                slot: "12".to_string(),
+                ..Default::default()
+            },
+        )]);
+        let csv = repair_list_csv(&entries, &thresholds, &locations);
+        let rows: Vec<&str> = csv.lines().collect();
+        assert_eq!(rows.len(), 3);
+        assert!(rows[1].starts_with("10.0.0.2,miner,S19,,R4,12,"));
+        assert!(rows[1].contains(",1,,76,70,6,60.0,30.00"));
+    }
+}
@@ -1,23 +1,65 @@
+mod actions;
+mod audit;
+mod availability;
+mod baselines;
 mod config;
+mod credentials;
+mod energy;
+mod export;
+mod firmware;
+mod health;
 mod models;
 mod options;
+mod pool_audit;
+mod pools;
+mod power_cap;
+mod profiles;
+mod profitability;
 mod recording;
+mod restarts;
+mod rollout;
 mod runtime;
+mod safety;
 mod scanner;
+mod schedule;
+mod secrets;
+mod snapshots;
+mod tenants;
+mod thermal;
 mod ui;
+mod watchdog;
+mod workers;
 
+use audit::{AuditEntry, AuditFilter, AuditLog};
+use availability::{AvailabilityLog, SlaGrouping, SlaSettings};
+use baselines::BaselineDatabase;
+use credentials::MinerCredential;
 use eframe::egui;
 use egui::Color32;
+use energy::{EnergyLedger, StatementGrouping};
+use export::csv_field;
+use health::{HealthSort, HealthThresholds};
 use models::{
-    MetricsHistory, MinerInfo, MinerOptionSettings, SavedRange, ScanProgress, SortColumn,
-    SortDirection,
+    MetricsHistory, MinerInfo, MinerLocation, MinerOptionSettings, OptionProfile,
+    OptionsApplyPreview, SavedRange, ScanProgress, SortColumn, SortDirection,
 };
-use std::collections::{HashMap, HashSet};
+use power_cap::{FleetMinerPower, PowerCapAction, PowerCapSettings, PowerCapState, PowerCapStep};
+use profitability::{HashpriceSource, ProfitabilitySettings};
+use rollout::{RolloutAction, RolloutSettings, StagedRollout};
+use safety::{BulkAction, BulkConfirmation, SafetySettings};
+use schedule::{ScheduleLog, ScheduleRunRecord, ScheduledAction, ScheduledTask};
+use secrets::{SecretKind, SecretStore, SecretsForm, SecretsRequest};
+use snapshots::{ConfigSnapshot, SnapshotRecorder, SnapshotStore};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
-use ui::ScanControlState;
+use tenants::{ReportFormat, ReportPeriod, Tenant};
+use thermal::{ThermalReading, ThermalSettings, ThermalState};
+use ui::{RollbackRequest, ScanControlState};
+use watchdog::{WatchdogSettings, WatchdogState};
 
 fn main() -> Result<(), eframe::Error> {
+    let read_only_flag = std::env::args().skip(1).any(|arg| arg == "--read-only");
     let options = eframe::NativeOptions {
         viewport: egui::ViewportBuilder::default()
             .with_inner_size([1400.0, 900.0])
@@ -28,7 +70,7 @@ fn main() -> Result<(), eframe::Error> {
     eframe::run_native(
         "asic-rs Miner Scanner",
         options,
-        Box::new(|cc| Ok(Box::new(MinerScannerApp::new(cc)))),
+        Box::new(move |cc| Ok(Box::new(MinerScannerApp::new(cc, read_only_flag)))),
     )
 }
 
@@ -58,11 +100,100 @@ struct MinerScannerApp {
     miner_option_overrides: Arc<Mutex<HashMap<String, MinerOptionSettings>>>,
     miner_options_prefill_pending: Arc<Mutex<HashSet<String>>>,
     prev_global_options: MinerOptionSettings,
+    apply_preview: Arc<Mutex<Option<OptionsApplyPreview>>>,
+    option_snapshots: Arc<Mutex<SnapshotStore>>,
+    show_snapshots_window: bool,
+    rollout_settings: RolloutSettings,
+    prev_rollout_settings: RolloutSettings,
+    staged_rollout: Option<StagedRollout>,
+    option_profiles: Vec<OptionProfile>,
+    prev_option_profiles: Vec<OptionProfile>,
+    show_profiles_window: bool,
+    new_profile_name: String,
+    miner_locations: HashMap<String, MinerLocation>,
+    prev_miner_locations: HashMap<String, MinerLocation>,
+    show_pool_audit_window: bool,
+    pool_audit_show_unaudited: bool,
+    scheduled_actions: Vec<ScheduledAction>,
+    prev_scheduled_actions: Vec<ScheduledAction>,
+    schedule_log: Arc<Mutex<ScheduleLog>>,
+    show_schedule_window: bool,
+    last_schedule_minute: i64,
+    power_cap_settings: PowerCapSettings,
+    prev_power_cap_settings: PowerCapSettings,
+    power_cap_state: Arc<Mutex<PowerCapState>>,
+    show_power_cap_window: bool,
+    fleet_was_scanning: bool,
+    thermal_settings: ThermalSettings,
+    prev_thermal_settings: ThermalSettings,
+    thermal_state: Arc<Mutex<ThermalState>>,
+    show_thermal_window: bool,
+    watchdog_settings: WatchdogSettings,
+    prev_watchdog_settings: WatchdogSettings,
+    watchdog_state: Arc<Mutex<WatchdogState>>,
+    show_watchdog_window: bool,
+    health_thresholds: HealthThresholds,
+    prev_health_thresholds: HealthThresholds,
+    health_sort: HealthSort,
+    health_only_candidates: bool,
+    show_health_window: bool,
+    model_baselines: BaselineDatabase,
+    show_baselines_window: bool,
+    profitability_settings: ProfitabilitySettings,
+    prev_profitability_settings: ProfitabilitySettings,
+    file_hashprice: Result<f64, String>,
+    profitability_only_unprofitable: bool,
+    show_profitability_window: bool,
+    energy_ledger: EnergyLedger,
+    energy_month: String,
+    energy_grouping: StatementGrouping,
+    show_energy_window: bool,
+    tenants: Vec<Tenant>,
+    prev_tenants: Vec<Tenant>,
+    new_tenant_name: String,
+    tenant_report_period: ReportPeriod,
+    tenant_view: Option<String>,
+    show_tenants_window: bool,
+    availability_log: AvailabilityLog,
+    sla_settings: SlaSettings,
+    prev_sla_settings: SlaSettings,
+    sla_period: ReportPeriod,
+    sla_grouping: SlaGrouping,
+    show_availability_window: bool,
+    audit_log: Arc<Mutex<AuditLog>>,
+    audit_filter: AuditFilter,
+    show_audit_window: bool,
+    safety_settings: SafetySettings,
+    prev_safety_settings: SafetySettings,
+    protected_miners: HashSet<String>,
+    prev_protected_miners: HashSet<String>,
+    approved_firmware: BTreeMap<String, String>,
+    prev_approved_firmware: BTreeMap<String, String>,
+    show_firmware_window: bool,
+    bulk_confirmation: Option<BulkConfirmation>,
+    show_safety_window: bool,
+    /// Read-only as stored in the config, kept apart from the `--read-only` flag when saving.
+    config_read_only: bool,
+    read_only: bool,
+    secret_store: SecretStore,
+    secrets_form: SecretsForm,
+    show_secrets_window: bool,
+    miner_credentials: Vec<MinerCredential>,
+    prev_miner_credentials: Vec<MinerCredential>,
+    new_credential: MinerCredential,
+    show_credentials_window: bool,
 }
 
 impl MinerScannerApp {
-    fn new(_cc: &eframe::CreationContext<'_>) -> Self {
+    fn new(_cc: &eframe::CreationContext<'_>, read_only_flag: bool) -> Self {
         let app_config = config::load_config();
+        let read_only = read_only_flag || app_config.read_only;
+        actions::set_read_only(read_only);
+        if read_only {
+            println!("✓ Read-only mode: control actions are disabled");
+        }
+        let secret_store = SecretStore::load();
+        credentials::update_registry(&app_config.miner_credentials, &[], &app_config.saved_ranges);
 
         Self {
             miners: Arc::new(Mutex::new(Vec::new())),
@@ -111,6 +242,90 @@ impl MinerScannerApp {
             miner_option_overrides: Arc::new(Mutex::new(HashMap::new())),
             miner_options_prefill_pending: Arc::new(Mutex::new(HashSet::new())),
             prev_global_options: app_config.global_options,
+            apply_preview: Arc::new(Mutex::new(None)),
+            option_snapshots: Arc::new(Mutex::new(SnapshotStore::load())),
+            show_snapshots_window: false,
+            rollout_settings: app_config.rollout_settings.clone(),
+            prev_rollout_settings: app_config.rollout_settings,
+            staged_rollout: None,
+            option_profiles: app_config.option_profiles.clone(),
+            prev_option_profiles: app_config.option_profiles,
+            show_profiles_window: false,
+            new_profile_name: String::new(),
+            miner_locations: app_config.miner_locations.clone(),
+            prev_miner_locations: app_config.miner_locations,
+            show_pool_audit_window: false,
+            pool_audit_show_unaudited: false,
+            scheduled_actions: app_config.scheduled_actions.clone(),
+            prev_scheduled_actions: app_config.scheduled_actions,
+            schedule_log: Arc::new(Mutex::new(ScheduleLog::load())),
+            show_schedule_window: false,
+            last_schedule_minute: 0,
+            power_cap_settings: app_config.power_cap.clone(),
+            prev_power_cap_settings: app_config.power_cap,
+            power_cap_state: Arc::new(Mutex::new(PowerCapState::load())),
+            show_power_cap_window: false,
+            fleet_was_scanning: false,
+            thermal_settings: app_config.thermal.clone(),
+            prev_thermal_settings: app_config.thermal,
+            thermal_state: Arc::new(Mutex::new(ThermalState::load())),
+            show_thermal_window: false,
+            watchdog_settings: app_config.watchdog.clone(),
+            prev_watchdog_settings: app_config.watchdog,
+            watchdog_state: Arc::new(Mutex::new(WatchdogState::load())),
+            show_watchdog_window: false,
+            health_thresholds: app_config.health_thresholds.clone(),
+            prev_health_thresholds: app_config.health_thresholds,
+            health_sort: HealthSort::MissingChips,
+            health_only_candidates: false,
+            show_health_window: false,
+            model_baselines: BaselineDatabase::load(),
+            show_baselines_window: false,
+            file_hashprice: profitability::read_hashprice_file(
+                &app_config.profitability.hashprice_file,
+            ),
+            profitability_settings: app_config.profitability.clone(),
+            prev_profitability_settings: app_config.profitability,
+            profitability_only_unprofitable: false,
+            show_profitability_window: false,
+            energy_ledger: EnergyLedger::load(),
+            energy_month: chrono::Local::now().format("%Y-%m").to_string(),
+            energy_grouping: StatementGrouping::Tenant,
+            show_energy_window: false,
+            tenants: app_config.tenants.clone(),
+            prev_tenants: app_config.tenants,
+            new_tenant_name: String::new(),
+            tenant_report_period: ReportPeriod::this_month(),
+            tenant_view: None,
+            show_tenants_window: false,
+            availability_log: AvailabilityLog::load(),
+            sla_settings: app_config.sla.clone(),
+            prev_sla_settings: app_config.sla,
+            sla_period: ReportPeriod::this_month(),
+            sla_grouping: SlaGrouping::Tenant,
+            show_availability_window: false,
+            audit_log: Arc::new(Mutex::new(AuditLog::load())),
+            audit_filter: AuditFilter::default(),
+            show_audit_window: false,
+            safety_settings: app_config.safety.clone(),
+            prev_safety_settings: app_config.safety,
+            protected_miners: app_config.protected_miners.clone(),
+            prev_protected_miners: app_config.protected_miners,
+            approved_firmware: app_config.approved_firmware.clone(),
+            prev_approved_firmware: app_config.approved_firmware,
+            show_firmware_window: false,
+            bulk_confirmation: None,
+            show_safety_window: false,
+            config_read_only: app_config.read_only,
+            read_only,
+            // Pool passwords stay as references until the operator unlocks
+            show_secrets_window: secret_store.is_initialized() && !read_only,
+            secret_store,
+            secrets_form: SecretsForm::default(),
+            miner_credentials: app_config.miner_credentials.clone(),
+            prev_miner_credentials: app_config.miner_credentials,
+            new_credential: MinerCredential::default(),
+            show_credentials_window: false,
         }
     }
 
@@ -128,6 +343,11 @@ impl MinerScannerApp {
 
         let mut miners = self.miners.lock().unwrap();
         let direction = self.sort_direction;
+        let percent_of_nominal = if column == SortColumn::PercentOfNominal {
+            baselines::percent_of_nominal_by_ip(&self.model_baselines, &miners)
+        } else {
+            HashMap::new()
+        };
 
         // Helper function to extract numeric value from a string
         fn extract_numeric(s: &str) -> f64 {
@@ -168,6 +388,11 @@ impl MinerScannerApp {
                     .unwrap_or(extract_numeric(&a.hashrate))
                     .partial_cmp(&b.hashrate_th.unwrap_or(extract_numeric(&b.hashrate)))
                     .unwrap_or(std::cmp::Ordering::Equal),
+                SortColumn::PercentOfNominal => {
+                    let percent =
+                        |m: &MinerInfo| percent_of_nominal.get(&m.ip).copied().unwrap_or(f64::MAX);
+                    percent(a).total_cmp(&percent(b))
+                }
                 SortColumn::Wattage => a
                     .wattage_w
                     .unwrap_or(extract_numeric(&a.wattage))
@@ -199,7 +424,37 @@ impl MinerScannerApp {
         });
     }
 
-    fn save_config(&self) {
+    fn save_config(&mut self) {
+        let mut global_options = self.global_options.clone();
+        let mut option_profiles = self.option_profiles.clone();
+        let mut referenced =
+            secrets::seal_pool_passwords(&mut global_options, "Global", &mut self.secret_store);
+        for profile in &mut option_profiles {
+            referenced.extend(secrets::seal_pool_passwords(
+                &mut profile.settings,
+                &format!("Profile {}", profile.name),
+                &mut self.secret_store,
+            ));
+        }
+        referenced.extend(
+            self.option_snapshots
+                .lock()
+                .unwrap()
+                .seal_passwords(&mut self.secret_store),
+        );
+        let mut miner_credentials = self.miner_credentials.clone();
+        let credential_refs =
+            credentials::seal_passwords(&mut miner_credentials, &mut self.secret_store);
+        if self.secret_store.is_unlocked() {
+            self.secret_store
+                .retain_referenced(SecretKind::PoolPassword, &referenced);
+            self.secret_store
+                .retain_referenced(SecretKind::MinerCredential, &credential_refs);
+        }
+        if self.secret_store.is_initialized() {
+            self.secret_store.save();
+        }
+
         let app_config = config::AppConfig {
             saved_ranges: self.saved_ranges.clone(),
             detail_refresh_interval_secs: self.detail_refresh_interval_secs,
@@ -207,7 +462,23 @@ impl MinerScannerApp {
             identification_timeout_secs: self.scan_control_state.identification_timeout_secs,
             connectivity_timeout_secs: self.scan_control_state.connectivity_timeout_secs,
             connectivity_retries: self.scan_control_state.connectivity_retries,
-            global_options: self.global_options.clone(),
+            global_options,
+            rollout_settings: self.rollout_settings.clone(),
+            option_profiles,
+            miner_locations: self.miner_locations.clone(),
+            scheduled_actions: self.scheduled_actions.clone(),
+            power_cap: self.power_cap_settings.clone(),
+            thermal: self.thermal_settings.clone(),
+            watchdog: self.watchdog_settings.clone(),
+            health_thresholds: self.health_thresholds.clone(),
+            profitability: self.profitability_settings.clone(),
+            tenants: self.tenants.clone(),
+            sla: self.sla_settings.clone(),
+            safety: self.safety_settings.clone(),
+            protected_miners: self.protected_miners.clone(),
+            read_only: self.config_read_only,
+            miner_credentials,
+            approved_firmware: self.approved_firmware.clone(),
         };
         config::save_config(&app_config);
     }
@@ -232,6 +503,7 @@ impl MinerScannerApp {
             self.scan_control_state.new_range_name.clear();
             self.scan_control_state.show_name_error = false;
             self.save_config();
+            self.refresh_credential_registry();
         }
     }
 
@@ -239,9 +511,18 @@ impl MinerScannerApp {
         if index < self.saved_ranges.len() {
             self.saved_ranges.remove(index);
             self.save_config();
+            self.refresh_credential_registry();
         }
     }
 
+    fn refresh_credential_registry(&self) {
+        credentials::update_registry(
+            &self.miner_credentials,
+            &self.miners.lock().unwrap(),
+            &self.saved_ranges,
+        );
+    }
+
     fn load_saved_range(&mut self, range: &SavedRange) {
         // Parse the range back into start and end IPs
         if let Some(dash_pos) = range.range.rfind('-') {
@@ -283,18 +564,18 @@ impl MinerScannerApp {
                 csv_content.push_str(&format!(
                     "{},{},{},{},{},{},{},{},{},{},{},{},{}\n",
                     miner.ip,
-                    miner.hostname,
-                    miner.model,
-                    miner.firmware_version,
-                    miner.control_board,
+                    csv_field(&miner.hostname),
+                    csv_field(&miner.model),
+                    csv_field(&miner.firmware_version),
+                    csv_field(&miner.control_board),
                     miner.active_boards,
                     miner.hashrate.replace(" TH/s", ""),
                     miner.wattage.replace(" W", ""),
                     miner.efficiency.replace(" W/TH", ""),
                     miner.temperature.replace("°C", ""),
                     miner.fan_speed.replace(" RPM", ""),
-                    miner.pool,
-                    miner.worker
+                    csv_field(&miner.pool),
+                    csv_field(&miner.worker)
                 ));
             }
 
@@ -306,6 +587,932 @@ impl MinerScannerApp {
         }
     }
 
+    fn export_repair_list(&self) {
+        use chrono::Local;
+        use std::fs;
+
+        let mut entries = health::fleet_health(&self.miners.lock().unwrap());
+        health::sort_health_report(&mut entries, self.health_sort);
+
+        let timestamp = Local::now().format("%Y-%m-%d_%H-%M-%S");
+        let filename = format!("repair_list_{}.csv", timestamp);
+
+        if let Some(path) = rfd::FileDialog::new()
+            .set_file_name(&filename)
+            .add_filter("CSV Files", &["csv"])
+            .save_file()
+        {
+            let csv_content =
+                health::repair_list_csv(&entries, &self.health_thresholds, &self.miner_locations);
+            if let Err(e) = fs::write(&path, csv_content) {
+                eprintln!("Failed to export repair list: {}", e);
+            } else {
+                println!("Exported repair list to {}", path.display());
+            }
+        }
+    }
+
+    fn export_firmware_report(&self) {
+        use chrono::Local;
+        use std::fs;
+
+        let miners = self.miners.lock().unwrap();
+        let entries = firmware::non_compliant(&miners, &self.approved_firmware);
+
+        let timestamp = Local::now().format("%Y-%m-%d_%H-%M-%S");
+        let filename = format!("firmware_noncompliant_{}.csv", timestamp);
+
+        if let Some(path) = rfd::FileDialog::new()
+            .set_file_name(&filename)
+            .add_filter("CSV Files", &["csv"])
+            .save_file()
+        {
+            let csv_content = firmware::non_compliant_csv(&entries, &self.miner_locations);
+            if let Err(e) = fs::write(&path, csv_content) {
+                eprintln!("Failed to export firmware report: {}", e);
+            } else {
+                println!("Exported firmware report to {}", path.display());
+            }
+        }
+    }
+
+    fn current_hashprice(&self) -> Result<f64, String> {
+        match self.profitability_settings.hashprice_source {
+            HashpriceSource::Manual => Ok(self.profitability_settings.hashprice_usd_per_th_day),
+            HashpriceSource::File => self.file_hashprice.clone(),
+        }
+    }
+
+    fn reload_hashprice_file(&mut self) {
+        if self.profitability_settings.hashprice_source == HashpriceSource::File {
+            self.file_hashprice =
+                profitability::read_hashprice_file(&self.profitability_settings.hashprice_file);
+            if let Err(e) = &self.file_hashprice {
+                eprintln!("✗ {e}");
+            }
+        }
+    }
+
+    fn record_scan_history(&mut self) {
+        // Bridge consecutive scans, but not a miner that dropped out for longer
+        let max_gap_secs = (self.scan_control_state.auto_scan_interval_secs as i64 * 2)
+            .max(energy::MIN_BRIDGED_GAP_SECS);
+        let now = chrono::Local::now().timestamp();
+        let miners = self.miners.lock().unwrap();
+        self.energy_ledger.record_scan(&miners, now, max_gap_secs);
+        self.availability_log
+            .record_scan(&miners, &self.saved_ranges, now, max_gap_secs);
+    }
+
+    fn sla_rows(&self) -> Vec<availability::SlaRow> {
+        let Some((from, to)) =
+            availability::period_bounds(&self.sla_period.from, &self.sla_period.to)
+        else {
+            return Vec::new();
+        };
+        availability::sla_report(
+            &self.availability_log,
+            from,
+            to,
+            self.sla_grouping,
+            &self.saved_ranges,
+            &self.tenants_by_identity(self.availability_log.miners.keys()),
+        )
+    }
+
+    fn handle_secrets_request(&mut self, request: SecretsRequest) {
+        let result = match request {
+            SecretsRequest::Create(passphrase) => self.secret_store.create(&passphrase).map(|()| {
+                // Encrypt the passwords already in the config right away
+                self.save_config();
+                println!("✓ Master passphrase set, passwords encrypted");
+            }),
+            SecretsRequest::Unlock(passphrase) => self.secret_store.unlock(&passphrase).map(|()| {
+                let mut unresolved =
+                    secrets::reveal_pool_passwords(&mut self.global_options, &self.secret_store);
+                for profile in &mut self.option_profiles {
+                    unresolved +=
+                        secrets::reveal_pool_passwords(&mut profile.settings, &self.secret_store);
+                }
+                unresolved +=
+                    credentials::reveal_passwords(&mut self.miner_credentials, &self.secret_store);
+                if unresolved > 0 {
+                    eprintln!("✗ {unresolved} password(s) missing from the secret store");
+                }
+                self.refresh_credential_registry();
+                self.show_secrets_window = false;
+            }),
+            SecretsRequest::Lock => {
+                // Persist pending edits, then drop the plain passwords from memory
+                self.save_config();
+                secrets::seal_pool_passwords(
+                    &mut self.global_options,
+                    "Global",
+                    &mut self.secret_store,
+                );
+                for profile in &mut self.option_profiles {
+                    secrets::seal_pool_passwords(
+                        &mut profile.settings,
+                        &format!("Profile {}", profile.name),
+                        &mut self.secret_store,
+                    );
+                }
+                credentials::seal_passwords(&mut self.miner_credentials, &mut self.secret_store);
+                self.prev_global_options = self.global_options.clone();
+                self.prev_option_profiles = self.option_profiles.clone();
+                self.prev_miner_credentials = self.miner_credentials.clone();
+                self.secret_store.lock();
+                self.refresh_credential_registry();
+                Ok(())
+            }
+            SecretsRequest::ChangePassphrase(passphrase) => {
+                self.secret_store.change_passphrase(&passphrase).map(|()| {
+                    self.secret_store.save();
+                    println!("✓ Secrets re-encrypted with the new passphrase");
+                })
+            }
+        };
+        self.secrets_form.clear();
+        if let Err(err) = result {
+            self.secrets_form.error = Some(err);
+        }
+    }
+
+    fn request_bulk_action(&mut self, action: BulkAction) {
+        let confirmation = BulkConfirmation::new(
+            action,
+            self.selected_miners.iter().cloned().collect(),
+            &self.miners.lock().unwrap(),
+            &self.protected_miners,
+        );
+        if confirmation.needs_dialog(&self.safety_settings) {
+            self.bulk_confirmation = Some(confirmation);
+        } else if confirmation.is_confirmed(&self.safety_settings) {
+            self.execute_bulk_action(confirmation);
+        }
+    }
+
+    fn execute_bulk_action(&self, confirmation: BulkConfirmation) {
+        let parameters = if confirmation.skipped_protected.is_empty() {
+            String::new()
+        } else {
+            format!("skipped {} protected", confirmation.skipped_protected.len())
+        };
+        let entry = AuditEntry::new(
+            "Table",
+            confirmation.action.label(),
+            confirmation.targets.clone(),
+            parameters,
+        );
+        let audit_log = Arc::clone(&self.audit_log);
+
+        match confirmation.action {
+            BulkAction::Miner(action) => {
+                runtime::spawn(async move {
+                    let results = actions::run_action_on_many(confirmation.targets, action).await;
+                    audit::record(&audit_log, entry.with_results(&results));
+                });
+            }
+            BulkAction::ToggleFaultLight => {
+                let miners = self.miners.lock().unwrap();
+                let states = confirmation
+                    .targets
+                    .into_iter()
+                    .map(|ip| {
+                        let flashing = miners
+                            .iter()
+                            .find(|m| m.ip == ip)
+                            .is_some_and(|m| m.light_flashing);
+                        (ip, flashing)
+                    })
+                    .collect();
+                runtime::spawn(async move {
+                    let results = actions::toggle_fault_lights(states).await;
+                    audit::record(&audit_log, entry.with_results(&results));
+                });
+            }
+        }
+    }
+
+    fn export_audit_log(&self) {
+        use std::fs;
+
+        let log = self.audit_log.lock().unwrap();
+        let entries = audit::filtered_entries(&log, &self.audit_filter);
+        let filename = format!(
+            "audit_log_{}.csv",
+            chrono::Local::now().format("%Y%m%d_%H%M%S")
+        );
+
+        if let Some(path) = rfd::FileDialog::new()
+            .set_file_name(&filename)
+            .add_filter("CSV Files", &["csv"])
+            .save_file()
+        {
+            if let Err(e) = fs::write(&path, audit::audit_csv(&entries)) {
+                eprintln!("Failed to export audit log: {}", e);
+            } else {
+                println!("Exported audit log to {}", path.display());
+            }
+        }
+    }
+
+    fn export_sla_report(&self) {
+        use std::fs;
+
+        let rows = self.sla_rows();
+        let filename = format!(
+            "sla_report_{}_{}_{}.csv",
+            self.sla_period.from,
+            self.sla_period.to,
+            self.sla_grouping.label().to_lowercase()
+        );
+
+        if let Some(path) = rfd::FileDialog::new()
+            .set_file_name(&filename)
+            .add_filter("CSV Files", &["csv"])
+            .save_file()
+        {
+            let csv_content = availability::sla_report_csv(
+                &self.sla_period.from,
+                &self.sla_period.to,
+                self.sla_grouping,
+                &self.sla_settings,
+                &rows,
+            );
+            if let Err(e) = fs::write(&path, csv_content) {
+                eprintln!("Failed to export SLA report: {}", e);
+            } else {
+                println!("Exported SLA report to {}", path.display());
+            }
+        }
+    }
+
+    fn tenants_by_identity<'a>(
+        &self,
+        identities: impl IntoIterator<Item = &'a String>,
+    ) -> HashMap<String, String> {
+        tenants::tenants_by_identity(
+            identities,
+            &self.miners.lock().unwrap(),
+            &self.tenants,
+            &self.miner_locations,
+            &self.saved_ranges,
+        )
+    }
+
+    /// IPs of the miners in the tenant chosen for the main view, if any.
+    fn tenant_scope(&self) -> Option<HashSet<String>> {
+        let view = self.tenant_view.as_ref()?;
+        Some(
+            self.miners
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|miner| {
+                    tenants::tenant_for(
+                        miner,
+                        &self.tenants,
+                        &self.miner_locations,
+                        &self.saved_ranges,
+                    )
+                    .as_deref()
+                    .unwrap_or(tenants::UNASSIGNED)
+                        == view
+                })
+                .map(|miner| miner.ip.clone())
+                .collect(),
+        )
+    }
+
+    fn export_tenant_report(&self, format: ReportFormat) {
+        use std::fs;
+
+        let period = &self.tenant_report_period;
+        let rows = tenants::tenant_report(
+            &self.energy_ledger,
+            &self.tenants_by_identity(self.energy_ledger.miners.keys()),
+            &period.from,
+            &period.to,
+        );
+        let (content, extension, filter) = match format {
+            ReportFormat::Csv => (
+                tenants::tenant_report_csv(&period.from, &period.to, &rows),
+                "csv",
+                "CSV Files",
+            ),
+            ReportFormat::Html => (
+                tenants::tenant_report_html(&period.from, &period.to, &rows),
+                "html",
+                "HTML Files",
+            ),
+        };
+        let filename = format!("tenant_report_{}_{}.{}", period.from, period.to, extension);
+
+        if let Some(path) = rfd::FileDialog::new()
+            .set_file_name(&filename)
+            .add_filter(filter, &[extension])
+            .save_file()
+        {
+            if let Err(e) = fs::write(&path, content) {
+                eprintln!("Failed to export tenant report: {}", e);
+            } else {
+                println!("Exported tenant report to {}", path.display());
+            }
+        }
+    }
+
+    fn export_energy_statement(&self) {
+        use std::fs;
+
+        let rows = energy::monthly_statement(
+            &self.energy_ledger,
+            &self.energy_month,
+            self.energy_grouping,
+            &self.saved_ranges,
+            &self.tenants_by_identity(self.energy_ledger.miners.keys()),
+        );
+        let filename = format!(
+            "energy_statement_{}_{}.csv",
+            self.energy_month,
+            self.energy_grouping.label().to_lowercase()
+        );
+
+        if let Some(path) = rfd::FileDialog::new()
+            .set_file_name(&filename)
+            .add_filter("CSV Files", &["csv"])
+            .save_file()
+        {
+            let csv_content =
+                energy::statement_csv(&self.energy_month, self.energy_grouping, &rows);
+            if let Err(e) = fs::write(&path, csv_content) {
+                eprintln!("Failed to export energy statement: {}", e);
+            } else {
+                println!("Exported energy statement to {}", path.display());
+            }
+        }
+    }
+
+    fn apply_previewed_options(&mut self) {
+        let Some(preview) = self.apply_preview.lock().unwrap().take() else {
+            return;
+        };
+
+        // Only miners whose dry run showed a difference are written to
+        let targets: Vec<(String, MinerOptionSettings)> = preview
+            .entries
+            .into_iter()
+            .filter(|entry| entry.will_write())
+            .map(|entry| (entry.ip, entry.settings))
+            .collect();
+
+        if targets.is_empty() {
+            return;
+        }
+
+        let identities: HashMap<String, String> = self
+            .miners
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|m| (m.ip.clone(), m.identity()))
+            .collect();
+        if preview.staged
+            && self
+                .staged_rollout
+                .as_ref()
+                .is_some_and(|r| !r.is_finished())
+        {
+            self.error_message =
+                "A staged rollout is already running - abort it before starting another"
+                    .to_string();
+            return;
+        }
+
+        let recorder =
+            SnapshotRecorder::start_job(&self.option_snapshots, &preview.title, identities);
+        let locations = Arc::new(self.miner_locations.clone());
+        let entry = AuditEntry::new(
+            "Options",
+            "Apply options",
+            targets.iter().map(|(ip, _)| ip.clone()).collect(),
+            preview.title.clone(),
+        );
+
+        if preview.staged && targets.len() > self.rollout_settings.canary_count {
+            audit::record(&self.audit_log, entry.dispatched());
+            self.staged_rollout = Some(StagedRollout::start(
+                preview.title,
+                self.rollout_settings.clone(),
+                targets,
+                &self.miners.lock().unwrap(),
+                recorder,
+                locations,
+            ));
+            return;
+        }
+
+        let audit_log = Arc::clone(&self.audit_log);
+        runtime::spawn(async move {
+            let results = options::apply_options_to_many(targets, recorder, locations).await;
+            audit::record(&audit_log, entry.with_results(&results));
+        });
+    }
+
+    fn current_ip_for_identity(&self, identity: &str, fallback_ip: &str) -> String {
+        self.miners
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|m| m.identity() == identity)
+            .map(|m| m.ip.clone())
+            .unwrap_or_else(|| fallback_ip.to_string())
+    }
+
+    /// Seals pool passwords captured by apply jobs as soon as the store can.
+    fn seal_snapshot_passwords(&mut self) {
+        if !self.secret_store.is_unlocked() {
+            return;
+        }
+        let mut snapshots = self.option_snapshots.lock().unwrap();
+        if snapshots.has_unsealed_passwords() {
+            snapshots.seal_passwords(&mut self.secret_store);
+            self.secret_store.save();
+        }
+    }
+
+    fn start_rollback(&mut self, request: RollbackRequest) {
+        let (title, snapshots) = match request {
+            RollbackRequest::Job(job_id) => {
+                let store = self.option_snapshots.lock().unwrap();
+                let Some(job) = store.jobs.iter().find(|job| job.id == job_id) else {
+                    return;
+                };
+                (format!("Rollback: {}", job.title), job.snapshots.clone())
+            }
+            RollbackRequest::Snapshot(snapshot) => {
+                (format!("Rollback: {}", snapshot.ip), vec![*snapshot])
+            }
+        };
+
+        if snapshots.iter().any(ConfigSnapshot::pool_passwords_lost) {
+            self.error_message = "Not rolled back: this snapshot's pool passwords were not kept \
+                because the secret store was locked or has no master passphrase. Restore the \
+                pools manually."
+                .to_string();
+            return;
+        }
+
+        let mut targets = snapshots
+            .into_iter()
+            .filter(|snapshot| snapshot.settings.has_any_enabled())
+            .map(|snapshot| {
+                (
+                    self.current_ip_for_identity(&snapshot.miner_identity, &snapshot.ip),
+                    snapshot.settings,
+                )
+            })
+            .collect::<Vec<_>>();
+        let unresolved: usize = targets
+            .iter_mut()
+            .map(|(_, settings)| secrets::reveal_pool_passwords(settings, &self.secret_store))
+            .sum();
+        if unresolved > 0 {
+            self.error_message =
+                "Unlock the secret store to roll back the pool passwords in this snapshot"
+                    .to_string();
+            return;
+        }
+
+        if targets.is_empty() {
+            self.error_message = "Nothing restorable was captured for this rollback".to_string();
+            return;
+        }
+
+        options::start_options_preview(
+            &self.apply_preview,
+            title,
+            targets,
+            false,
+            &self.miner_locations,
+            Vec::new(),
+        );
+    }
+
+    fn apply_matching_profiles(&mut self) {
+        if let Some(message) = self
+            .option_profiles
+            .iter()
+            .find_map(profiles::profile_validation_message)
+        {
+            self.error_message = message;
+            return;
+        }
+
+        let mut targets = Vec::new();
+        let mut skipped_protected = Vec::new();
+        let mut unmatched = 0;
+        for miner in self.miners.lock().unwrap().iter() {
+            if self.protected_miners.contains(&miner.identity()) {
+                skipped_protected.push(miner.ip.clone());
+                continue;
+            }
+            match profiles::find_matching_profile(&self.option_profiles, miner, &self.saved_ranges)
+            {
+                Some(profile) => targets.push((miner.ip.clone(), profile.settings.clone())),
+                None => unmatched += 1,
+            }
+        }
+
+        if targets.is_empty() {
+            self.error_message = "No discovered miners match any profile".to_string();
+            return;
+        }
+
+        self.error_message = if unmatched > 0 {
+            format!("{unmatched} miner(s) match no profile and will be left unchanged")
+        } else {
+            String::new()
+        };
+        options::start_options_preview(
+            &self.apply_preview,
+            "Apply Matching Profiles".to_string(),
+            targets,
+            self.rollout_settings.enabled,
+            &self.miner_locations,
+            skipped_protected,
+        );
+    }
+
+    /// Opens the preview for writing the global options to `ips`, minus protected miners.
+    fn preview_global_options(&mut self, title: &str, ips: Vec<String>, no_targets: &str) {
+        let (ips, skipped_protected) =
+            safety::split_protected(ips, &self.miners.lock().unwrap(), &self.protected_miners);
+        if !self.global_options.has_any_enabled() {
+            self.error_message = "Enable at least one global option before applying".to_string();
+        } else if let Some(message) = self.global_options.tuning_validation_message() {
+            self.error_message = message;
+        } else if let Some(message) = self.global_options.pool_validation_message() {
+            self.error_message = message;
+        } else if ips.is_empty() && !skipped_protected.is_empty() {
+            self.error_message = "Every targeted miner is protected".to_string();
+        } else if ips.is_empty() {
+            self.error_message = no_targets.to_string();
+        } else {
+            self.error_message.clear();
+            let targets = ips
+                .into_iter()
+                .map(|ip| (ip, self.global_options.clone()))
+                .collect();
+            options::start_options_preview(
+                &self.apply_preview,
+                title.to_string(),
+                targets,
+                self.rollout_settings.enabled,
+                &self.miner_locations,
+                skipped_protected,
+            );
+        }
+    }
+
+    fn run_due_scheduled_actions(&mut self) {
+        let minute = chrono::Local::now().timestamp().div_euclid(60);
+        if minute <= self.last_schedule_minute {
+            return;
+        }
+        // The first check after startup only looks at the current minute
+        let last_minute = if self.last_schedule_minute == 0 {
+            minute - 1
+        } else {
+            self.last_schedule_minute
+        };
+        self.last_schedule_minute = minute;
+
+        let due: Vec<ScheduledAction> =
+            schedule::due_since(&self.scheduled_actions, last_minute, minute)
+                .into_iter()
+                .cloned()
+                .collect();
+        for action in due {
+            self.run_scheduled_action(&action, false);
+        }
+    }
+
+    /// `manual` runs were started by the operator with "Run now".
+    fn run_scheduled_action(&mut self, action: &ScheduledAction, manual: bool) {
+        let targets: Vec<(String, String)> = self
+            .miners
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|m| profiles::rules_match(&action.targets, m, &self.saved_ranges))
+            .filter(|m| !self.protected_miners.contains(&m.identity()))
+            .map(|m| (m.ip.clone(), m.identity()))
+            .collect();
+
+        let log = Arc::clone(&self.schedule_log);
+        let audit_log = Arc::clone(&self.audit_log);
+        let label = if manual { "Run now" } else { "Scheduled" };
+        let audit_entry = AuditEntry::new(
+            "Schedule",
+            &format!("{label}: {}", action.name),
+            targets.iter().map(|(ip, _)| ip.clone()).collect(),
+            action.task.describe(),
+        );
+        let mut run = ScheduleRunRecord {
+            at: chrono::Local::now().timestamp(),
+            action_name: action.name.clone(),
+            task: action.task.describe(),
+            targets: targets.len(),
+            failures: Vec::new(),
+        };
+        println!(
+            "✓ Running scheduled action '{}' ({})",
+            run.action_name, run.task
+        );
+
+        if targets.is_empty() {
+            run.failures
+                .push("No discovered miners match the targets".to_string());
+            log.lock().unwrap().record(run);
+            return;
+        }
+
+        match &action.task {
+            ScheduledTask::Miner(miner_action) => {
+                let miner_action = *miner_action;
+                let ips = targets.into_iter().map(|(ip, _)| ip).collect();
+                runtime::spawn(async move {
+                    let results = actions::run_action_on_many(ips, miner_action).await;
+                    audit::record(&audit_log, audit_entry.with_results(&results));
+                    run.failures = results
+                        .into_iter()
+                        .filter_map(|(_, result)| result.err())
+                        .collect();
+                    log.lock().unwrap().record(run);
+                });
+            }
+            ScheduledTask::ApplyProfile(profile_name) => {
+                let Some(profile) = self
+                    .option_profiles
+                    .iter()
+                    .find(|p| &p.name == profile_name)
+                else {
+                    run.failures
+                        .push(format!("Profile '{profile_name}' no longer exists"));
+                    log.lock().unwrap().record(run);
+                    return;
+                };
+                if let Some(message) = profiles::profile_validation_message(profile) {
+                    run.failures.push(message);
+                    log.lock().unwrap().record(run);
+                    return;
+                }
+
+                let identities = targets.iter().cloned().collect();
+                let recorder = SnapshotRecorder::start_job(
+                    &self.option_snapshots,
+                    &format!("Schedule: {}", action.name),
+                    identities,
+                );
+                let targets = targets
+                    .into_iter()
+                    .map(|(ip, _)| (ip, profile.settings.clone()))
+                    .collect();
+                let locations = Arc::new(self.miner_locations.clone());
+                runtime::spawn(async move {
+                    let results =
+                        options::apply_options_to_many(targets, recorder, locations).await;
+                    audit::record(&audit_log, audit_entry.with_results(&results));
+                    run.failures = results
+                        .into_iter()
+                        .filter_map(|(_, result)| result.err())
+                        .collect();
+                    log.lock().unwrap().record(run);
+                });
+            }
+        }
+    }
+
+    fn evaluate_thermal_protection(&mut self) {
+        if !self.thermal_settings.enabled {
+            return;
+        }
+
+        let readings: Vec<ThermalReading> = self
+            .miners
+            .lock()
+            .unwrap()
+            .iter()
+            .map(ThermalReading::from_miner)
+            .collect();
+
+        let triggers = {
+            let mut state = self.thermal_state.lock().unwrap();
+            let state = &mut *state;
+            let readings: Vec<ThermalReading> = readings
+                .into_iter()
+                .filter(|r| !state.in_flight.contains(&r.identity))
+                .collect();
+            thermal::evaluate_thermal(
+                &self.thermal_settings,
+                &readings,
+                &mut state.hot_streaks,
+                &state.escalations,
+            )
+        };
+        if triggers.is_empty() {
+            return;
+        }
+
+        let identities = triggers
+            .iter()
+            .map(|t| (t.ip.clone(), t.identity.clone()))
+            .collect();
+        let recorder =
+            SnapshotRecorder::start_job(&self.option_snapshots, "Thermal protection", identities);
+        let audit_entry = AuditEntry::new(
+            "Thermal",
+            "Escalate",
+            triggers.iter().map(|t| t.ip.clone()).collect(),
+            triggers
+                .iter()
+                .map(|t| format!("{}: {}", t.ip, t.step.describe()))
+                .collect::<Vec<_>>()
+                .join("; "),
+        );
+        thermal::execute_triggers(
+            Arc::clone(&self.thermal_state),
+            triggers,
+            Some(recorder),
+            Arc::new(self.miner_locations.clone()),
+            Arc::clone(&self.audit_log),
+            audit_entry,
+        );
+    }
+
+    fn revert_thermal_escalation(&mut self, identity: String) {
+        let Some(ip) = self
+            .thermal_state
+            .lock()
+            .unwrap()
+            .escalations
+            .get(&identity)
+            .map(|e| e.ip.clone())
+        else {
+            return;
+        };
+
+        let audit_entry = AuditEntry::new(
+            "Thermal",
+            "Revert escalation",
+            vec![ip.clone()],
+            String::new(),
+        );
+        let recorder = SnapshotRecorder::start_job(
+            &self.option_snapshots,
+            &format!("Thermal revert: {ip}"),
+            HashMap::from([(ip, identity.clone())]),
+        );
+        thermal::revert_escalation(
+            Arc::clone(&self.thermal_state),
+            identity,
+            recorder,
+            Arc::new(self.miner_locations.clone()),
+            Arc::clone(&self.audit_log),
+            audit_entry,
+        );
+    }
+
+    fn evaluate_watchdog(&mut self) {
+        if !self.watchdog_settings.enabled {
+            return;
+        }
+
+        // Miners held down on purpose are not underperforming
+        let mut excluded: HashSet<String> = self
+            .thermal_state
+            .lock()
+            .unwrap()
+            .escalations
+            .keys()
+            .cloned()
+            .collect();
+        excluded.extend(
+            self.power_cap_state
+                .lock()
+                .unwrap()
+                .curtailed
+                .keys()
+                .cloned(),
+        );
+
+        let problems = watchdog::detect_underperformers(
+            &self.watchdog_settings,
+            &self.miners.lock().unwrap(),
+            &excluded,
+        );
+        let triggers = self.watchdog_state.lock().unwrap().plan_recoveries(
+            &self.watchdog_settings,
+            &problems,
+            chrono::Local::now().timestamp(),
+        );
+        if !triggers.is_empty() {
+            let audit_entry = AuditEntry::new(
+                "Watchdog",
+                "Recovery",
+                triggers.iter().map(|t| t.ip.clone()).collect(),
+                triggers
+                    .iter()
+                    .map(|t| format!("{}: {}", t.ip, t.reason))
+                    .collect::<Vec<_>>()
+                    .join("; "),
+            );
+            watchdog::execute_recoveries(
+                Arc::clone(&self.watchdog_state),
+                triggers,
+                self.watchdog_settings.pause_secs,
+                Arc::clone(&self.audit_log),
+                audit_entry,
+            );
+        }
+    }
+
+    fn evaluate_power_cap(&mut self) {
+        if !self.power_cap_settings.enabled {
+            return;
+        }
+
+        let fleet: Vec<FleetMinerPower> = self
+            .miners
+            .lock()
+            .unwrap()
+            .iter()
+            .map(FleetMinerPower::from_miner)
+            .collect();
+
+        let total_watts = power_cap::fleet_power_watts(&fleet);
+        let steps = {
+            let mut state = self.power_cap_state.lock().unwrap();
+            if state.in_flight {
+                return;
+            }
+            state.last_total_watts = Some(total_watts);
+            power_cap::plan_power_cap(&self.power_cap_settings, &fleet, &state.curtailed)
+        };
+        if steps.is_empty() {
+            return;
+        }
+
+        let action = if total_watts > self.power_cap_settings.cap_watts {
+            "Step down"
+        } else {
+            "Restore"
+        };
+        let parameters = format!(
+            "fleet {:.0} W, cap {:.0} W",
+            total_watts, self.power_cap_settings.cap_watts
+        );
+        self.start_power_cap_steps(steps, action, parameters);
+    }
+
+    fn start_power_cap_steps(
+        &mut self,
+        steps: Vec<PowerCapStep>,
+        action: &str,
+        parameters: String,
+    ) {
+        let title = format!("Power cap: {}", action.to_lowercase());
+        let writes_options = steps.iter().any(|step| {
+            matches!(
+                step.action,
+                PowerCapAction::Limit(_) | PowerCapAction::Restore
+            )
+        });
+        let recorder = writes_options.then(|| {
+            let identities = steps
+                .iter()
+                .map(|step| (step.ip.clone(), step.identity.clone()))
+                .collect();
+            SnapshotRecorder::start_job(&self.option_snapshots, &title, identities)
+        });
+        let audit_entry = AuditEntry::new(
+            "Power cap",
+            action,
+            steps.iter().map(|step| step.ip.clone()).collect(),
+            parameters,
+        );
+
+        power_cap::execute_steps(
+            Arc::clone(&self.power_cap_state),
+            steps,
+            recorder,
+            Arc::new(self.miner_locations.clone()),
+            Arc::clone(&self.audit_log),
+            audit_entry,
+        );
+    }
+
     fn scan_all_saved_ranges(&mut self) {
         if self.saved_ranges.is_empty() {
             self.error_message = "No saved ranges to scan".to_string();
@@ -339,6 +1546,7 @@ impl MinerScannerApp {
             self.scan_control_state.identification_timeout_secs,
             self.scan_control_state.connectivity_timeout_secs,
             self.scan_control_state.connectivity_retries,
+            self.model_baselines.mode_specific_models(),
         );
 
         self.scan_control_state.last_scan_time = Some(Instant::now());
@@ -357,11 +1565,28 @@ impl eframe::App for MinerScannerApp {
         });
 
         // Request repaint while scanning
-        {
-            let progress = self.scan_progress.lock().unwrap();
-            if progress.scanning {
-                ctx.request_repaint();
+        let scanning = self.scan_progress.lock().unwrap().scanning;
+        if scanning {
+            ctx.request_repaint();
+        }
+
+        // Run fleet automations against fresh readings after each scan
+        if self.fleet_was_scanning && !scanning {
+            if !self.read_only {
+                self.evaluate_thermal_protection();
+                self.evaluate_power_cap();
+                self.evaluate_watchdog();
             }
+            self.reload_hashprice_file();
+            self.record_scan_history();
+            self.refresh_credential_registry();
+        }
+        self.fleet_was_scanning = scanning;
+
+        // Scans save the history files only every few minutes
+        if ctx.input(|i| i.viewport().close_requested()) {
+            self.energy_ledger.flush();
+            self.availability_log.flush();
         }
 
         // Show miner detail modal if one is selected
@@ -377,8 +1602,446 @@ impl eframe::App for MinerScannerApp {
             &self.global_options,
             Arc::clone(&self.miner_option_overrides),
             Arc::clone(&self.miner_options_prefill_pending),
+            Arc::clone(&self.apply_preview),
+            Arc::clone(&self.option_snapshots),
+            &mut self.miner_locations,
+            &self.audit_log,
         );
 
+        // Review pending option changes before anything is written
+        let mut apply_confirmed = false;
+        ui::draw_apply_preview_window(
+            ctx,
+            Arc::clone(&self.apply_preview),
+            &self.miners.lock().unwrap(),
+            &self.safety_settings,
+            &mut apply_confirmed,
+        );
+        if apply_confirmed {
+            self.apply_previewed_options();
+        }
+
+        let mut rollout_action = None;
+        if let Some(rollout) = self.staged_rollout.as_mut() {
+            rollout.tick();
+            ui::draw_rollout_window(ctx, rollout, &mut rollout_action);
+        }
+        match rollout_action {
+            Some(RolloutAction::Dismiss) => self.staged_rollout = None,
+            Some(action) => {
+                if let Some(rollout) = self.staged_rollout.as_mut() {
+                    let label = match action {
+                        RolloutAction::Continue => "Continue rollout",
+                        _ => "Abort rollout",
+                    };
+                    audit::record(
+                        &self.audit_log,
+                        AuditEntry::new(
+                            "Rollout",
+                            label,
+                            rollout
+                                .waves
+                                .iter()
+                                .flatten()
+                                .map(|(ip, _)| ip.clone())
+                                .collect(),
+                            rollout.title.clone(),
+                        )
+                        .dispatched(),
+                    );
+                    rollout.handle_action(action);
+                }
+            }
+            None => {}
+        }
+
+        let mut rollback_request = None;
+        if self.show_snapshots_window {
+            ui::draw_snapshots_window(
+                ctx,
+                &mut self.show_snapshots_window,
+                &self.option_snapshots.lock().unwrap(),
+                &mut rollback_request,
+            );
+        }
+        if let Some(request) = rollback_request {
+            self.start_rollback(request);
+        }
+
+        let mut apply_profiles_clicked = false;
+        if self.show_profiles_window {
+            ui::draw_profiles_window(
+                ctx,
+                &mut self.show_profiles_window,
+                &mut self.option_profiles,
+                &mut self.new_profile_name,
+                &mut self.global_options,
+                &self.saved_ranges,
+                &self.miners.lock().unwrap(),
+                &mut apply_profiles_clicked,
+            );
+        }
+        if apply_profiles_clicked {
+            self.apply_matching_profiles();
+        }
+
+        let mut audit_select_ips = None;
+        if self.show_pool_audit_window {
+            let entries = pool_audit::audit_fleet(
+                &self.miners.lock().unwrap(),
+                &self.option_profiles,
+                &self.saved_ranges,
+                &self.miner_locations,
+            );
+            ui::draw_pool_audit_window(
+                ctx,
+                &mut self.show_pool_audit_window,
+                &entries,
+                &mut self.pool_audit_show_unaudited,
+                &mut audit_select_ips,
+            );
+        }
+        if let Some(ips) = audit_select_ips {
+            self.selected_miners = ips.into_iter().collect();
+        }
+
+        let mut firmware_select_ips = None;
+        let mut firmware_export = false;
+        if self.show_firmware_window {
+            ui::draw_firmware_window(
+                ctx,
+                &mut self.show_firmware_window,
+                &self.miners.lock().unwrap(),
+                &mut self.approved_firmware,
+                &mut firmware_select_ips,
+                &mut firmware_export,
+            );
+        }
+        if let Some(ips) = firmware_select_ips {
+            self.selected_miners = ips.into_iter().collect();
+        }
+        if firmware_export {
+            self.export_firmware_report();
+        }
+
+        let mut health_select_ips = None;
+        let mut health_export = false;
+        if self.show_health_window {
+            let mut entries = health::fleet_health(&self.miners.lock().unwrap());
+            health::sort_health_report(&mut entries, self.health_sort);
+            ui::draw_health_window(
+                ctx,
+                &mut self.show_health_window,
+                &entries,
+                &mut self.health_thresholds,
+                &mut self.health_sort,
+                &mut self.health_only_candidates,
+                &mut health_select_ips,
+                &mut health_export,
+            );
+        }
+        if let Some(ips) = health_select_ips {
+            self.selected_miners = ips.into_iter().collect();
+        }
+        if health_export {
+            self.export_repair_list();
+        }
+
+        let mut baselines_changed = false;
+        let mut baselines_reload = false;
+        if self.show_baselines_window {
+            let learned = baselines::learned_baselines(&self.miners.lock().unwrap());
+            ui::draw_baselines_window(
+                ctx,
+                &mut self.show_baselines_window,
+                &mut self.model_baselines,
+                &learned,
+                &mut baselines_changed,
+                &mut baselines_reload,
+            );
+        }
+        let mut select_unprofitable = None;
+        let mut reload_hashprice = false;
+        if self.show_profitability_window {
+            let hashprice = self.current_hashprice();
+            let economics = hashprice
+                .as_ref()
+                .map(|hashprice| {
+                    profitability::fleet_economics(
+                        &self.profitability_settings,
+                        *hashprice,
+                        &self.miners.lock().unwrap(),
+                    )
+                })
+                .unwrap_or_default();
+            let by_range = profitability::economics_by_range(&economics, &self.saved_ranges);
+            ui::draw_profitability_window(
+                ctx,
+                &mut self.show_profitability_window,
+                &mut self.profitability_settings,
+                &hashprice,
+                &economics,
+                &by_range,
+                &mut self.profitability_only_unprofitable,
+                &mut reload_hashprice,
+                &mut select_unprofitable,
+            );
+        }
+        if let Some(ips) = select_unprofitable {
+            self.selected_miners = ips.into_iter().collect();
+        }
+        if reload_hashprice
+            || self.profitability_settings.hashprice_file
+                != self.prev_profitability_settings.hashprice_file
+            || self.profitability_settings.hashprice_source
+                != self.prev_profitability_settings.hashprice_source
+        {
+            self.reload_hashprice_file();
+        }
+
+        let mut energy_export = false;
+        if self.show_energy_window {
+            let months = self.energy_ledger.months();
+            let rows = energy::monthly_statement(
+                &self.energy_ledger,
+                &self.energy_month,
+                self.energy_grouping,
+                &self.saved_ranges,
+                &self.tenants_by_identity(self.energy_ledger.miners.keys()),
+            );
+            ui::draw_energy_window(
+                ctx,
+                &mut self.show_energy_window,
+                &months,
+                &mut self.energy_month,
+                &mut self.energy_grouping,
+                &rows,
+                &mut energy_export,
+            );
+        }
+        if energy_export {
+            self.export_energy_statement();
+        }
+
+        let mut bulk_decision = None;
+        if let Some(confirmation) = self.bulk_confirmation.as_mut() {
+            ui::draw_bulk_confirmation_window(
+                ctx,
+                confirmation,
+                &self.safety_settings,
+                &mut bulk_decision,
+            );
+        }
+        match bulk_decision {
+            Some(true) => {
+                if let Some(confirmation) = self.bulk_confirmation.take() {
+                    self.execute_bulk_action(confirmation);
+                }
+            }
+            Some(false) => self.bulk_confirmation = None,
+            None => {}
+        }
+
+        if self.show_safety_window {
+            ui::draw_safety_window(
+                ctx,
+                &mut self.show_safety_window,
+                &mut self.safety_settings,
+                &mut self.protected_miners,
+                &self.miners.lock().unwrap(),
+                &self.selected_miners,
+            );
+        }
+
+        let mut secrets_request = None;
+        if self.show_secrets_window {
+            ui::draw_secrets_window(
+                ctx,
+                &mut self.show_secrets_window,
+                &self.secret_store,
+                &mut self.secrets_form,
+                &mut secrets_request,
+            );
+        }
+        if let Some(request) = secrets_request {
+            self.handle_secrets_request(request);
+        }
+
+        if self.show_credentials_window {
+            ui::draw_credentials_window(
+                ctx,
+                &mut self.show_credentials_window,
+                &mut self.miner_credentials,
+                &mut self.new_credential,
+                &self.saved_ranges,
+                &self.miners.lock().unwrap(),
+                &self.selected_miners,
+                &credentials::auth_failures_snapshot(),
+            );
+        }
+
+        let mut audit_export = false;
+        if self.show_audit_window {
+            ui::draw_audit_window(
+                ctx,
+                &mut self.show_audit_window,
+                &self.audit_log.lock().unwrap(),
+                &mut self.audit_filter,
+                &mut audit_export,
+            );
+        }
+        if audit_export {
+            self.export_audit_log();
+        }
+
+        let mut sla_export = false;
+        if self.show_availability_window {
+            let rows = self.sla_rows();
+            ui::draw_availability_window(
+                ctx,
+                &mut self.show_availability_window,
+                &mut self.sla_settings,
+                &mut self.sla_period,
+                &mut self.sla_grouping,
+                &rows,
+                &mut sla_export,
+            );
+        }
+        if sla_export {
+            self.export_sla_report();
+        }
+
+        let mut tenant_export = None;
+        if self.show_tenants_window {
+            let mut miner_counts: HashMap<String, usize> = HashMap::new();
+            for miner in self.miners.lock().unwrap().iter() {
+                let tenant = tenants::tenant_for(
+                    miner,
+                    &self.tenants,
+                    &self.miner_locations,
+                    &self.saved_ranges,
+                )
+                .unwrap_or_else(|| tenants::UNASSIGNED.to_string());
+                *miner_counts.entry(tenant).or_default() += 1;
+            }
+            let report_rows = if self.tenant_report_period.validation_message().is_none() {
+                tenants::tenant_report(
+                    &self.energy_ledger,
+                    &self.tenants_by_identity(self.energy_ledger.miners.keys()),
+                    &self.tenant_report_period.from,
+                    &self.tenant_report_period.to,
+                )
+            } else {
+                Vec::new()
+            };
+            ui::draw_tenants_window(
+                ctx,
+                &mut self.show_tenants_window,
+                &mut self.tenants,
+                &mut self.new_tenant_name,
+                &self.saved_ranges,
+                &miner_counts,
+                &mut self.tenant_report_period,
+                &report_rows,
+                &mut tenant_export,
+            );
+        }
+        if let Some(format) = tenant_export {
+            self.export_tenant_report(format);
+        }
+
+        if baselines_reload {
+            self.model_baselines = BaselineDatabase::load();
+        } else if baselines_changed {
+            self.model_baselines.save();
+        }
+
+        let mut run_now = None;
+        if self.show_schedule_window {
+            ui::draw_schedule_window(
+                ctx,
+                &mut self.show_schedule_window,
+                &mut self.scheduled_actions,
+                &self.option_profiles,
+                &self.saved_ranges,
+                &self.miners.lock().unwrap(),
+                &self.schedule_log.lock().unwrap(),
+                &mut run_now,
+            );
+        }
+        if let Some(action) = run_now.and_then(|idx| self.scheduled_actions.get(idx).cloned()) {
+            self.run_scheduled_action(&action, true);
+        }
+        if !self.read_only {
+            self.run_due_scheduled_actions();
+        }
+        self.seal_snapshot_passwords();
+
+        let mut power_cap_release_all = false;
+        if self.show_power_cap_window {
+            let fleet_watts = self
+                .miners
+                .lock()
+                .unwrap()
+                .iter()
+                .filter_map(|m| m.wattage_w)
+                .sum();
+            ui::draw_power_cap_window(
+                ctx,
+                &mut self.show_power_cap_window,
+                &mut self.power_cap_settings,
+                &self.power_cap_state.lock().unwrap(),
+                fleet_watts,
+                &mut power_cap_release_all,
+            );
+        }
+        let mut thermal_revert = None;
+        let mut thermal_forget = None;
+        if self.show_thermal_window {
+            ui::draw_thermal_window(
+                ctx,
+                &mut self.show_thermal_window,
+                &mut self.thermal_settings,
+                &self.thermal_state.lock().unwrap(),
+                &mut thermal_revert,
+                &mut thermal_forget,
+            );
+        }
+        if let Some(identity) = thermal_revert {
+            self.revert_thermal_escalation(identity);
+        }
+        if let Some(identity) = thermal_forget {
+            self.thermal_state.lock().unwrap().forget(&identity);
+        }
+
+        let mut watchdog_reset = None;
+        if self.show_watchdog_window {
+            ui::draw_watchdog_window(
+                ctx,
+                &mut self.show_watchdog_window,
+                &mut self.watchdog_settings,
+                &self.watchdog_state.lock().unwrap(),
+                &mut watchdog_reset,
+            );
+        }
+        if let Some(identity) = watchdog_reset {
+            audit::record(
+                &self.audit_log,
+                AuditEntry::new("Watchdog", "Reset record", Vec::new(), identity.clone()),
+            );
+            self.watchdog_state
+                .lock()
+                .unwrap()
+                .records
+                .remove(&identity);
+        }
+
+        if power_cap_release_all {
+            let steps =
+                power_cap::plan_release_all(&self.power_cap_state.lock().unwrap().curtailed);
+            self.start_power_cap_steps(steps, "Release all", String::new());
+        }
+
         // Save config if any interval or scan parameter changed
         if self.detail_refresh_interval_secs != self.prev_detail_refresh_interval_secs
             || self.scan_control_state.auto_scan_interval_secs != self.prev_auto_scan_interval_secs
@@ -388,6 +2051,21 @@ impl eframe::App for MinerScannerApp {
                 != self.prev_connectivity_timeout_secs
             || self.scan_control_state.connectivity_retries != self.prev_connectivity_retries
             || self.global_options != self.prev_global_options
+            || self.rollout_settings != self.prev_rollout_settings
+            || self.option_profiles != self.prev_option_profiles
+            || self.miner_locations != self.prev_miner_locations
+            || self.scheduled_actions != self.prev_scheduled_actions
+            || self.power_cap_settings != self.prev_power_cap_settings
+            || self.thermal_settings != self.prev_thermal_settings
+            || self.watchdog_settings != self.prev_watchdog_settings
+            || self.health_thresholds != self.prev_health_thresholds
+            || self.profitability_settings != self.prev_profitability_settings
+            || self.tenants != self.prev_tenants
+            || self.sla_settings != self.prev_sla_settings
+            || self.safety_settings != self.prev_safety_settings
+            || self.protected_miners != self.prev_protected_miners
+            || self.miner_credentials != self.prev_miner_credentials
+            || self.approved_firmware != self.prev_approved_firmware
         {
             self.prev_detail_refresh_interval_secs = self.detail_refresh_interval_secs;
             self.prev_auto_scan_interval_secs = self.scan_control_state.auto_scan_interval_secs;
@@ -396,6 +2074,24 @@ impl eframe::App for MinerScannerApp {
             self.prev_connectivity_timeout_secs = self.scan_control_state.connectivity_timeout_secs;
             self.prev_connectivity_retries = self.scan_control_state.connectivity_retries;
             self.prev_global_options = self.global_options.clone();
+            self.prev_rollout_settings = self.rollout_settings.clone();
+            self.prev_option_profiles = self.option_profiles.clone();
+            self.prev_miner_locations = self.miner_locations.clone();
+            self.prev_scheduled_actions = self.scheduled_actions.clone();
+            self.prev_power_cap_settings = self.power_cap_settings.clone();
+            self.prev_thermal_settings = self.thermal_settings.clone();
+            self.prev_watchdog_settings = self.watchdog_settings.clone();
+            self.prev_health_thresholds = self.health_thresholds.clone();
+            self.prev_profitability_settings = self.profitability_settings.clone();
+            self.prev_tenants = self.tenants.clone();
+            self.prev_sla_settings = self.sla_settings.clone();
+            self.prev_safety_settings = self.safety_settings.clone();
+            self.prev_protected_miners = self.protected_miners.clone();
+            self.prev_approved_firmware = self.approved_firmware.clone();
+            if self.miner_credentials != self.prev_miner_credentials {
+                self.prev_miner_credentials = self.miner_credentials.clone();
+                self.refresh_credential_registry();
+            }
             self.save_config();
         }
 
@@ -446,6 +2142,271 @@ impl eframe::App for MinerScannerApp {
                                 .color(Color32::from_rgb(160, 160, 160))
                                 .monospace(),
                         );
+
+                        if self.read_only {
+                            ui.add_space(10.0);
+                            ui.label(
+                                egui::RichText::new("👁 READ-ONLY")
+                                    .size(11.0)
+                                    .color(Color32::from_rgb(255, 200, 100))
+                                    .monospace(),
+                            )
+                            .on_hover_text("Viewer mode: scanning and monitoring only");
+                        }
+
+                        ui.add_space(15.0);
+
+                        if !self.read_only
+                            && ui
+                                .button(
+                                    egui::RichText::new("🕘 HISTORY")
+                                        .size(11.0)
+                                        .color(Color32::WHITE)
+                                        .monospace(),
+                                )
+                                .on_hover_text("Option snapshots and rollback")
+                                .clicked()
+                        {
+                            self.show_snapshots_window = !self.show_snapshots_window;
+                        }
+
+                        if !self.read_only
+                            && ui
+                                .button(
+                                    egui::RichText::new("🗂 PROFILES")
+                                        .size(11.0)
+                                        .color(Color32::WHITE)
+                                        .monospace(),
+                                )
+                                .on_hover_text("Named option profiles per model and firmware")
+                                .clicked()
+                        {
+                            self.show_profiles_window = !self.show_profiles_window;
+                        }
+
+                        if ui
+                            .button(
+                                egui::RichText::new("🧭 POOL AUDIT")
+                                    .size(11.0)
+                                    .color(Color32::WHITE)
+                                    .monospace(),
+                            )
+                            .on_hover_text("Miners not on the pool or worker their profile expects")
+                            .clicked()
+                        {
+                            self.show_pool_audit_window = !self.show_pool_audit_window;
+                        }
+
+                        if ui
+                            .button(
+                                egui::RichText::new("🧬 FIRMWARE")
+                                    .size(11.0)
+                                    .color(Color32::WHITE)
+                                    .monospace(),
+                            )
+                            .on_hover_text("Firmware inventory and miners off the approved version")
+                            .clicked()
+                        {
+                            self.show_firmware_window = !self.show_firmware_window;
+                        }
+
+                        if !self.read_only
+                            && ui
+                                .button(
+                                    egui::RichText::new("⏰ SCHEDULE")
+                                        .size(11.0)
+                                        .color(Color32::WHITE)
+                                        .monospace(),
+                                )
+                                .on_hover_text("Scheduled pause, resume and profile changes")
+                                .clicked()
+                        {
+                            self.show_schedule_window = !self.show_schedule_window;
+                        }
+
+                        if !self.read_only
+                            && ui
+                                .button(
+                                    egui::RichText::new("⚡ POWER CAP")
+                                        .size(11.0)
+                                        .color(Color32::WHITE)
+                                        .monospace(),
+                                )
+                                .on_hover_text("Keep total fleet power under the site budget")
+                                .clicked()
+                        {
+                            self.show_power_cap_window = !self.show_power_cap_window;
+                        }
+
+                        if !self.read_only
+                            && ui
+                                .button(
+                                    egui::RichText::new("🌡 THERMAL")
+                                        .size(11.0)
+                                        .color(Color32::WHITE)
+                                        .monospace(),
+                                )
+                                .on_hover_text(
+                                    "Automatic fan, power and pause response to overheating",
+                                )
+                                .clicked()
+                        {
+                            self.show_thermal_window = !self.show_thermal_window;
+                        }
+
+                        if !self.read_only
+                            && ui
+                                .button(
+                                    egui::RichText::new("🩺 WATCHDOG")
+                                        .size(11.0)
+                                        .color(Color32::WHITE)
+                                        .monospace(),
+                                )
+                                .on_hover_text("Restart miners with dead boards or low hashrate")
+                                .clicked()
+                        {
+                            self.show_watchdog_window = !self.show_watchdog_window;
+                        }
+
+                        if ui
+                            .button(
+                                egui::RichText::new("🔬 HEALTH")
+                                    .size(11.0)
+                                    .color(Color32::WHITE)
+                                    .monospace(),
+                            )
+                            .on_hover_text(
+                                "Missing chips, board temperature spread and repair list",
+                            )
+                            .clicked()
+                        {
+                            self.show_health_window = !self.show_health_window;
+                        }
+
+                        if ui
+                            .button(
+                                egui::RichText::new("📐 BASELINES")
+                                    .size(11.0)
+                                    .color(Color32::WHITE)
+                                    .monospace(),
+                            )
+                            .on_hover_text("Nominal hashrate and power per model")
+                            .clicked()
+                        {
+                            self.show_baselines_window = !self.show_baselines_window;
+                        }
+
+                        if ui
+                            .button(
+                                egui::RichText::new("💲 PROFIT")
+                                    .size(11.0)
+                                    .color(Color32::WHITE)
+                                    .monospace(),
+                            )
+                            .on_hover_text(
+                                "Power cost, hashprice revenue and margin per miner and range",
+                            )
+                            .clicked()
+                        {
+                            self.show_profitability_window = !self.show_profitability_window;
+                        }
+
+                        if ui
+                            .button(
+                                egui::RichText::new("🔋 ENERGY")
+                                    .size(11.0)
+                                    .color(Color32::WHITE)
+                                    .monospace(),
+                            )
+                            .on_hover_text(
+                                "kWh per miner, range and customer with monthly statements",
+                            )
+                            .clicked()
+                        {
+                            self.show_energy_window = !self.show_energy_window;
+                        }
+
+                        if ui
+                            .button(
+                                egui::RichText::new("🏢 TENANTS")
+                                    .size(11.0)
+                                    .color(Color32::WHITE)
+                                    .monospace(),
+                            )
+                            .on_hover_text("Hosting customers, their miners and period reports")
+                            .clicked()
+                        {
+                            self.show_tenants_window = !self.show_tenants_window;
+                        }
+
+                        if ui
+                            .button(
+                                egui::RichText::new("⏱ SLA")
+                                    .size(11.0)
+                                    .color(Color32::WHITE)
+                                    .monospace(),
+                            )
+                            .on_hover_text(
+                                "Availability, reboots and downtime incidents per period",
+                            )
+                            .clicked()
+                        {
+                            self.show_availability_window = !self.show_availability_window;
+                        }
+
+                        if ui
+                            .button(
+                                egui::RichText::new("📝 AUDIT")
+                                    .size(11.0)
+                                    .color(Color32::WHITE)
+                                    .monospace(),
+                            )
+                            .on_hover_text("Who did what to which miners, and how it went")
+                            .clicked()
+                        {
+                            self.show_audit_window = !self.show_audit_window;
+                        }
+
+                        if !self.read_only
+                            && ui
+                                .button(
+                                    egui::RichText::new("🛡 SAFETY")
+                                        .size(11.0)
+                                        .color(Color32::WHITE)
+                                        .monospace(),
+                                )
+                                .on_hover_text("Bulk action confirmations and protected miners")
+                                .clicked()
+                        {
+                            self.show_safety_window = !self.show_safety_window;
+                        }
+
+                        if !self.read_only
+                            && ui
+                                .button(
+                                    egui::RichText::new("🔐 SECRETS")
+                                        .size(11.0)
+                                        .color(Color32::WHITE)
+                                        .monospace(),
+                                )
+                                .on_hover_text("Master passphrase for encrypted passwords")
+                                .clicked()
+                        {
+                            self.show_secrets_window = !self.show_secrets_window;
+                        }
+
+                        if ui
+                            .button(
+                                egui::RichText::new("🔑 CREDENTIALS")
+                                    .size(11.0)
+                                    .color(Color32::WHITE)
+                                    .monospace(),
+                            )
+                            .on_hover_text("Miner web UI logins per model, range or miner")
+                            .clicked()
+                        {
+                            self.show_credentials_window = !self.show_credentials_window;
+                        }
                     });
                 });
                 ui.add_space(15.0);
@@ -472,6 +2433,36 @@ impl eframe::App for MinerScannerApp {
             )
             .show(ctx, |ui| {
                 egui::ScrollArea::vertical().show(ui, |ui| {
+                    if !self.tenants.is_empty() {
+                        ui.horizontal(|ui| {
+                            ui.label(
+                                egui::RichText::new("TENANT VIEW")
+                                    .size(11.0)
+                                    .color(Color32::from_rgb(160, 160, 160))
+                                    .monospace(),
+                            );
+                            egui::ComboBox::from_id_salt("tenant_view")
+                                .selected_text(self.tenant_view.as_deref().unwrap_or("All miners"))
+                                .show_ui(ui, |ui| {
+                                    ui.selectable_value(&mut self.tenant_view, None, "All miners");
+                                    for tenant in &self.tenants {
+                                        ui.selectable_value(
+                                            &mut self.tenant_view,
+                                            Some(tenant.name.clone()),
+                                            &tenant.name,
+                                        );
+                                    }
+                                    ui.selectable_value(
+                                        &mut self.tenant_view,
+                                        Some(tenants::UNASSIGNED.to_string()),
+                                        tenants::UNASSIGNED,
+                                    );
+                                });
+                        });
+                        ui.add_space(10.0);
+                    }
+                    let tenant_scope = self.tenant_scope();
+
                     // Top row: two equal columns with explicit gutter
                     let column_gap = 20.0;
                     let total_top_width = ui.available_width();
@@ -510,18 +2501,38 @@ impl eframe::App for MinerScannerApp {
                             egui::vec2(column_width, 0.0),
                             egui::Layout::top_down(egui::Align::Min),
                             |ui| {
+                                let hashprice = self.current_hashprice().ok();
                                 let miners = self.miners.lock().unwrap();
-                                ui::draw_stats_card(ui, &miners);
-                                ui.add_space(12.0);
-                                ui::draw_global_options_card(
-                                    ui,
-                                    &mut self.global_options,
-                                    selected_count,
-                                    miners_count,
-                                    show_epic_tuning_presets,
-                                    &mut apply_global_selected_clicked,
-                                    &mut apply_global_all_clicked,
-                                );
+                                let in_scope = |ip: &String| {
+                                    tenant_scope.as_ref().is_none_or(|ips| ips.contains(ip))
+                                };
+                                let scoped: Vec<&MinerInfo> =
+                                    miners.iter().filter(|m| in_scope(&m.ip)).collect();
+                                let daily = hashprice.map(|hashprice| {
+                                    let economics: Vec<_> = profitability::fleet_economics(
+                                        &self.profitability_settings,
+                                        hashprice,
+                                        &miners,
+                                    )
+                                    .into_iter()
+                                    .filter(|e| in_scope(&e.ip))
+                                    .collect();
+                                    profitability::total_economics("Fleet", &economics)
+                                });
+                                ui::draw_stats_card(ui, &scoped, daily.as_ref());
+                                if !self.read_only {
+                                    ui.add_space(12.0);
+                                    ui::draw_global_options_card(
+                                        ui,
+                                        &mut self.global_options,
+                                        &mut self.rollout_settings,
+                                        selected_count,
+                                        miners_count,
+                                        show_epic_tuning_presets,
+                                        &mut apply_global_selected_clicked,
+                                        &mut apply_global_all_clicked,
+                                    );
+                                }
                             },
                         );
 
@@ -559,72 +2570,61 @@ impl eframe::App for MinerScannerApp {
                         self.load_saved_range(&range);
                     }
                     if apply_global_selected_clicked {
-                        let selected_ips: Vec<String> =
-                            self.selected_miners.iter().cloned().collect();
-                        if !self.global_options.has_any_enabled() {
-                            self.error_message =
-                                "Enable at least one global option before applying".to_string();
-                        } else if let Some(message) =
-                            self.global_options.tuning_validation_message()
-                        {
-                            self.error_message = message;
-                        } else if let Some(message) = self.global_options.pool_validation_message()
-                        {
-                            self.error_message = message;
-                        } else if selected_ips.is_empty() {
-                            self.error_message = "No selected miners to apply options".to_string();
-                        } else {
-                            self.error_message.clear();
-                            let settings = self.global_options.clone();
-                            runtime::spawn(async move {
-                                options::apply_options_to_many(selected_ips, settings).await;
-                            });
-                        }
+                        let selected_ips = self.selected_miners.iter().cloned().collect();
+                        self.preview_global_options(
+                            "Apply to Selected",
+                            selected_ips,
+                            "No selected miners to apply options",
+                        );
                     }
                     if apply_global_all_clicked {
-                        let all_ips: Vec<String> = self
+                        let all_ips = self
                             .miners
                             .lock()
                             .unwrap()
                             .iter()
                             .map(|m| m.ip.clone())
                             .collect();
-                        if !self.global_options.has_any_enabled() {
-                            self.error_message =
-                                "Enable at least one global option before applying".to_string();
-                        } else if let Some(message) =
-                            self.global_options.tuning_validation_message()
-                        {
-                            self.error_message = message;
-                        } else if let Some(message) = self.global_options.pool_validation_message()
-                        {
-                            self.error_message = message;
-                        } else if all_ips.is_empty() {
-                            self.error_message =
-                                "No discovered miners to apply options".to_string();
-                        } else {
-                            self.error_message.clear();
-                            let settings = self.global_options.clone();
-                            runtime::spawn(async move {
-                                options::apply_options_to_many(all_ips, settings).await;
-                            });
-                        }
+                        self.preview_global_options(
+                            "Apply to All",
+                            all_ips,
+                            "No discovered miners to apply options",
+                        );
                     }
 
                     ui.add_space(15.0);
 
                     let mut export_clicked = false;
-                    let clicked_column = ui::draw_miners_table(
-                        ui,
-                        &self.miners.lock().unwrap(),
-                        &mut self.search_query,
-                        &mut self.selected_miners,
-                        &mut self.detail_view_miners,
-                        self.sort_column,
-                        self.sort_direction,
-                        Arc::clone(&self.scan_progress),
-                        &mut export_clicked,
-                    );
+                    let mut bulk_request = None;
+                    let clicked_column = {
+                        let miners = self.miners.lock().unwrap();
+                        let percent_of_nominal =
+                            baselines::percent_of_nominal_by_ip(&self.model_baselines, &miners);
+                        let protected_ips = safety::protected_ips(&miners, &self.protected_miners);
+                        let auth_failures = credentials::auth_failures_snapshot();
+                        let restarts = restarts::snapshot();
+                        ui::draw_miners_table(
+                            ui,
+                            &miners,
+                            &mut self.search_query,
+                            &mut self.selected_miners,
+                            &mut self.detail_view_miners,
+                            &percent_of_nominal,
+                            tenant_scope.as_ref(),
+                            &protected_ips,
+                            &auth_failures,
+                            &restarts,
+                            self.sort_column,
+                            self.sort_direction,
+                            Arc::clone(&self.scan_progress),
+                            &mut bulk_request,
+                            &mut export_clicked,
+                        )
+                    };
+
+                    if let Some(action) = bulk_request {
+                        self.request_bulk_action(action);
+                    }
 
                     // Sort if a column header was clicked
                     if let Some(column) = clicked_column {
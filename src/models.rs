@@ -2,13 +2,14 @@ use asic_rs_core::data::miner::MinerData;
 use serde::{Deserialize, Serialize};
 use std::collections::VecDeque;
 
-#[derive(Clone, Copy, Debug, Default)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
 pub struct MinerCapabilities {
     pub set_power_limit: bool,
     pub fan_config: bool,
     pub tuning_config: bool,
     pub scaling_config: bool,
     pub pools_config: bool,
+    pub restart: bool,
 }
 
 #[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
@@ -52,6 +53,25 @@ impl Default for PoolInput {
     }
 }
 
+// Groups are listed in failover order: the miner falls back to the next group
+// when every pool in the current one is down
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PoolGroupInput {
+    pub name: String,
+    pub quota: u32,
+    pub pools: Vec<PoolInput>,
+}
+
+impl Default for PoolGroupInput {
+    fn default() -> Self {
+        Self {
+            name: "Primary".to_string(),
+            quota: 100,
+            pools: vec![PoolInput::default()],
+        }
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 #[serde(default)]
 pub struct MinerOptionSettings {
@@ -75,9 +95,9 @@ pub struct MinerOptionSettings {
     pub scaling_shutdown: bool,
     pub scaling_shutdown_duration: f32,
     pub apply_pool_config: bool,
-    pub pool_group_name: String,
-    pub pool_group_quota: u32,
-    pub pool_inputs: Vec<PoolInput>,
+    pub pool_groups: Vec<PoolGroupInput>,
+    pub pool_account: String,
+    pub verify_pools: bool,
 }
 
 impl Default for MinerOptionSettings {
@@ -103,14 +123,41 @@ impl Default for MinerOptionSettings {
             scaling_shutdown: false,
             scaling_shutdown_duration: 15.0,
             apply_pool_config: false,
-            pool_group_name: "Primary".to_string(),
-            pool_group_quota: 100,
-            pool_inputs: vec![PoolInput::default()],
+            pool_groups: vec![PoolGroupInput::default()],
+            pool_account: String::new(),
+            verify_pools: false,
         }
     }
 }
 
 impl MinerOptionSettings {
+    /// Rewrites settings saved before pool groups existed (a single
+    /// `pool_group_name`/`pool_group_quota`/`pool_inputs` set) into `pool_groups`.
+    pub fn upgrade_legacy_json(value: &mut serde_json::Value) {
+        let Some(object) = value.as_object_mut() else {
+            return;
+        };
+        if object.contains_key("pool_groups") || !object.contains_key("pool_inputs") {
+            return;
+        }
+
+        let name = object
+            .remove("pool_group_name")
+            .unwrap_or_else(|| serde_json::json!("Primary"));
+        let quota = object
+            .remove("pool_group_quota")
+            .unwrap_or_else(|| serde_json::json!(100));
+        let pools = object.remove("pool_inputs").unwrap_or_default();
+        object.insert(
+            "pool_groups".to_string(),
+            serde_json::json!([{ "name": name, "quota": quota, "pools": pools }]),
+        );
+    }
+
+    pub fn all_pools(&self) -> impl Iterator<Item = &PoolInput> {
+        self.pool_groups.iter().flat_map(|group| group.pools.iter())
+    }
+
     pub fn has_any_enabled(&self) -> bool {
         self.apply_power_limit
             || self.apply_fan_config
@@ -124,16 +171,52 @@ impl MinerOptionSettings {
             return None;
         }
 
-        if self.pool_inputs.is_empty() {
-            return Some("At least one pool entry is required".to_string());
+        if self.pool_groups.is_empty() {
+            return Some("At least one pool group is required".to_string());
         }
 
-        for (idx, pool) in self.pool_inputs.iter().enumerate() {
-            if pool.url.trim().is_empty() {
-                return Some(format!("Pool {} URL is required", idx + 1));
+        for (group_idx, group) in self.pool_groups.iter().enumerate() {
+            let group_label = if self.pool_groups.len() > 1 {
+                format!("Group {} pool", group_idx + 1)
+            } else {
+                "Pool".to_string()
+            };
+
+            if group.pools.is_empty() {
+                return Some(format!(
+                    "Group {} needs at least one pool entry",
+                    group_idx + 1
+                ));
             }
-            if pool.username.trim().is_empty() {
-                return Some(format!("Pool {} username is required", idx + 1));
+            if group.quota == 0 {
+                return Some(format!("Group {} quota must be at least 1", group_idx + 1));
+            }
+
+            for (idx, pool) in group.pools.iter().enumerate() {
+                if pool.url.trim().is_empty() {
+                    return Some(format!("{group_label} {} URL is required", idx + 1));
+                }
+                if let Err(err) = crate::pools::parse_pool_url(&pool.url) {
+                    return Some(format!("{group_label} {}: {err}", idx + 1));
+                }
+                if pool.username.trim().is_empty() {
+                    return Some(format!("{group_label} {} username is required", idx + 1));
+                }
+                if let Some(err) = crate::workers::worker_template_error(&pool.username) {
+                    return Some(format!("{group_label} {}: {err}", idx + 1));
+                }
+                if crate::secrets::is_secret_ref(&pool.password) {
+                    return Some(format!(
+                        "{group_label} {} password is locked; unlock 🔐 SECRETS",
+                        idx + 1
+                    ));
+                }
+                if pool.username.contains("{account}") && self.pool_account.trim().is_empty() {
+                    return Some(format!(
+                        "{group_label} {} uses {{account}} but no account is set",
+                        idx + 1
+                    ));
+                }
             }
         }
 
@@ -169,7 +252,83 @@ impl MinerOptionSettings {
     }
 }
 
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub enum EpicFirmwareFilter {
+    #[default]
+    Any,
+    EpicOnly,
+    StockOnly,
+}
+
+// Empty strings match everything; a profile applies when every rule matches
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct ProfileMatchRules {
+    pub model_contains: String,
+    pub firmware_contains: String,
+    pub epic_firmware: EpicFirmwareFilter,
+    pub saved_range: String,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct OptionProfile {
+    pub name: String,
+    #[serde(default)]
+    pub rules: ProfileMatchRules,
+    pub settings: MinerOptionSettings,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct OptionChange {
+    pub field: String,
+    pub current: String,
+    pub requested: String,
+}
+
+#[derive(Clone, Debug)]
+pub struct OptionsPreviewEntry {
+    pub ip: String,
+    pub settings: MinerOptionSettings,
+    pub changes: Vec<OptionChange>,
+    pub unsupported: Vec<String>,
+    pub error: Option<String>,
+}
+
+impl OptionsPreviewEntry {
+    pub fn will_write(&self) -> bool {
+        self.error.is_none() && !self.changes.is_empty()
+    }
+}
+
+// Pending apply job waiting for operator confirmation after the dry run
+#[derive(Clone, Debug)]
+pub struct OptionsApplyPreview {
+    pub title: String,
+    pub started_at: std::time::Instant,
+    pub total_targets: usize,
+    pub staged: bool,
+    pub loading: bool,
+    pub entries: Vec<OptionsPreviewEntry>,
+    pub pool_checks: Vec<PoolCheckResult>,
+    /// Protected miners left out of this job.
+    pub skipped_protected: Vec<String>,
+    /// What the operator has typed so far when a typed confirmation is required.
+    pub confirm_text: String,
+}
+
+impl OptionsApplyPreview {
+    pub fn pool_checks_failed(&self) -> bool {
+        self.pool_checks.iter().any(|check| check.result.is_err())
+    }
+}
+
 #[derive(Clone, Debug)]
+pub struct PoolCheckResult {
+    pub url: String,
+    pub result: Result<String, String>,
+}
+
+#[derive(Clone, Debug, Default)]
 pub struct MinerInfo {
     pub ip: String,
     pub hostname: String,
@@ -194,6 +353,8 @@ pub struct MinerInfo {
     pub active_boards_count: Option<usize>,
     pub total_boards_count: Option<usize>,
     pub capabilities: MinerCapabilities,
+    /// Mining mode preset reported at the last scan, if the miner runs one.
+    pub mining_mode: Option<MiningModeSelection>,
 }
 
 impl MinerInfo {
@@ -212,6 +373,28 @@ impl MinerInfo {
 
         from_version || from_data
     }
+
+    /// Stable key for per-miner records: the MAC address when known, since IPs
+    /// can move between DHCP leases.
+    pub fn identity(&self) -> String {
+        self.mac_address().unwrap_or_else(|| self.ip.clone())
+    }
+}
+
+// Physical position of a miner, keyed by `MinerInfo::identity()`
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct MinerLocation {
+    pub rack: String,
+    pub slot: String,
+    /// Hosting tenant the miner is assigned to, overriding tenant rules.
+    #[serde(default, alias = "customer")]
+    pub tenant: String,
+}
+
+impl MinerLocation {
+    pub fn is_empty(&self) -> bool {
+        self.rack.trim().is_empty() && self.slot.trim().is_empty() && self.tenant.trim().is_empty()
+    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -248,6 +431,7 @@ pub enum SortColumn {
     ControlBoard,
     ActiveBoards,
     Hashrate,
+    PercentOfNominal,
     Wattage,
     Efficiency,
     Temperature,
@@ -1,7 +1,11 @@
+use crate::credentials;
 use crate::models::{
-    FanModeSelection, MinerOptionSettings, MiningModeSelection, PoolInput, TuningTargetSelection,
+    FanModeSelection, MinerCapabilities, MinerLocation, MinerOptionSettings, MiningModeSelection,
+    OptionChange, OptionsApplyPreview, OptionsPreviewEntry, PoolGroupInput, PoolInput,
+    TuningTargetSelection,
 };
-use asic_rs::MinerFactory;
+use crate::snapshots::SnapshotRecorder;
+use crate::workers::{self, WorkerNameVars};
 use asic_rs_core::{
     config::{
         fan::FanConfig,
@@ -17,6 +21,9 @@ use asic_rs_core::{
 };
 use futures::stream::{self, StreamExt};
 use measurements::Power;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
 
 fn map_mining_mode(mode: MiningModeSelection) -> MiningMode {
     match mode {
@@ -26,7 +33,7 @@ fn map_mining_mode(mode: MiningModeSelection) -> MiningMode {
     }
 }
 
-fn map_mining_mode_selection(mode: MiningMode) -> MiningModeSelection {
+pub fn map_mining_mode_selection(mode: MiningMode) -> MiningModeSelection {
     match mode {
         MiningMode::Low => MiningModeSelection::Low,
         MiningMode::Normal => MiningModeSelection::Normal,
@@ -54,20 +61,46 @@ pub async fn fetch_current_options(
     ip: String,
     defaults: MinerOptionSettings,
 ) -> Result<MinerOptionSettings, String> {
-    let factory = MinerFactory::new();
-    let parsed_ip = ip
-        .parse()
-        .map_err(|e| format!("Invalid IP address {ip}: {e}"))?;
-
-    let Some(miner) = factory
-        .get_miner(parsed_ip)
+    fetch_current_options_with_capabilities(ip, defaults)
         .await
-        .map_err(|e| format!("Failed to connect to {ip}: {e}"))?
-    else {
-        return Err(format!("No supported miner found at {ip}"));
+        .map(|(settings, _)| settings)
+}
+
+pub async fn fetch_current_options_with_capabilities(
+    ip: String,
+    defaults: MinerOptionSettings,
+) -> Result<(MinerOptionSettings, MinerCapabilities), String> {
+    let result = read_current_options(&ip, defaults).await;
+    // A section that could not be read is how a rejected login shows up here
+    let outcome = match &result {
+        Ok((_, _, Some(err))) | Err(err) => Err(err.clone()),
+        Ok(_) => Ok(()),
+    };
+    credentials::record_outcome(&ip, &outcome);
+    result.map(|(settings, capabilities, _)| (settings, capabilities))
+}
+
+/// Also returns the error of the first section that could not be read.
+async fn read_current_options(
+    ip: &str,
+    defaults: MinerOptionSettings,
+) -> Result<(MinerOptionSettings, MinerCapabilities, Option<String>), String> {
+    let miner = credentials::connect(ip).await?;
+
+    let capabilities = MinerCapabilities {
+        set_power_limit: miner.supports_set_power_limit(),
+        fan_config: miner.supports_fan_config(),
+        tuning_config: miner.supports_tuning_config(),
+        scaling_config: miner.supports_scaling_config(),
+        pools_config: miner.supports_pools_config(),
+        restart: miner.supports_restart(),
     };
 
     let mut settings = defaults;
+    let mut read_error = None;
+    let mut note_error = |err: String| {
+        read_error.get_or_insert(format!("Could not read options from {ip}: {err}"));
+    };
 
     if let Some(target) = miner.get_tuning_target().await {
         match target {
@@ -94,114 +127,523 @@ pub async fn fetch_current_options(
     }
 
     if miner.supports_fan_config() {
-        if let Ok(fan_config) = miner.get_fan_config().await {
-            settings.apply_fan_config = true;
-            match fan_config {
-                FanConfig::Auto {
-                    target_temp,
-                    idle_speed,
-                } => {
-                    settings.fan_mode = FanModeSelection::Auto;
-                    settings.fan_target_temp_c = target_temp;
-                    if let Some(idle) = idle_speed {
-                        settings.fan_idle_speed_percent = idle;
+        match miner.get_fan_config().await {
+            Err(e) => note_error(e.to_string()),
+            Ok(fan_config) => {
+                settings.apply_fan_config = true;
+                match fan_config {
+                    FanConfig::Auto {
+                        target_temp,
+                        idle_speed,
+                    } => {
+                        settings.fan_mode = FanModeSelection::Auto;
+                        settings.fan_target_temp_c = target_temp;
+                        if let Some(idle) = idle_speed {
+                            settings.fan_idle_speed_percent = idle;
+                        }
+                    }
+                    FanConfig::Manual { fan_speed } => {
+                        settings.fan_mode = FanModeSelection::Manual;
+                        settings.fan_speed_percent = fan_speed;
                     }
-                }
-                FanConfig::Manual { fan_speed } => {
-                    settings.fan_mode = FanModeSelection::Manual;
-                    settings.fan_speed_percent = fan_speed;
                 }
             }
         }
     }
 
     if miner.supports_tuning_config() {
-        if let Ok(tuning_config) = miner.get_tuning_config().await {
-            if let Some(algorithm) = tuning_config.algorithm {
-                settings.tuning_algorithm = algorithm;
-            }
-
-            match tuning_config.target {
-                TuningTarget::MiningMode(mode) => {
-                    settings.apply_tuning_config = true;
-                    settings.tuning_target = TuningTargetSelection::MiningMode;
-                    settings.mining_mode = map_mining_mode_selection(mode);
+        match miner.get_tuning_config().await {
+            Err(e) => note_error(e.to_string()),
+            Ok(tuning_config) => {
+                if let Some(algorithm) = tuning_config.algorithm {
+                    settings.tuning_algorithm = algorithm;
                 }
-                TuningTarget::Power(power) if miner.supports_set_power_limit() => {
-                    settings.apply_power_limit = true;
-                    settings.power_limit_watts = power.as_watts();
-                    settings.apply_tuning_config = true;
-                    settings.tuning_target = TuningTargetSelection::Power;
-                    settings.tuning_power_watts = power.as_watts();
-                }
-                TuningTarget::HashRate(hashrate) => {
-                    settings.apply_tuning_config = true;
-                    settings.tuning_target = TuningTargetSelection::Hashrate;
-                    settings.tuning_hashrate_ths =
-                        hashrate.clone().as_unit(HashRateUnit::TeraHash).value;
-                    settings.tuning_hashrate_algo = hashrate.algo;
+
+                match tuning_config.target {
+                    TuningTarget::MiningMode(mode) => {
+                        settings.apply_tuning_config = true;
+                        settings.tuning_target = TuningTargetSelection::MiningMode;
+                        settings.mining_mode = map_mining_mode_selection(mode);
+                    }
+                    TuningTarget::Power(power) if miner.supports_set_power_limit() => {
+                        settings.apply_power_limit = true;
+                        settings.power_limit_watts = power.as_watts();
+                        settings.apply_tuning_config = true;
+                        settings.tuning_target = TuningTargetSelection::Power;
+                        settings.tuning_power_watts = power.as_watts();
+                    }
+                    TuningTarget::HashRate(hashrate) => {
+                        settings.apply_tuning_config = true;
+                        settings.tuning_target = TuningTargetSelection::Hashrate;
+                        settings.tuning_hashrate_ths =
+                            hashrate.clone().as_unit(HashRateUnit::TeraHash).value;
+                        settings.tuning_hashrate_algo = hashrate.algo;
+                    }
+                    _ => {}
                 }
-                _ => {}
             }
         }
     }
 
     if miner.supports_scaling_config() {
-        if let Ok(scaling) = miner.get_scaling_config().await {
-            settings.apply_scaling_config = true;
-            settings.scaling_step = scaling.step;
-            settings.scaling_minimum = scaling.minimum;
-            settings.scaling_shutdown = scaling.shutdown.unwrap_or(false);
-            if let Some(duration) = scaling.shutdown_duration {
-                settings.scaling_shutdown_duration = duration;
+        match miner.get_scaling_config().await {
+            Err(e) => note_error(e.to_string()),
+            Ok(scaling) => {
+                settings.apply_scaling_config = true;
+                settings.scaling_step = scaling.step;
+                settings.scaling_minimum = scaling.minimum;
+                settings.scaling_shutdown = scaling.shutdown.unwrap_or(false);
+                if let Some(duration) = scaling.shutdown_duration {
+                    settings.scaling_shutdown_duration = duration;
+                }
             }
         }
     }
 
     if miner.supports_pools_config() {
-        if let Ok(pool_groups) = miner.get_pools_config().await {
-            if let Some(group) = pool_groups.first() {
-                if !group.pools.is_empty() {
+        match miner.get_pools_config().await {
+            Err(e) => note_error(e.to_string()),
+            Ok(pool_groups) => {
+                // Keep every group in the order the miner reports, so failover
+                // ordering survives a fetch/apply round trip
+                let groups: Vec<PoolGroupInput> = pool_groups
+                    .iter()
+                    .filter(|group| !group.pools.is_empty())
+                    .enumerate()
+                    .map(|(idx, group)| PoolGroupInput {
+                        name: if group.name.trim().is_empty() {
+                            default_pool_group_name(idx)
+                        } else {
+                            group.name.clone()
+                        },
+                        quota: group.quota,
+                        pools: group
+                            .pools
+                            .iter()
+                            .map(|pool| PoolInput {
+                                url: pool.url.to_string(),
+                                username: pool.username.clone(),
+                                password: pool.password.clone(),
+                            })
+                            .collect(),
+                    })
+                    .collect();
+
+                if !groups.is_empty() {
                     settings.apply_pool_config = true;
-                    settings.pool_group_name = if group.name.trim().is_empty() {
-                        "Primary".to_string()
+                    settings.pool_groups = groups;
+                }
+            }
+        }
+    }
+
+    Ok((settings, capabilities, read_error))
+}
+
+fn default_pool_group_name(idx: usize) -> String {
+    if idx == 0 {
+        "Primary".to_string()
+    } else {
+        format!("Backup {idx}")
+    }
+}
+
+fn push_change(
+    changes: &mut Vec<OptionChange>,
+    field: &str,
+    current: Option<String>,
+    requested: String,
+) {
+    if current.as_deref() == Some(requested.as_str()) {
+        return;
+    }
+
+    changes.push(OptionChange {
+        field: field.to_string(),
+        current: current.unwrap_or_else(|| "unknown".to_string()),
+        requested,
+    });
+}
+
+fn describe_tuning_target(settings: &MinerOptionSettings) -> String {
+    match settings.tuning_target {
+        TuningTargetSelection::MiningMode => format!("mode {:?}", settings.mining_mode),
+        TuningTargetSelection::Power => format!("{} W", settings.tuning_power_watts.round()),
+        TuningTargetSelection::Hashrate => format!(
+            "{:.2} TH/s ({})",
+            settings.tuning_hashrate_ths,
+            settings.tuning_hashrate_algo.trim()
+        ),
+    }
+}
+
+/// Field-by-field diff of the enabled sections of `requested` against what the
+/// miner currently reports. Sections the miner cannot configure are returned
+/// separately instead of as changes.
+pub fn diff_options(
+    current: &MinerOptionSettings,
+    requested: &MinerOptionSettings,
+    capabilities: MinerCapabilities,
+) -> (Vec<OptionChange>, Vec<String>) {
+    let mut changes = Vec::new();
+    let mut unsupported = Vec::new();
+
+    if requested.apply_power_limit {
+        if !capabilities.set_power_limit {
+            unsupported.push("power".to_string());
+        } else {
+            push_change(
+                &mut changes,
+                "power_limit",
+                current
+                    .apply_power_limit
+                    .then(|| format!("{} W", current.power_limit_watts.round())),
+                format!("{} W", requested.power_limit_watts.round()),
+            );
+        }
+    }
+
+    if requested.apply_fan_config {
+        if !capabilities.fan_config {
+            unsupported.push("fan".to_string());
+        } else {
+            let known = current.apply_fan_config;
+            push_change(
+                &mut changes,
+                "fan_mode",
+                known.then(|| format!("{:?}", current.fan_mode)),
+                format!("{:?}", requested.fan_mode),
+            );
+            match requested.fan_mode {
+                FanModeSelection::Auto => {
+                    push_change(
+                        &mut changes,
+                        "fan_target_temp",
+                        (known && current.fan_mode == FanModeSelection::Auto)
+                            .then(|| format!("{} C", current.fan_target_temp_c)),
+                        format!("{} C", requested.fan_target_temp_c),
+                    );
+                    push_change(
+                        &mut changes,
+                        "fan_idle_speed",
+                        (known && current.fan_mode == FanModeSelection::Auto)
+                            .then(|| format!("{}%", current.fan_idle_speed_percent)),
+                        format!("{}%", requested.fan_idle_speed_percent),
+                    );
+                }
+                FanModeSelection::Manual => {
+                    push_change(
+                        &mut changes,
+                        "fan_speed",
+                        (known && current.fan_mode == FanModeSelection::Manual)
+                            .then(|| format!("{}%", current.fan_speed_percent)),
+                        format!("{}%", requested.fan_speed_percent),
+                    );
+                }
+            }
+        }
+    }
+
+    if requested.apply_tuning_config {
+        if !capabilities.tuning_config {
+            unsupported.push("tuning".to_string());
+        } else {
+            push_change(
+                &mut changes,
+                "tuning_target",
+                current
+                    .apply_tuning_config
+                    .then(|| describe_tuning_target(current)),
+                describe_tuning_target(requested),
+            );
+            if !requested.tuning_algorithm.trim().is_empty() {
+                push_change(
+                    &mut changes,
+                    "tuning_algorithm",
+                    (!current.tuning_algorithm.trim().is_empty())
+                        .then(|| current.tuning_algorithm.trim().to_string()),
+                    requested.tuning_algorithm.trim().to_string(),
+                );
+            }
+        }
+    }
+
+    if requested.apply_scaling_config {
+        if !capabilities.scaling_config {
+            unsupported.push("scaling".to_string());
+        } else {
+            let known = current.apply_scaling_config;
+            push_change(
+                &mut changes,
+                "scaling_step",
+                known.then(|| current.scaling_step.to_string()),
+                requested.scaling_step.to_string(),
+            );
+            push_change(
+                &mut changes,
+                "scaling_minimum",
+                known.then(|| current.scaling_minimum.to_string()),
+                requested.scaling_minimum.to_string(),
+            );
+            push_change(
+                &mut changes,
+                "scaling_shutdown",
+                known.then(|| current.scaling_shutdown.to_string()),
+                requested.scaling_shutdown.to_string(),
+            );
+            if requested.scaling_shutdown {
+                push_change(
+                    &mut changes,
+                    "scaling_shutdown_duration",
+                    (known && current.scaling_shutdown)
+                        .then(|| format!("{} s", current.scaling_shutdown_duration)),
+                    format!("{} s", requested.scaling_shutdown_duration),
+                );
+            }
+        }
+    }
+
+    if requested.apply_pool_config {
+        if !capabilities.pools_config {
+            unsupported.push("pools".to_string());
+        } else {
+            let known = current.apply_pool_config;
+            let describe_group =
+                |group: &PoolGroupInput| format!("{} ({}%)", group.name.trim(), group.quota);
+            let describe_pool =
+                |pool: &PoolInput| format!("{} ({})", pool.url.trim(), pool.username.trim());
+
+            let group_count =
+                requested
+                    .pool_groups
+                    .len()
+                    .max(if known { current.pool_groups.len() } else { 0 });
+            for group_idx in 0..group_count {
+                let current_group = current.pool_groups.get(group_idx);
+                let requested_group = requested.pool_groups.get(group_idx);
+                let prefix = if group_count > 1 {
+                    format!("group_{}", group_idx + 1)
+                } else {
+                    "pool_group".to_string()
+                };
+
+                push_change(
+                    &mut changes,
+                    &prefix,
+                    known.then(|| {
+                        current_group
+                            .map(describe_group)
+                            .unwrap_or_else(|| "none".to_string())
+                    }),
+                    requested_group
+                        .map(describe_group)
+                        .unwrap_or_else(|| "none".to_string()),
+                );
+
+                let pool_count = requested_group.map_or(0, |g| g.pools.len()).max(if known {
+                    current_group.map_or(0, |g| g.pools.len())
+                } else {
+                    0
+                });
+                for idx in 0..pool_count {
+                    let field = if group_count > 1 {
+                        format!("{prefix}.pool_{}", idx + 1)
                     } else {
-                        group.name.clone()
+                        format!("pool_{}", idx + 1)
                     };
-                    settings.pool_group_quota = group.quota;
-                    settings.pool_inputs = group
-                        .pools
-                        .iter()
-                        .map(|pool| PoolInput {
-                            url: pool.url.to_string(),
-                            username: pool.username.clone(),
-                            password: pool.password.clone(),
-                        })
-                        .collect();
+                    push_change(
+                        &mut changes,
+                        &field,
+                        known.then(|| {
+                            current_group
+                                .and_then(|g| g.pools.get(idx))
+                                .map(describe_pool)
+                                .unwrap_or_else(|| "none".to_string())
+                        }),
+                        requested_group
+                            .and_then(|g| g.pools.get(idx))
+                            .map(describe_pool)
+                            .unwrap_or_else(|| "none".to_string()),
+                    );
+
+                    // Passwords are compared but never shown. Firmware that
+                    // does not report them leaves the current one blank.
+                    let current_pool = current_group.and_then(|g| g.pools.get(idx));
+                    let requested_pool = requested_group.and_then(|g| g.pools.get(idx));
+                    if let (true, Some(current_pool), Some(requested_pool)) =
+                        (known, current_pool, requested_pool)
+                    {
+                        if !current_pool.password.is_empty()
+                            && current_pool.password != requested_pool.password
+                        {
+                            changes.push(OptionChange {
+                                field: format!("{field}.password"),
+                                current: "hidden".to_string(),
+                                requested: "password changed".to_string(),
+                            });
+                        }
+                    }
                 }
             }
         }
     }
 
-    Ok(settings)
+    (changes, unsupported)
+}
+
+/// Replaces templated pool usernames with this miner's worker name, reading
+/// hostname, MAC and serial from the miner itself.
+pub async fn resolve_worker_names_for_miner(
+    ip: &str,
+    settings: &MinerOptionSettings,
+    locations: &HashMap<String, MinerLocation>,
+) -> Result<MinerOptionSettings, String> {
+    if !workers::settings_use_worker_templates(settings) {
+        return Ok(settings.clone());
+    }
+
+    let miner = credentials::connect(ip).await?;
+    let data = miner.get_data().await;
+    let vars = WorkerNameVars::from_data(ip, &data, &settings.pool_account, locations);
+    workers::resolve_worker_names(settings, &vars)
+}
+
+/// Dry run for an apply job: reads every target's current options and diffs
+/// them against the settings that would be written, without changing anything.
+pub async fn preview_options_for_many(
+    targets: Vec<(String, MinerOptionSettings)>,
+    locations: Arc<HashMap<String, MinerLocation>>,
+) -> Vec<OptionsPreviewEntry> {
+    if targets.is_empty() {
+        return Vec::new();
+    }
+
+    let concurrency = targets.len().clamp(1, 24);
+
+    stream::iter(targets)
+        .map(|(ip, settings)| {
+            let locations = Arc::clone(&locations);
+            async move {
+                let result = async {
+                    let current = fetch_current_options_with_capabilities(
+                        ip.clone(),
+                        MinerOptionSettings::default(),
+                    )
+                    .await?;
+                    let resolved =
+                        resolve_worker_names_for_miner(&ip, &settings, &locations).await?;
+                    Ok::<_, String>((current, resolved))
+                }
+                .await;
+
+                match result {
+                    Ok(((current, capabilities), resolved)) => {
+                        let (changes, unsupported) =
+                            diff_options(&current, &resolved, capabilities);
+                        OptionsPreviewEntry {
+                            ip,
+                            settings,
+                            changes,
+                            unsupported,
+                            error: None,
+                        }
+                    }
+                    Err(err) => OptionsPreviewEntry {
+                        ip,
+                        settings,
+                        changes: Vec::new(),
+                        unsupported: Vec::new(),
+                        error: Some(err),
+                    },
+                }
+            }
+        })
+        .buffer_unordered(concurrency)
+        .collect()
+        .await
+}
+
+/// Opens the confirmation preview for an apply job and fills it in the background.
+pub fn start_options_preview(
+    apply_preview: &Arc<Mutex<Option<OptionsApplyPreview>>>,
+    title: String,
+    targets: Vec<(String, MinerOptionSettings)>,
+    staged: bool,
+    locations: &HashMap<String, MinerLocation>,
+    skipped_protected: Vec<String>,
+) {
+    let started_at = Instant::now();
+    *apply_preview.lock().unwrap() = Some(OptionsApplyPreview {
+        title,
+        started_at,
+        total_targets: targets.len(),
+        staged,
+        loading: true,
+        entries: Vec::new(),
+        pool_checks: Vec::new(),
+        skipped_protected,
+        confirm_text: String::new(),
+    });
+
+    // Pools are checked once per distinct URL, only for settings that ask for it
+    let mut pool_urls: Vec<String> = targets
+        .iter()
+        .filter(|(_, settings)| settings.apply_pool_config && settings.verify_pools)
+        .flat_map(|(_, settings)| settings.all_pools().map(|pool| pool.url.trim().to_string()))
+        .collect();
+    pool_urls.sort();
+    pool_urls.dedup();
+
+    let apply_preview = Arc::clone(apply_preview);
+    let locations = Arc::new(locations.clone());
+    crate::runtime::spawn(async move {
+        let (mut entries, pool_checks) = futures::join!(
+            preview_options_for_many(targets, locations),
+            crate::pools::preflight_pools(pool_urls)
+        );
+        entries.sort_by(|a, b| a.ip.cmp(&b.ip));
+
+        // Ignore results for a preview that was cancelled or replaced meanwhile
+        if let Some(preview) = apply_preview.lock().unwrap().as_mut() {
+            if preview.started_at == started_at {
+                preview.entries = entries;
+                preview.pool_checks = pool_checks;
+                preview.loading = false;
+            }
+        }
+    });
 }
 
 pub async fn apply_options_to_miner(
     ip: String,
     settings: MinerOptionSettings,
+    recorder: &SnapshotRecorder,
+    locations: &HashMap<String, MinerLocation>,
 ) -> Result<Vec<String>, String> {
-    let factory = MinerFactory::new();
-    let parsed_ip = ip
-        .parse()
-        .map_err(|e| format!("Invalid IP address {ip}: {e}"))?;
+    crate::actions::ensure_writable(&ip)?;
+    let result = write_options_to_miner(&ip, settings, recorder, locations).await;
+    credentials::record_outcome(&ip, &result);
+    result
+}
 
-    let Some(miner) = factory
-        .get_miner(parsed_ip)
+async fn write_options_to_miner(
+    ip: &str,
+    settings: MinerOptionSettings,
+    recorder: &SnapshotRecorder,
+    locations: &HashMap<String, MinerLocation>,
+) -> Result<Vec<String>, String> {
+    let settings = resolve_worker_names_for_miner(ip, &settings, locations)
         .await
-        .map_err(|e| format!("Failed to connect to {ip}: {e}"))?
-    else {
-        return Err(format!("No supported miner found at {ip}"));
-    };
+        .map_err(|e| format!("Could not resolve worker names, nothing written: {e}"))?;
+
+    // Capture what the miner has now so the job can be rolled back
+    let (current, _) =
+        fetch_current_options_with_capabilities(ip.to_string(), MinerOptionSettings::default())
+            .await
+            .map_err(|e| format!("Could not snapshot current options, nothing written: {e}"))?;
+    recorder.record(ip, &current, &settings);
+
+    let miner = credentials::connect(ip).await?;
 
     let mut applied = Vec::new();
 
@@ -304,33 +746,40 @@ pub async fn apply_options_to_miner(
             return Err(format!("{} ({})", message, ip));
         }
 
-        let group = PoolGroupConfig {
-            name: if settings.pool_group_name.trim().is_empty() {
-                "Primary".to_string()
-            } else {
-                settings.pool_group_name.trim().to_string()
-            },
-            quota: settings.pool_group_quota,
-            pools: settings
-                .pool_inputs
-                .iter()
-                .map(|pool| PoolConfig {
-                    url: PoolURL::from(pool.url.trim().to_string()),
-                    username: pool.username.trim().to_string(),
-                    password: pool.password.clone(),
-                })
-                .collect(),
-        };
+        let groups: Vec<PoolGroupConfig> = settings
+            .pool_groups
+            .iter()
+            .enumerate()
+            .map(|(idx, group)| PoolGroupConfig {
+                name: if group.name.trim().is_empty() {
+                    default_pool_group_name(idx)
+                } else {
+                    group.name.trim().to_string()
+                },
+                quota: group.quota,
+                pools: group
+                    .pools
+                    .iter()
+                    .map(|pool| PoolConfig {
+                        url: PoolURL::from(pool.url.trim().to_string()),
+                        username: pool.username.trim().to_string(),
+                        password: pool.password.clone(),
+                    })
+                    .collect(),
+            })
+            .collect();
+
+        let group_names: Vec<String> = groups.iter().map(|g| g.name.clone()).collect();
 
         miner
-            .set_pools_config(vec![group])
+            .set_pools_config(groups)
             .await
             .map_err(|e| format!("Failed pool config on {ip}: {e}"))?;
 
         applied.push(format!(
-            "pools={} group={}",
-            settings.pool_inputs.len(),
-            settings.pool_group_name
+            "pools={} groups=[{}]",
+            settings.all_pools().count(),
+            group_names.join(" > ")
         ));
     }
 
@@ -344,24 +793,201 @@ pub async fn apply_options_to_miner(
     Ok(applied)
 }
 
-pub async fn apply_options_to_many(ips: Vec<String>, settings: MinerOptionSettings) {
-    if ips.is_empty() {
-        return;
+pub async fn apply_options_to_many(
+    targets: Vec<(String, MinerOptionSettings)>,
+    recorder: SnapshotRecorder,
+    locations: Arc<HashMap<String, MinerLocation>>,
+) -> Vec<(String, Result<Vec<String>, String>)> {
+    if targets.is_empty() {
+        return Vec::new();
     }
 
-    let concurrency = ips.len().clamp(1, 24);
+    let concurrency = targets.len().clamp(1, 24);
 
-    stream::iter(ips)
-        .map(|ip| {
-            let settings = settings.clone();
+    let results = stream::iter(targets)
+        .map(|(ip, settings)| {
+            let recorder = recorder.clone();
+            let locations = Arc::clone(&locations);
             async move {
-                match apply_options_to_miner(ip.clone(), settings).await {
-                    Ok(applied) => println!("✓ Applied options to {} ({})", ip, applied.join(", ")),
+                let result =
+                    apply_options_to_miner(ip.clone(), settings, &recorder, &locations).await;
+                match &result {
+                    Ok(applied) => {
+                        println!("✓ Applied options to {} ({})", ip, applied.join(", "))
+                    }
                     Err(err) => eprintln!("✗ {}", err),
                 }
+                (ip, result)
             }
         })
         .buffer_unordered(concurrency)
-        .collect::<Vec<()>>()
+        .collect()
         .await;
+    recorder.flush();
+    results
+}
+
+#[cfg(test)]
+mod tests {
+    use super::diff_options;
+    use crate::models::{
+        MinerCapabilities, MinerOptionSettings, OptionChange, OptionsPreviewEntry, PoolGroupInput,
+        PoolInput,
+    };
+
+    fn all_capabilities() -> MinerCapabilities {
+        MinerCapabilities {
+            set_power_limit: true,
+            fan_config: true,
+            tuning_config: true,
+            scaling_config: true,
+            pools_config: true,
+            restart: true,
+        }
+    }
+
+    #[test]
+    fn diff_options_reports_changed_power_limit() {
+        let current = MinerOptionSettings {
+            apply_power_limit: true,
+            power_limit_watts: 3000.0,
+            ..Default::default()
+        };
+        let requested = MinerOptionSettings {
+            apply_power_limit: true,
+            power_limit_watts: 2500.0,
+            ..Default::default()
+        };
+
+        let (changes, unsupported) = diff_options(&current, &requested, all_capabilities());
+        assert!(unsupported.is_empty());
+        assert_eq!(
+            changes,
+            vec![OptionChange {
+                field: "power_limit".to_string(),
+                current: "3000 W".to_string(),
+                requested: "2500 W".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn diff_options_skips_matching_and_disabled_sections() {
+        let settings = MinerOptionSettings {
+            apply_fan_config: true,
+            ..Default::default()
+        };
+
+        let (changes, unsupported) = diff_options(&settings, &settings, all_capabilities());
+        assert!(changes.is_empty());
+        assert!(unsupported.is_empty());
+    }
+
+    #[test]
+    fn diff_options_lists_unsupported_capabilities() {
+        let requested = MinerOptionSettings {
+            apply_scaling_config: true,
+            ..Default::default()
+        };
+
+        let (changes, unsupported) = diff_options(
+            &MinerOptionSettings::default(),
+            &requested,
+            MinerCapabilities::default(),
+        );
+        assert!(changes.is_empty());
+        assert_eq!(unsupported, vec!["scaling".to_string()]);
+    }
+
+    #[test]
+    fn diff_options_marks_unreadable_values_as_unknown() {
+        let requested = MinerOptionSettings {
+            apply_power_limit: true,
+            ..Default::default()
+        };
+
+        let (changes, _) = diff_options(
+            &MinerOptionSettings::default(),
+            &requested,
+            all_capabilities(),
+        );
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].current, "unknown");
+    }
+
+    #[test]
+    fn diff_options_reports_added_backup_pool_group() {
+        let primary = PoolGroupInput {
+            pools: vec![PoolInput {
+                url: "stratum+tcp://primary:3333".to_string(),
+                username: "acme.w1".to_string(),
+                password: "x".to_string(),
+            }],
+            ..Default::default()
+        };
+        let current = MinerOptionSettings {
+            apply_pool_config: true,
+            pool_groups: vec![primary.clone()],
+            ..Default::default()
+        };
+        let requested = MinerOptionSettings {
+            apply_pool_config: true,
+            pool_groups: vec![
+                primary.clone(),
+                PoolGroupInput {
+                    name: "Backup 1".to_string(),
+                    ..primary
+                },
+            ],
+            ..Default::default()
+        };
+
+        let (changes, _) = diff_options(&current, &requested, all_capabilities());
+        let fields: Vec<&str> = changes.iter().map(|c| c.field.as_str()).collect();
+        assert_eq!(fields, vec!["group_2", "group_2.pool_1"]);
+        assert_eq!(changes[0].current, "none");
+        assert_eq!(changes[0].requested, "Backup 1 (100%)");
+    }
+
+    #[test]
+    fn diff_options_reports_password_only_change_redacted() {
+        let pool = PoolInput {
+            url: "stratum+tcp://primary:3333".to_string(),
+            username: "acme.w1".to_string(),
+            password: "old-secret".to_string(),
+        };
+        let settings = |password: &str| MinerOptionSettings {
+            apply_pool_config: true,
+            pool_groups: vec![PoolGroupInput {
+                pools: vec![PoolInput {
+                    password: password.to_string(),
+                    ..pool.clone()
+                }],
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        let (changes, _) = diff_options(
+            &settings("old-secret"),
+            &settings("new-secret"),
+            all_capabilities(),
+        );
+        assert_eq!(
+            changes,
+            vec![OptionChange {
+                field: "pool_1.password".to_string(),
+                current: "hidden".to_string(),
+                requested: "password changed".to_string(),
+            }]
+        );
+        let entry = OptionsPreviewEntry {
+            ip: "10.0.0.1".to_string(),
+            settings: settings("new-secret"),
+            changes,
+            unsupported: Vec::new(),
+            error: None,
+        };
+        assert!(entry.will_write());
+    }
 }
@@ -0,0 +1,271 @@
+use crate::models::{MinerInfo, MinerLocation, OptionProfile, PoolGroupInput, SavedRange};
+use crate::profiles;
+use crate::scanner;
+use crate::workers::{self, WorkerNameVars};
+use asic_rs_core::data::miner::MinerData;
+use std::collections::HashMap;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ObservedPool {
+    pub url: String,
+    pub user: String,
+    pub active: bool,
+    pub alive: Option<bool>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum PoolAuditIssue {
+    NoPolicy,
+    NoPoolData,
+    PolicyError(String),
+    WrongActivePool { expected: String, actual: String },
+    WrongWorker { expected: String, actual: String },
+    OnBackupPool(String),
+    ConfigDrift(String),
+    DeadPool(String),
+}
+
+impl PoolAuditIssue {
+    pub fn describe(&self) -> String {
+        match self {
+            PoolAuditIssue::NoPolicy => "No profile with a pool policy matches".to_string(),
+            PoolAuditIssue::NoPoolData => "Miner reported no pools".to_string(),
+            PoolAuditIssue::PolicyError(err) => format!("Policy error: {err}"),
+            PoolAuditIssue::WrongActivePool { expected, actual } => {
+                format!("Mining on {actual}, expected {expected}")
+            }
+            PoolAuditIssue::WrongWorker { expected, actual } => {
+                format!("Worker {actual}, expected {expected}")
+            }
+            PoolAuditIssue::OnBackupPool(url) => format!("Running on backup pool {url}"),
+            PoolAuditIssue::ConfigDrift(detail) => format!("Configured pools differ: {detail}"),
+            PoolAuditIssue::DeadPool(url) => format!("Pool {url} is dead"),
+        }
+    }
+
+    /// Whether the miner is pointed somewhere it should not be, as opposed to
+    /// a pool-side problem or a gap in the policy.
+    pub fn is_mismatch(&self) -> bool {
+        matches!(
+            self,
+            PoolAuditIssue::WrongActivePool { .. }
+                | PoolAuditIssue::WrongWorker { .. }
+                | PoolAuditIssue::ConfigDrift(_)
+        )
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct PoolAuditEntry {
+    pub ip: String,
+    pub model: String,
+    pub policy: Option<String>,
+    pub issues: Vec<PoolAuditIssue>,
+}
+
+fn normalize_url(url: &str) -> String {
+    url.trim().trim_end_matches('/').to_ascii_lowercase()
+}
+
+/// Flattens reported pool groups, marking the pool the scanner treats as the
+/// miner's current one.
+pub fn observed_pools(data: &MinerData) -> Vec<Vec<ObservedPool>> {
+    let primary = scanner::primary_pool(data);
+
+    data.pools
+        .iter()
+        .map(|group| {
+            group
+                .pools
+                .iter()
+                .map(|pool| ObservedPool {
+                    url: pool.url.as_ref().map(|u| u.to_string()).unwrap_or_default(),
+                    user: pool.user.clone().unwrap_or_default(),
+                    active: primary.is_some_and(|p| std::ptr::eq(p, pool)),
+                    alive: pool.alive,
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Compares what a miner reports against the pool groups it should have, with
+/// worker names already resolved for that miner.
+pub fn compare_pools(
+    expected: &[PoolGroupInput],
+    observed: &[Vec<ObservedPool>],
+) -> Vec<PoolAuditIssue> {
+    let mut issues = Vec::new();
+    let observed_flat: Vec<&ObservedPool> = observed.iter().flatten().collect();
+    if observed_flat.is_empty() {
+        return vec![PoolAuditIssue::NoPoolData];
+    }
+
+    let expected_flat: Vec<_> = expected.iter().flat_map(|g| g.pools.iter()).collect();
+    let Some(expected_primary) = expected_flat.first() else {
+        return vec![PoolAuditIssue::NoPolicy];
+    };
+
+    if let Some(active) = observed_flat.iter().find(|pool| pool.active) {
+        let matched = expected_flat
+            .iter()
+            .position(|pool| normalize_url(&pool.url) == normalize_url(&active.url));
+
+        match matched {
+            None => issues.push(PoolAuditIssue::WrongActivePool {
+                expected: expected_primary.url.trim().to_string(),
+                actual: active.url.clone(),
+            }),
+            Some(idx) => {
+                let expected_user = expected_flat[idx].username.trim();
+                if active.user.trim() != expected_user {
+                    issues.push(PoolAuditIssue::WrongWorker {
+                        expected: expected_user.to_string(),
+                        actual: active.user.clone(),
+                    });
+                }
+                if idx > 0 {
+                    issues.push(PoolAuditIssue::OnBackupPool(active.url.clone()));
+                }
+            }
+        }
+    }
+
+    let group_count = expected.len().max(observed.len());
+    'groups: for group_idx in 0..group_count {
+        let expected_pools = expected.get(group_idx).map_or(&[][..], |g| &g.pools[..]);
+        let observed_pools = observed.get(group_idx).map_or(&[][..], |g| &g[..]);
+        for idx in 0..expected_pools.len().max(observed_pools.len()) {
+            let want = expected_pools
+                .get(idx)
+                .map(|p| (normalize_url(&p.url), p.username.trim().to_string()));
+            let have = observed_pools
+                .get(idx)
+                .map(|p| (normalize_url(&p.url), p.user.trim().to_string()));
+            if want != have {
+                let show = |pool: Option<(String, String)>| {
+                    pool.map(|(url, user)| format!("{url} ({user})"))
+                        .unwrap_or_else(|| "none".to_string())
+                };
+                issues.push(PoolAuditIssue::ConfigDrift(format!(
+                    "group {} pool {}: expected {}, found {}",
+                    group_idx + 1,
+                    idx + 1,
+                    show(want),
+                    show(have)
+                )));
+                break 'groups;
+            }
+        }
+    }
+
+    for pool in &observed_flat {
+        if pool.alive == Some(false) {
+            issues.push(PoolAuditIssue::DeadPool(pool.url.clone()));
+        }
+    }
+
+    issues
+}
+
+pub fn audit_fleet(
+    miners: &[MinerInfo],
+    option_profiles: &[OptionProfile],
+    saved_ranges: &[SavedRange],
+    miner_locations: &HashMap<String, MinerLocation>,
+) -> Vec<PoolAuditEntry> {
+    miners
+        .iter()
+        .map(|miner| {
+            let profile = profiles::find_matching_profile(option_profiles, miner, saved_ranges)
+                .filter(|p| p.settings.apply_pool_config);
+
+            let issues = match (profile, &miner.full_data) {
+                (None, _) => vec![PoolAuditIssue::NoPolicy],
+                (Some(_), None) => vec![PoolAuditIssue::NoPoolData],
+                (Some(profile), Some(data)) => {
+                    let vars = WorkerNameVars::from_data(
+                        &miner.ip,
+                        data,
+                        &profile.settings.pool_account,
+                        miner_locations,
+                    );
+                    match workers::resolve_worker_names(&profile.settings, &vars) {
+                        Ok(expected) => compare_pools(&expected.pool_groups, &observed_pools(data)),
+                        Err(err) => vec![PoolAuditIssue::PolicyError(err)],
+                    }
+                }
+            };
+
+            PoolAuditEntry {
+                ip: miner.ip.clone(),
+                model: miner.model.clone(),
+                policy: profile.map(|p| p.name.clone()),
+                issues,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{compare_pools, ObservedPool, PoolAuditIssue};
+    use crate::models::{PoolGroupInput, PoolInput};
+
+    fn expected() -> Vec<PoolGroupInput> {
+        vec![PoolGroupInput {
+            pools: vec![
+                PoolInput {
+                    url: "stratum+tcp://main:3333".to_string(),
+                    username: "acme.r1s1".to_string(),
+                    password: "x".to_string(),
+                },
+                PoolInput {
+                    url: "stratum+tcp://backup:3333".to_string(),
+                    username: "acme.r1s1".to_string(),
+                    password: "x".to_string(),
+                },
+            ],
+            ..Default::default()
+        }]
+    }
+
+    fn observed(url: &str, user: &str, active: bool, alive: bool) -> ObservedPool {
+        ObservedPool {
+            url: url.to_string(),
+            user: user.to_string(),
+            active,
+            alive: Some(alive),
+        }
+    }
+
+    #[test]
+    fn compare_pools_accepts_matching_miner() {
+        let pools = vec![vec![
+            observed("stratum+tcp://main:3333", "acme.r1s1", true, true),
+            observed("stratum+tcp://backup:3333", "acme.r1s1", false, true),
+        ]];
+        assert!(compare_pools(&expected(), &pools).is_empty());
+    }
+
+    #[test]
+    fn compare_pools_flags_backup_dead_and_wrong_worker() {
+        let pools = vec![vec![
+            observed("stratum+tcp://main:3333", "acme.r1s1", false, false),
+            observed("stratum+tcp://backup:3333", "acme.default", true, true),
+        ]];
+        let issues = compare_pools(&expected(), &pools);
+        assert!(issues.contains(&PoolAuditIssue::OnBackupPool(
+            "stratum+tcp://backup:3333".to_string()
+        )));
+        assert!(issues.contains(&PoolAuditIssue::DeadPool(
+            "stratum+tcp://main:3333".to_string()
+        )));
+        assert!(issues
+            .iter()
+            .any(|issue| matches!(issue, PoolAuditIssue::WrongWorker { .. })));
+        assert!(issues
+            .iter()
+            .any(|issue| matches!(issue, PoolAuditIssue::ConfigDrift(_))));
+    }
+}
@@ -0,0 +1,242 @@
+use crate::models::PoolCheckResult;
+use futures::stream::{self, StreamExt};
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::TcpStream;
+
+const PREFLIGHT_TIMEOUT: Duration = Duration::from_secs(5);
+// Pools may push a few notifications before answering the subscribe
+const MAX_PREFLIGHT_LINES: usize = 8;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PoolProtocol {
+    StratumV1,
+    StratumV1Tls,
+    StratumV2,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PoolEndpoint {
+    pub protocol: PoolProtocol,
+    pub host: String,
+    pub port: u16,
+    /// Stratum V2 authority public key from the URL path, if given.
+    pub authority_key: Option<String>,
+}
+
+impl PoolEndpoint {
+    fn address(&self) -> String {
+        if self.host.contains(':') {
+            format!("[{}]:{}", self.host, self.port)
+        } else {
+            format!("{}:{}", self.host, self.port)
+        }
+    }
+}
+
+fn is_valid_hostname(host: &str) -> bool {
+    !host.is_empty()
+        && host.len() <= 253
+        && host.split('.').all(|label| {
+            !label.is_empty()
+                && !label.starts_with('-')
+                && !label.ends_with('-')
+                && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+        })
+}
+
+/// Parses `stratum+tcp://host:port`, `stratum+ssl://host:port` and
+/// `stratum2+tcp://host:port/authority_key` pool URLs.
+pub fn parse_pool_url(url: &str) -> Result<PoolEndpoint, String> {
+    let url = url.trim();
+    let Some((scheme, rest)) = url.split_once("://") else {
+        return Err(format!(
+            "'{url}' is missing the scheme separator, expected e.g. stratum+tcp://host:port"
+        ));
+    };
+
+    let protocol = match scheme.to_ascii_lowercase().as_str() {
+        "stratum+tcp" | "stratum" => PoolProtocol::StratumV1,
+        "stratum+ssl" | "stratum+tls" => PoolProtocol::StratumV1Tls,
+        "stratum2+tcp" | "stratum2" => PoolProtocol::StratumV2,
+        other => return Err(format!("Unsupported pool scheme '{other}://'")),
+    };
+
+    let (authority, path) = match rest.split_once('/') {
+        Some((authority, path)) => (authority, path.trim_end_matches('/')),
+        None => (rest, ""),
+    };
+
+    let authority_key = match (protocol, path.is_empty()) {
+        (_, true) => None,
+        (PoolProtocol::StratumV2, false) => {
+            if !path.chars().all(|c| c.is_ascii_alphanumeric()) {
+                return Err(format!("Invalid stratum V2 authority key '{path}'"));
+            }
+            Some(path.to_string())
+        }
+        (_, false) => return Err(format!("Unexpected path '/{path}' in pool URL")),
+    };
+
+    let (host, port) = if let Some(bracketed) = authority.strip_prefix('[') {
+        let Some((host, port)) = bracketed.split_once("]:") else {
+            return Err(format!("Pool URL '{url}' needs a port"));
+        };
+        if host.parse::<std::net::Ipv6Addr>().is_err() {
+            return Err(format!("Invalid IPv6 address '{host}'"));
+        }
+        (host, port)
+    } else {
+        let Some((host, port)) = authority.rsplit_once(':') else {
+            return Err(format!("Pool URL '{url}' needs a port"));
+        };
+        if !is_valid_hostname(host) {
+            return Err(format!("Invalid pool host '{host}'"));
+        }
+        (host, port)
+    };
+
+    let port = match port.parse::<u16>() {
+        Ok(port) if port > 0 => port,
+        _ => return Err(format!("Invalid pool port '{port}'")),
+    };
+
+    Ok(PoolEndpoint {
+        protocol,
+        host: host.to_string(),
+        port,
+        authority_key,
+    })
+}
+
+async fn stratum_v1_subscribe(stream: TcpStream) -> Result<String, String> {
+    let (reader, mut writer) = stream.into_split();
+    writer
+        .write_all(
+            b"{\"id\":1,\"method\":\"mining.subscribe\",\"params\":[\"asic-miner-scanner\"]}\n",
+        )
+        .await
+        .map_err(|e| format!("send failed: {e}"))?;
+
+    let mut lines = BufReader::new(reader).lines();
+    for _ in 0..MAX_PREFLIGHT_LINES {
+        let Some(line) = lines
+            .next_line()
+            .await
+            .map_err(|e| format!("read failed: {e}"))?
+        else {
+            return Err("connection closed before subscribe reply".to_string());
+        };
+
+        let Ok(message) = serde_json::from_str::<serde_json::Value>(&line) else {
+            return Err("reply is not stratum JSON".to_string());
+        };
+        if message.get("id").and_then(|id| id.as_u64()) != Some(1) {
+            continue;
+        }
+
+        if let Some(error) = message.get("error").filter(|e| !e.is_null()) {
+            return Err(format!("subscribe rejected: {error}"));
+        }
+        return match message.get("result") {
+            Some(result) if !result.is_null() => Ok("mining.subscribe accepted".to_string()),
+            _ => Err("subscribe reply has no result".to_string()),
+        };
+    }
+
+    Err("no reply to mining.subscribe".to_string())
+}
+
+/// Connects to the pool and, for plain stratum V1, performs a `mining.subscribe`
+/// handshake. TLS and V2 endpoints are only checked for TCP reachability.
+pub async fn check_pool(url: &str, timeout: Duration) -> Result<String, String> {
+    let endpoint = parse_pool_url(url)?;
+
+    let stream = tokio::time::timeout(timeout, TcpStream::connect(endpoint.address()))
+        .await
+        .map_err(|_| format!("timed out connecting to {}", endpoint.address()))?
+        .map_err(|e| format!("cannot connect to {}: {e}", endpoint.address()))?;
+
+    match endpoint.protocol {
+        PoolProtocol::StratumV1 => tokio::time::timeout(timeout, stratum_v1_subscribe(stream))
+            .await
+            .map_err(|_| "timed out waiting for mining.subscribe reply".to_string())?,
+        PoolProtocol::StratumV1Tls => Ok("reachable (TLS handshake not checked)".to_string()),
+        PoolProtocol::StratumV2 => Ok("reachable (V2 handshake not checked)".to_string()),
+    }
+}
+
+pub async fn preflight_pools(urls: Vec<String>) -> Vec<PoolCheckResult> {
+    let concurrency = urls.len().clamp(1, 8);
+
+    let mut results: Vec<PoolCheckResult> = stream::iter(urls)
+        .map(|url| async move {
+            let result = check_pool(&url, PREFLIGHT_TIMEOUT).await;
+            PoolCheckResult { url, result }
+        })
+        .buffer_unordered(concurrency)
+        .collect()
+        .await;
+    results.sort_by(|a, b| a.url.cmp(&b.url));
+    results
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{check_pool, parse_pool_url, PoolProtocol};
+    use std::time::Duration;
+    use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+    use tokio::net::TcpListener;
+
+    #[test]
+    fn parse_pool_url_accepts_v1_and_v2_forms() {
+        let v1 = parse_pool_url("stratum+tcp://btc.example.com:3333").unwrap();
+        assert_eq!(v1.protocol, PoolProtocol::StratumV1);
+        assert_eq!((v1.host.as_str(), v1.port), ("btc.example.com", 3333));
+
+        let v2 = parse_pool_url("stratum2+tcp://v2.example.com:3336/9awtMD5KQgvRUh2yFbjVeT7b6hjipWcAsQHd6wEhgtDT9soosna").unwrap();
+        assert_eq!(v2.protocol, PoolProtocol::StratumV2);
+        assert!(v2.authority_key.is_some());
+
+        let tls = parse_pool_url("stratum+ssl://[2001:db8::1]:443").unwrap();
+        assert_eq!(tls.host, "2001:db8::1");
+    }
+
+    #[test]
+    fn parse_pool_url_rejects_common_typos() {
+        assert!(parse_pool_url("stratum+tcp//btc.example.com:3333").is_err());
+        assert!(parse_pool_url("stratum+tpc://btc.example.com:3333").is_err());
+        assert!(parse_pool_url("stratum+tcp://btc.example.com").is_err());
+        assert!(parse_pool_url("stratum+tcp://btc.example.com:99999").is_err());
+        assert!(parse_pool_url("stratum+tcp://btc example.com:3333").is_err());
+    }
+
+    async fn stratum_stand_in(reply: &'static str) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(async move {
+            let (socket, _) = listener.accept().await.unwrap();
+            let (reader, mut writer) = socket.into_split();
+            let mut lines = BufReader::new(reader).lines();
+            let request = lines.next_line().await.unwrap().unwrap();
+            assert!(request.contains("mining.subscribe"));
+            writer.write_all(reply.as_bytes()).await.unwrap();
+        });
+        format!("stratum+tcp://127.0.0.1:{port}")
+    }
+
+    #[tokio::test]
+    async fn check_pool_completes_subscribe_handshake() {
+        let url = stratum_stand_in(
+            "{\"id\":null,\"method\":\"mining.set_difficulty\",\"params\":[512]}\n\
+             {\"id\":1,\"result\":[[[\"mining.notify\",\"ae68\"]],\"08000002\",4],\"error\":null}\n",
+        )
+        .await;
+        assert!(check_pool(&url, Duration::from_secs(2)).await.is_ok());
+
+        let url =
+            stratum_stand_in("{\"id\":1,\"result\":null,\"error\":[20,\"Banned\",null]}\n").await;
+        let err = check_pool(&url, Duration::from_secs(2)).await.unwrap_err();
+        assert!(err.contains("rejected"));
+    }
+}
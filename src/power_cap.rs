@@ -0,0 +1,540 @@
+use crate::actions::{self, MinerAction};
+use crate::audit::{self, AuditEntry, AuditLog};
+use crate::models::{MinerInfo, MinerLocation, MinerOptionSettings};
+use crate::options;
+use crate::snapshots::SnapshotRecorder;
+use chrono::Local;
+use futures::stream::{self, StreamExt};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::sync::{Arc, Mutex};
+
+const POWER_CAP_STATE_FILE: &str = "power_cap_state.json";
+const MAX_EVENTS: usize = 200;
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct PowerCapSettings {
+    pub enabled: bool,
+    pub cap_watts: f64,
+    /// Headroom that must open up below the cap before anything is restored,
+    /// so the controller does not flap around the limit.
+    pub restore_margin_watts: f64,
+    pub step_watts: f64,
+    pub min_power_limit_watts: f64,
+    pub allow_pause: bool,
+}
+
+impl Default for PowerCapSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            cap_watts: 100_000.0,
+            restore_margin_watts: 3000.0,
+            step_watts: 250.0,
+            min_power_limit_watts: 1500.0,
+            allow_pause: false,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq)]
+pub enum CurtailmentKind {
+    PowerLimited(f64),
+    /// Paused, remembering any limit that was in place before the pause.
+    Paused {
+        limited_to: Option<f64>,
+    },
+}
+
+/// A miner the controller has stepped down, keyed by `MinerInfo::identity()`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Curtailment {
+    pub ip: String,
+    pub kind: CurtailmentKind,
+    pub baseline_watts: f64,
+    pub efficiency_w_th: Option<f64>,
+    /// Power and tuning settings read before the first step-down.
+    pub original: Option<MinerOptionSettings>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PowerCapAction {
+    Limit(f64),
+    Pause,
+    Resume,
+    Restore,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct PowerCapStep {
+    pub identity: String,
+    pub ip: String,
+    pub action: PowerCapAction,
+    pub baseline_watts: f64,
+    pub efficiency_w_th: Option<f64>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct FleetMinerPower {
+    pub identity: String,
+    pub ip: String,
+    pub wattage_w: Option<f64>,
+    pub efficiency_w_th: Option<f64>,
+    pub can_limit_power: bool,
+}
+
+impl FleetMinerPower {
+    pub fn from_miner(miner: &MinerInfo) -> Self {
+        Self {
+            identity: miner.identity(),
+            ip: miner.ip.clone(),
+            wattage_w: miner.wattage_w,
+            efficiency_w_th: miner.efficiency_w_th,
+            can_limit_power: miner.capabilities.set_power_limit,
+        }
+    }
+}
+
+pub fn fleet_power_watts(miners: &[FleetMinerPower]) -> f64 {
+    miners.iter().filter_map(|m| m.wattage_w).sum()
+}
+
+// Unknown efficiency sorts as the best so measured offenders go first
+fn efficiency_key(efficiency: Option<f64>) -> f64 {
+    efficiency.unwrap_or(0.0)
+}
+
+/// Decides the next round of step-downs or restores. Over the cap, miners are
+/// curtailed least efficient first, one step each, until the excess is
+/// covered. With enough headroom, curtailed miners come back most efficient
+/// first.
+pub fn plan_power_cap(
+    settings: &PowerCapSettings,
+    miners: &[FleetMinerPower],
+    curtailed: &HashMap<String, Curtailment>,
+) -> Vec<PowerCapStep> {
+    let total = fleet_power_watts(miners);
+    let mut steps = Vec::new();
+
+    if total > settings.cap_watts {
+        let mut excess = total - settings.cap_watts;
+        let mut candidates: Vec<&FleetMinerPower> = miners
+            .iter()
+            .filter(|m| m.wattage_w.is_some_and(|w| w > 0.0))
+            .filter(|m| {
+                !curtailed
+                    .get(&m.identity)
+                    .is_some_and(|c| matches!(c.kind, CurtailmentKind::Paused { .. }))
+            })
+            .collect();
+        candidates.sort_by(|a, b| {
+            efficiency_key(b.efficiency_w_th)
+                .total_cmp(&efficiency_key(a.efficiency_w_th))
+                .then_with(|| {
+                    b.wattage_w
+                        .unwrap_or(0.0)
+                        .total_cmp(&a.wattage_w.unwrap_or(0.0))
+                })
+        });
+
+        for miner in candidates {
+            if excess <= 0.0 {
+                break;
+            }
+            let wattage = miner.wattage_w.unwrap_or(0.0);
+            let existing = curtailed.get(&miner.identity);
+            let current = match existing.map(|c| c.kind) {
+                Some(CurtailmentKind::PowerLimited(limit)) => limit,
+                _ => wattage,
+            };
+
+            let action = if miner.can_limit_power
+                && current - settings.step_watts.max(1.0) >= settings.min_power_limit_watts
+            {
+                let next = current - settings.step_watts.max(1.0);
+                excess -= current - next;
+                Some(PowerCapAction::Limit(next.round()))
+            } else if settings.allow_pause {
+                excess -= wattage;
+                Some(PowerCapAction::Pause)
+            } else {
+                None
+            };
+
+            if let Some(action) = action {
+                steps.push(PowerCapStep {
+                    identity: miner.identity.clone(),
+                    ip: miner.ip.clone(),
+                    action,
+                    baseline_watts: existing.map_or(wattage, |c| c.baseline_watts),
+                    efficiency_w_th: existing
+                        .and_then(|c| c.efficiency_w_th)
+                        .or(miner.efficiency_w_th),
+                });
+            }
+        }
+        return steps;
+    }
+
+    let mut headroom = settings.cap_watts - settings.restore_margin_watts - total;
+    if headroom <= 0.0 {
+        return steps;
+    }
+
+    let mut restorable: Vec<(&String, &Curtailment)> = curtailed.iter().collect();
+    restorable.sort_by(|(_, a), (_, b)| {
+        efficiency_key(a.efficiency_w_th).total_cmp(&efficiency_key(b.efficiency_w_th))
+    });
+
+    for (identity, curtailment) in restorable {
+        let (action, needed) = match curtailment.kind {
+            CurtailmentKind::Paused { limited_to } => (
+                PowerCapAction::Resume,
+                limited_to.unwrap_or(curtailment.baseline_watts),
+            ),
+            CurtailmentKind::PowerLimited(limit) => {
+                let next = limit + settings.step_watts.max(1.0);
+                if next >= curtailment.baseline_watts {
+                    (
+                        PowerCapAction::Restore,
+                        (curtailment.baseline_watts - limit).max(0.0),
+                    )
+                } else {
+                    (PowerCapAction::Limit(next.round()), next - limit)
+                }
+            }
+        };
+        if needed > headroom {
+            continue;
+        }
+
+        headroom -= needed;
+        steps.push(PowerCapStep {
+            identity: identity.clone(),
+            ip: curtailment.ip.clone(),
+            action,
+            baseline_watts: curtailment.baseline_watts,
+            efficiency_w_th: curtailment.efficiency_w_th,
+        });
+    }
+
+    steps
+}
+
+/// Steps that hand every curtailed miner back its original settings at once.
+pub fn plan_release_all(curtailed: &HashMap<String, Curtailment>) -> Vec<PowerCapStep> {
+    curtailed
+        .iter()
+        .map(|(identity, curtailment)| PowerCapStep {
+            identity: identity.clone(),
+            ip: curtailment.ip.clone(),
+            action: match curtailment.kind {
+                CurtailmentKind::Paused { limited_to: None } => PowerCapAction::Resume,
+                CurtailmentKind::Paused { .. } | CurtailmentKind::PowerLimited(_) => {
+                    PowerCapAction::Restore
+                }
+            },
+            baseline_watts: curtailment.baseline_watts,
+            efficiency_w_th: curtailment.efficiency_w_th,
+        })
+        .collect()
+}
+
+pub fn power_limit_settings(watts: f64) -> MinerOptionSettings {
+    MinerOptionSettings {
+        apply_power_limit: true,
+        power_limit_watts: watts,
+        ..Default::default()
+    }
+}
+
+/// Settings that put a curtailed miner back the way it was found, falling back
+/// to a limit at its pre-curtailment draw when the original was unreadable.
+fn restore_settings(curtailment: &Curtailment) -> MinerOptionSettings {
+    match &curtailment.original {
+        Some(original) if original.apply_power_limit || original.apply_tuning_config => {
+            MinerOptionSettings {
+                apply_fan_config: false,
+                apply_scaling_config: false,
+                apply_pool_config: false,
+                ..original.clone()
+            }
+        }
+        _ => power_limit_settings(curtailment.baseline_watts.round()),
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct PowerCapEvent {
+    pub at: i64,
+    pub message: String,
+    pub ok: bool,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct PowerCapState {
+    pub curtailed: HashMap<String, Curtailment>,
+    #[serde(skip)]
+    pub events: Vec<PowerCapEvent>,
+    #[serde(skip)]
+    pub in_flight: bool,
+    #[serde(skip)]
+    pub last_total_watts: Option<f64>,
+}
+
+impl PowerCapState {
+    pub fn load() -> Self {
+        crate::config::app_data_path(POWER_CAP_STATE_FILE)
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+            .unwrap_or_default()
+    }
+
+    fn save(&self) {
+        if let Some(path) = crate::config::app_data_path(POWER_CAP_STATE_FILE) {
+            if let Ok(json) = serde_json::to_string_pretty(self) {
+                if let Err(e) = fs::write(path, json) {
+                    eprintln!("✗ Failed to save power cap state: {e}");
+                }
+            }
+        }
+    }
+
+    pub fn push_event(&mut self, message: String, ok: bool) {
+        self.events.push(PowerCapEvent {
+            at: Local::now().timestamp(),
+            message,
+            ok,
+        });
+        if self.events.len() > MAX_EVENTS {
+            self.events.drain(0..self.events.len() - MAX_EVENTS);
+        }
+    }
+}
+
+async fn execute_step(
+    step: &PowerCapStep,
+    state: &Arc<Mutex<PowerCapState>>,
+    recorder: Option<&SnapshotRecorder>,
+    locations: &HashMap<String, MinerLocation>,
+) -> Result<String, String> {
+    let existing = state.lock().unwrap().curtailed.get(&step.identity).cloned();
+
+    match step.action {
+        PowerCapAction::Limit(watts) => {
+            let recorder = recorder.ok_or("No snapshot job for power limit change")?;
+            let original = match &existing {
+                Some(curtailment) => curtailment.original.clone(),
+                None => options::fetch_current_options(step.ip.clone(), Default::default())
+                    .await
+                    .ok(),
+            };
+            options::apply_options_to_miner(
+                step.ip.clone(),
+                power_limit_settings(watts),
+                recorder,
+                locations,
+            )
+            .await?;
+
+            state.lock().unwrap().curtailed.insert(
+                step.identity.clone(),
+                Curtailment {
+                    ip: step.ip.clone(),
+                    kind: CurtailmentKind::PowerLimited(watts),
+                    baseline_watts: step.baseline_watts,
+                    efficiency_w_th: step.efficiency_w_th,
+                    original,
+                },
+            );
+            Ok(format!("{} limited to {watts} W", step.ip))
+        }
+        PowerCapAction::Pause => {
+            actions::run_action(&step.ip, MinerAction::Pause).await?;
+            let limited_to = existing.as_ref().and_then(|c| match c.kind {
+                CurtailmentKind::PowerLimited(limit) => Some(limit),
+                CurtailmentKind::Paused { limited_to } => limited_to,
+            });
+            state.lock().unwrap().curtailed.insert(
+                step.identity.clone(),
+                Curtailment {
+                    ip: step.ip.clone(),
+                    kind: CurtailmentKind::Paused { limited_to },
+                    baseline_watts: step.baseline_watts,
+                    efficiency_w_th: step.efficiency_w_th,
+                    original: existing.and_then(|c| c.original),
+                },
+            );
+            Ok(format!("{} paused", step.ip))
+        }
+        PowerCapAction::Resume => {
+            actions::run_action(&step.ip, MinerAction::Resume).await?;
+            let mut state = state.lock().unwrap();
+            // A miner limited before it was paused still needs its limit lifted
+            match existing {
+                Some(
+                    mut curtailment @ Curtailment {
+                        kind:
+                            CurtailmentKind::Paused {
+                                limited_to: Some(limit),
+                            },
+                        ..
+                    },
+                ) => {
+                    curtailment.kind = CurtailmentKind::PowerLimited(limit);
+                    state.curtailed.insert(step.identity.clone(), curtailment);
+                }
+                _ => {
+                    state.curtailed.remove(&step.identity);
+                }
+            }
+            Ok(format!("{} resumed", step.ip))
+        }
+        PowerCapAction::Restore => {
+            let recorder = recorder.ok_or("No snapshot job for restore")?;
+            let Some(curtailment) = existing else {
+                return Ok(format!("{} was not curtailed", step.ip));
+            };
+            if matches!(curtailment.kind, CurtailmentKind::Paused { .. }) {
+                actions::run_action(&step.ip, MinerAction::Resume).await?;
+            }
+            options::apply_options_to_miner(
+                step.ip.clone(),
+                restore_settings(&curtailment),
+                recorder,
+                locations,
+            )
+            .await?;
+            state.lock().unwrap().curtailed.remove(&step.identity);
+            Ok(format!("{} restored", step.ip))
+        }
+    }
+}
+
+/// Runs a planned round in the background. Power limit writes go through the
+/// snapshot recorder so the round shows up in History and can be rolled back,
+/// and the round is audited once every step has finished.
+pub fn execute_steps(
+    state: Arc<Mutex<PowerCapState>>,
+    steps: Vec<PowerCapStep>,
+    recorder: Option<SnapshotRecorder>,
+    locations: Arc<HashMap<String, MinerLocation>>,
+    audit_log: Arc<Mutex<AuditLog>>,
+    audit_entry: AuditEntry,
+) {
+    state.lock().unwrap().in_flight = true;
+
+    crate::runtime::spawn(async move {
+        let concurrency = steps.len().clamp(1, 24);
+        let results: Vec<(String, Result<String, String>)> = stream::iter(steps)
+            .map(|step| {
+                let state = Arc::clone(&state);
+                let recorder = recorder.clone();
+                let locations = Arc::clone(&locations);
+                async move {
+                    let result = execute_step(&step, &state, recorder.as_ref(), &locations).await;
+                    (step.ip, result)
+                }
+            })
+            .buffer_unordered(concurrency)
+            .collect()
+            .await;
+        if let Some(recorder) = &recorder {
+            recorder.flush();
+        }
+        audit::record(&audit_log, audit_entry.with_results(&results));
+
+        let mut state = state.lock().unwrap();
+        for (_, result) in results {
+            match result {
+                Ok(message) => {
+                    println!("✓ Power cap: {message}");
+                    state.push_event(message, true);
+                }
+                Err(err) => {
+                    eprintln!("✗ Power cap: {err}");
+                    state.push_event(err, false);
+                }
+            }
+        }
+        state.in_flight = false;
+        state.save();
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        plan_power_cap, Curtailment, CurtailmentKind, FleetMinerPower, PowerCapAction,
+        PowerCapSettings,
+    };
+    use std::collections::HashMap;
+
+    fn miner(id: &str, watts: f64, efficiency: f64) -> FleetMinerPower {
+        FleetMinerPower {
+            identity: id.to_string(),
+            ip: id.to_string(),
+            wattage_w: Some(watts),
+            efficiency_w_th: Some(efficiency),
+            can_limit_power: true,
+        }
+    }
+
+    fn settings() -> PowerCapSettings {
+        PowerCapSettings {
+            enabled: true,
+            cap_watts: 9000.0,
+            restore_margin_watts: 500.0,
+            step_watts: 300.0,
+            min_power_limit_watts: 2000.0,
+            allow_pause: true,
+        }
+    }
+
+    #[test]
+    fn plan_curtails_least_efficient_first() {
+        let miners = vec![
+            miner("good", 3200.0, 17.0),
+            miner("bad", 3300.0, 30.0),
+            miner("mid", 3000.0, 22.0),
+        ];
+        let steps = plan_power_cap(&settings(), &miners, &HashMap::new());
+        assert_eq!(steps[0].identity, "bad");
+        assert_eq!(steps[0].action, PowerCapAction::Limit(3000.0));
+        assert_eq!(steps[1].identity, "mid");
+        assert_eq!(steps.len(), 2);
+
+        // At the floor a miner gets paused instead
+        let mut floored = miners.clone();
+        floored[1].wattage_w = Some(2100.0);
+        floored[0].wattage_w = Some(5000.0);
+        let steps = plan_power_cap(&settings(), &floored, &HashMap::new());
+        assert_eq!(steps[0].action, PowerCapAction::Pause);
+    }
+
+    #[test]
+    fn plan_restores_when_headroom_returns() {
+        let miners = vec![miner("good", 3200.0, 17.0), miner("bad", 2700.0, 30.0)];
+        let curtailed = HashMap::from([(
+            "bad".to_string(),
+            Curtailment {
+                ip: "bad".to_string(),
+                kind: CurtailmentKind::PowerLimited(2700.0),
+                baseline_watts: 2900.0,
+                efficiency_w_th: Some(30.0),
+                original: None,
+            },
+        )]);
+
+        let steps = plan_power_cap(&settings(), &miners, &curtailed);
+        assert_eq!(steps.len(), 1);
+        assert_eq!(steps[0].action, PowerCapAction::Restore);
+
+        // Inside the restore margin nothing changes
+        let mut tight = settings();
+        tight.cap_watts = 6000.0;
+        assert!(plan_power_cap(&tight, &miners, &curtailed).is_empty());
+    }
+}
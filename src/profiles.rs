@@ -0,0 +1,121 @@
+use crate::models::{EpicFirmwareFilter, MinerInfo, OptionProfile, ProfileMatchRules, SavedRange};
+use crate::scanner;
+
+fn contains_ignore_case(haystack: &str, needle: &str) -> bool {
+    let needle = needle.trim();
+    needle.is_empty()
+        || haystack
+            .to_ascii_lowercase()
+            .contains(&needle.to_ascii_lowercase())
+}
+
+pub fn rules_match(
+    rules: &ProfileMatchRules,
+    miner: &MinerInfo,
+    saved_ranges: &[SavedRange],
+) -> bool {
+    if !contains_ignore_case(&miner.model, &rules.model_contains)
+        || !contains_ignore_case(&miner.firmware_version, &rules.firmware_contains)
+    {
+        return false;
+    }
+
+    let firmware_ok = match rules.epic_firmware {
+        EpicFirmwareFilter::Any => true,
+        EpicFirmwareFilter::EpicOnly => miner.is_epic_firmware(),
+        EpicFirmwareFilter::StockOnly => !miner.is_epic_firmware(),
+    };
+    if !firmware_ok {
+        return false;
+    }
+
+    if rules.saved_range.is_empty() {
+        return true;
+    }
+
+    // A rule pointing at a deleted range matches nothing rather than everything
+    saved_ranges
+        .iter()
+        .find(|r| r.name == rules.saved_range)
+        .is_some_and(|r| scanner::range_contains_ip(&r.range, &miner.ip))
+}
+
+/// First profile, in list order, whose rules all match the miner.
+pub fn find_matching_profile<'a>(
+    profiles: &'a [OptionProfile],
+    miner: &MinerInfo,
+    saved_ranges: &[SavedRange],
+) -> Option<&'a OptionProfile> {
+    profiles
+        .iter()
+        .find(|profile| rules_match(&profile.rules, miner, saved_ranges))
+}
+
+pub fn profile_validation_message(profile: &OptionProfile) -> Option<String> {
+    if !profile.settings.has_any_enabled() {
+        return Some(format!("Profile '{}' has no options enabled", profile.name));
+    }
+    profile
+        .settings
+        .tuning_validation_message()
+        .or_else(|| profile.settings.pool_validation_message())
+        .map(|message| format!("Profile '{}': {message}", profile.name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::find_matching_profile;
+    use crate::models::{
+        EpicFirmwareFilter, MinerInfo, MinerOptionSettings, OptionProfile, ProfileMatchRules,
+        SavedRange,
+    };
+
+    fn profile(name: &str, rules: ProfileMatchRules) -> OptionProfile {
+        OptionProfile {
+            name: name.to_string(),
+            rules,
+            settings: MinerOptionSettings::default(),
+        }
+    }
+
+    #[test]
+    fn find_matching_profile_uses_first_full_match() {
+        let profiles = vec![
+            profile(
+                "s21 site b",
+                ProfileMatchRules {
+                    model_contains: "s21".to_string(),
+                    saved_range: "Site B".to_string(),
+                    ..Default::default()
+                },
+            ),
+            profile(
+                "s21",
+                ProfileMatchRules {
+                    model_contains: "S21".to_string(),
+                    epic_firmware: EpicFirmwareFilter::StockOnly,
+                    ..Default::default()
+                },
+            ),
+        ];
+        let ranges = vec![SavedRange {
+            name: "Site B".to_string(),
+            range: "10.0.82.1-254".to_string(),
+        }];
+        let miner = MinerInfo {
+            ip: "10.0.81.5".to_string(),
+            model: "Antminer S21".to_string(),
+            firmware_version: "2024.1".to_string(),
+            ..Default::default()
+        };
+
+        let matched = find_matching_profile(&profiles, &miner, &ranges).map(|p| p.name.as_str());
+        assert_eq!(matched, Some("s21"));
+
+        let whatsminer = MinerInfo {
+            model: "WhatsMiner M50".to_string(),
+            ..miner
+        };
+        assert!(find_matching_profile(&profiles, &whatsminer, &ranges).is_none());
+    }
+}
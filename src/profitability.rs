@@ -0,0 +1,287 @@
+use crate::models::{MinerInfo, SavedRange};
+use crate::scanner;
+use serde::{Deserialize, Serialize};
+use std::fs;
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub enum TariffKind {
+    Flat,
+    TimeOfUse,
+}
+
+/// A daily window billed at its own rate. `end_hour` is exclusive and may be
+/// smaller than `start_hour` to wrap past midnight.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct TariffPeriod {
+    pub name: String,
+    pub start_hour: u32,
+    pub end_hour: u32,
+    pub usd_per_kwh: f64,
+}
+
+impl TariffPeriod {
+    fn covers(&self, hour: u32) -> bool {
+        if self.start_hour <= self.end_hour {
+            hour >= self.start_hour && hour < self.end_hour
+        } else {
+            hour >= self.start_hour || hour < self.end_hour
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub enum HashpriceSource {
+    Manual,
+    /// Read from a file kept up to date by another tool.
+    File,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct ProfitabilitySettings {
+    pub tariff: TariffKind,
+    pub flat_usd_per_kwh: f64,
+    pub periods: Vec<TariffPeriod>,
+    /// Rate for hours no time-of-use period covers.
+    pub base_usd_per_kwh: f64,
+    pub hashprice_source: HashpriceSource,
+    pub hashprice_usd_per_th_day: f64,
+    pub hashprice_file: String,
+    pub pool_fee_percent: f64,
+}
+
+impl Default for ProfitabilitySettings {
+    fn default() -> Self {
+        Self {
+            tariff: TariffKind::Flat,
+            flat_usd_per_kwh: 0.06,
+            periods: vec![TariffPeriod {
+                name: "Peak".to_string(),
+                start_hour: 16,
+                end_hour: 21,
+                usd_per_kwh: 0.12,
+            }],
+            base_usd_per_kwh: 0.05,
+            hashprice_source: HashpriceSource::Manual,
+            hashprice_usd_per_th_day: 0.05,
+            hashprice_file: String::new(),
+            pool_fee_percent: 2.0,
+        }
+    }
+}
+
+impl ProfitabilitySettings {
+    pub fn rate_at_hour(&self, hour: u32) -> f64 {
+        match self.tariff {
+            TariffKind::Flat => self.flat_usd_per_kwh,
+            TariffKind::TimeOfUse => self
+                .periods
+                .iter()
+                .find(|period| period.covers(hour))
+                .map(|period| period.usd_per_kwh)
+                .unwrap_or(self.base_usd_per_kwh),
+        }
+    }
+
+    /// Cost of running one kilowatt for a full day across every tariff period.
+    pub fn usd_per_kw_day(&self) -> f64 {
+        (0..24).map(|hour| self.rate_at_hour(hour)).sum()
+    }
+}
+
+/// Reads a hashprice in $/TH/day from either a bare number or a JSON object
+/// with a `hashprice_usd_per_th_day` (or `hashprice`) field.
+pub fn read_hashprice_file(path: &str) -> Result<f64, String> {
+    let contents = fs::read_to_string(path).map_err(|e| format!("Failed to read {path}: {e}"))?;
+    let trimmed = contents.trim();
+    if let Ok(value) = trimmed.parse::<f64>() {
+        return Ok(value);
+    }
+    let json: serde_json::Value =
+        serde_json::from_str(trimmed).map_err(|e| format!("Unrecognised hashprice file: {e}"))?;
+    ["hashprice_usd_per_th_day", "hashprice"]
+        .iter()
+        .find_map(|key| json.get(key).and_then(|v| v.as_f64()))
+        .ok_or_else(|| "Hashprice file has no hashprice_usd_per_th_day field".to_string())
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct MinerEconomics {
+    pub ip: String,
+    pub model: String,
+    pub hashrate_th: f64,
+    pub power_w: f64,
+    pub revenue_per_day: f64,
+    pub cost_per_day: f64,
+    /// Average electricity price at which this miner stops making money.
+    pub breakeven_usd_per_kwh: f64,
+}
+
+impl MinerEconomics {
+    pub fn margin_per_day(&self) -> f64 {
+        self.revenue_per_day - self.cost_per_day
+    }
+
+    pub fn is_unprofitable(&self) -> bool {
+        self.margin_per_day() < 0.0
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct EconomicsTotals {
+    pub name: String,
+    pub miners: usize,
+    pub unprofitable: usize,
+    pub revenue_per_day: f64,
+    pub cost_per_day: f64,
+}
+
+impl EconomicsTotals {
+    pub fn margin_per_day(&self) -> f64 {
+        self.revenue_per_day - self.cost_per_day
+    }
+
+    fn add(&mut self, economics: &MinerEconomics) {
+        self.miners += 1;
+        self.unprofitable += economics.is_unprofitable() as usize;
+        self.revenue_per_day += economics.revenue_per_day;
+        self.cost_per_day += economics.cost_per_day;
+    }
+}
+
+/// Daily economics for every miner reporting power, at its current settings.
+pub fn fleet_economics(
+    settings: &ProfitabilitySettings,
+    hashprice_usd_per_th_day: f64,
+    miners: &[MinerInfo],
+) -> Vec<MinerEconomics> {
+    let usd_per_kw_day = settings.usd_per_kw_day();
+    let revenue_share = 1.0 - settings.pool_fee_percent / 100.0;
+
+    miners
+        .iter()
+        .filter_map(|miner| {
+            let power_w = miner.wattage_w.filter(|w| *w > 0.0)?;
+            let hashrate_th = miner.hashrate_th.unwrap_or(0.0);
+            let revenue_per_day = hashrate_th * hashprice_usd_per_th_day * revenue_share;
+            Some(MinerEconomics {
+                ip: miner.ip.clone(),
+                model: miner.model.clone(),
+                hashrate_th,
+                power_w,
+                revenue_per_day,
+                cost_per_day: power_w / 1000.0 * usd_per_kw_day,
+                breakeven_usd_per_kwh: revenue_per_day / (power_w / 1000.0 * 24.0),
+            })
+        })
+        .collect()
+}
+
+pub fn total_economics(name: &str, economics: &[MinerEconomics]) -> EconomicsTotals {
+    let mut totals = EconomicsTotals {
+        name: name.to_string(),
+        ..Default::default()
+    };
+    for entry in economics {
+        totals.add(entry);
+    }
+    totals
+}
+
+/// Totals per saved range, in range order. Miners outside every range are
+/// grouped last so the ranges add up to the fleet.
+pub fn economics_by_range(
+    economics: &[MinerEconomics],
+    saved_ranges: &[SavedRange],
+) -> Vec<EconomicsTotals> {
+    let mut totals: Vec<EconomicsTotals> = saved_ranges
+        .iter()
+        .map(|range| EconomicsTotals {
+            name: range.name.clone(),
+            ..Default::default()
+        })
+        .collect();
+    let mut unassigned = EconomicsTotals {
+        name: "Not in a saved range".to_string(),
+        ..Default::default()
+    };
+
+    for entry in economics {
+        match saved_ranges
+            .iter()
+            .position(|range| scanner::range_contains_ip(&range.range, &entry.ip))
+        {
+            Some(index) => totals[index].add(entry),
+            None => unassigned.add(entry),
+        }
+    }
+
+    totals.retain(|t| t.miners > 0);
+    if unassigned.miners > 0 {
+        totals.push(unassigned);
+    }
+    totals
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        economics_by_range, fleet_economics, ProfitabilitySettings, TariffKind, TariffPeriod,
+    };
+    use crate::models::{MinerInfo, SavedRange};
+
+    #[test]
+    fn time_of_use_periods_wrap_midnight() {
+        let settings = ProfitabilitySettings {
+            tariff: TariffKind::TimeOfUse,
+            periods: vec![TariffPeriod {
+                name: "Night".to_string(),
+                start_hour: 22,
+                end_hour: 6,
+                usd_per_kwh: 0.03,
+            }],
+            base_usd_per_kwh: 0.09,
+            ..Default::default()
+        };
+
+        assert_eq!(settings.rate_at_hour(23), 0.03);
+        assert_eq!(settings.rate_at_hour(5), 0.03);
+        assert_eq!(settings.rate_at_hour(6), 0.09);
+        // 8 night hours and 16 day hours
+        assert!((settings.usd_per_kw_day() - (8.0 * 0.03 + 16.0 * 0.09)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn economics_flag_unprofitable_miners_and_group_by_range() {
+        let miner = |ip: &str, hashrate: f64, watts: f64| MinerInfo {
+            ip: ip.to_string(),
+            hashrate_th: Some(hashrate),
+            wattage_w: Some(watts),
+            ..Default::default()
+        };
+        let settings = ProfitabilitySettings {
+            flat_usd_per_kwh: 0.10,
+            pool_fee_percent: 0.0,
+            ..Default::default()
+        };
+        let miners = vec![
+            miner("10.0.0.5", 200.0, 3500.0),
+            miner("10.0.0.6", 90.0, 3400.0),
+            miner("10.0.1.5", 100.0, 3000.0),
+        ];
+
+        let economics = fleet_economics(&settings, 0.05, &miners);
+        // 200 TH * $0.05 = $10 revenue against 3.5 kW * 24 h * $0.10 = $8.40
+        assert!((economics[0].margin_per_day() - 1.6).abs() < 1e-9);
+        assert!(economics[1].is_unprofitable());
+
+        let ranges = vec![SavedRange {
+            name: "Row A".to_string(),
+            range: "10.0.0.1-254".to_string(),
+        }];
+        let totals = economics_by_range(&economics, &ranges);
+        assert_eq!(totals.len(), 2);
+        assert_eq!((totals[0].miners, totals[0].unprofitable), (2, 1));
+        assert_eq!(totals[1].name, "Not in a saved range");
+    }
+}
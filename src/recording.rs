@@ -1,3 +1,4 @@
+use crate::export::csv_field;
 use crate::models::{MinerInfo, RecordingState};
 use asic_rs_core::data::hashrate::HashRateUnit;
 use chrono::Local;
@@ -151,8 +152,8 @@ pub fn append_data_point(
         data.mac
             .map(|m| m.to_string())
             .unwrap_or_else(|| "N/A".to_string()),
-        miner.model,
-        miner.firmware_version,
+        csv_field(&miner.model),
+        csv_field(&miner.firmware_version),
         timestamp,
         total_hashrate,
         power,
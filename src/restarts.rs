@@ -0,0 +1,150 @@
+use crate::credentials;
+use chrono::Local;
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+
+// Give the miner time to actually go down before the first poll
+const INITIAL_DELAY_SECS: u64 = 20;
+const POLL_INTERVAL_SECS: u64 = 10;
+const GIVE_UP_AFTER_SECS: i64 = 15 * 60;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RestartPhase {
+    WaitingForReboot,
+    BackOnline { after_secs: i64 },
+    TimedOut,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RestartStatus {
+    pub requested_at: i64,
+    pub phase: RestartPhase,
+}
+
+impl RestartStatus {
+    pub fn describe(&self, now: i64) -> String {
+        match self.phase {
+            RestartPhase::WaitingForReboot => {
+                format!("restarting for {}", format_secs(now - self.requested_at))
+            }
+            RestartPhase::BackOnline { after_secs } => {
+                format!("back online after {}", format_secs(after_secs))
+            }
+            RestartPhase::TimedOut => {
+                format!("not back after {}", format_secs(GIVE_UP_AFTER_SECS))
+            }
+        }
+    }
+}
+
+fn format_secs(secs: i64) -> String {
+    format!("{}m{:02}s", secs / 60, secs % 60)
+}
+
+/// Whether a miner answering now has been through the reboot requested at
+/// `requested_at`. A reported uptime shorter than the time since the request
+/// proves it; without one, answering after having been unreachable has to do.
+pub fn has_rebooted(
+    requested_at: i64,
+    now: i64,
+    uptime_secs: Option<u64>,
+    went_offline: bool,
+) -> bool {
+    match uptime_secs {
+        Some(uptime) => (uptime as i64) < now - requested_at,
+        None => went_offline,
+    }
+}
+
+fn statuses() -> &'static Mutex<HashMap<String, RestartStatus>> {
+    static STATUSES: OnceLock<Mutex<HashMap<String, RestartStatus>>> = OnceLock::new();
+    STATUSES.get_or_init(Default::default)
+}
+
+fn set_phase(ip: &str, requested_at: i64, phase: RestartPhase) {
+    let mut statuses = statuses().lock().unwrap();
+    // A newer restart of the same miner owns the entry now
+    if statuses
+        .get(ip)
+        .is_some_and(|status| status.requested_at != requested_at)
+    {
+        return;
+    }
+    statuses.insert(
+        ip.to_string(),
+        RestartStatus {
+            requested_at,
+            phase,
+        },
+    );
+}
+
+/// Polls `ip` in the background after a restart was sent until it is back.
+pub fn track(ip: &str) {
+    let ip = ip.to_string();
+    let requested_at = Local::now().timestamp();
+    statuses().lock().unwrap().insert(
+        ip.clone(),
+        RestartStatus {
+            requested_at,
+            phase: RestartPhase::WaitingForReboot,
+        },
+    );
+
+    crate::runtime::spawn(async move {
+        tokio::time::sleep(Duration::from_secs(INITIAL_DELAY_SECS)).await;
+        let mut went_offline = false;
+        loop {
+            let now = Local::now().timestamp();
+            if now - requested_at >= GIVE_UP_AFTER_SECS {
+                eprintln!("✗ {ip} did not come back after restart");
+                set_phase(&ip, requested_at, RestartPhase::TimedOut);
+                return;
+            }
+
+            match credentials::connect(&ip).await {
+                Ok(miner) => {
+                    let data = miner.get_data().await;
+                    let uptime = data.uptime.map(|u| u.as_secs());
+                    if has_rebooted(requested_at, now, uptime, went_offline) {
+                        println!("✓ {ip} back online after restart");
+                        set_phase(
+                            &ip,
+                            requested_at,
+                            RestartPhase::BackOnline {
+                                after_secs: now - requested_at,
+                            },
+                        );
+                        return;
+                    }
+                }
+                Err(_) => went_offline = true,
+            }
+            tokio::time::sleep(Duration::from_secs(POLL_INTERVAL_SECS)).await;
+        }
+    });
+}
+
+pub fn status(ip: &str) -> Option<RestartStatus> {
+    statuses().lock().unwrap().get(ip).copied()
+}
+
+/// IP -> status of every miner restarted this session.
+pub fn snapshot() -> HashMap<String, RestartStatus> {
+    statuses().lock().unwrap().clone()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::has_rebooted;
+
+    #[test]
+    fn reboot_needs_fresh_uptime_or_an_outage() {
+        // Restart sent at t=1000, miner answers at t=1120
+        assert!(has_rebooted(1000, 1120, Some(60), false));
+        assert!(!has_rebooted(1000, 1120, Some(86_400), true));
+        assert!(has_rebooted(1000, 1120, None, true));
+        assert!(!has_rebooted(1000, 1120, None, false));
+    }
+}
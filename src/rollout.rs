@@ -0,0 +1,435 @@
+use crate::credentials;
+use crate::models::{MinerInfo, MinerLocation, MinerOptionSettings};
+use crate::options;
+use crate::snapshots::SnapshotRecorder;
+use asic_rs_core::data::hashrate::HashRateUnit;
+use futures::stream::{self, StreamExt};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Regression checks per soak, spread evenly so a miner that fails a few
+/// minutes after the write is still caught.
+const SOAK_CHECKS: u32 = 3;
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct RolloutSettings {
+    pub enabled: bool,
+    pub canary_count: usize,
+    pub wave_size: usize,
+    pub soak_secs: u64,
+    pub max_hashrate_drop_percent: f64,
+    pub max_temp_rise_c: f64,
+}
+
+impl Default for RolloutSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            canary_count: 3,
+            wave_size: 25,
+            soak_secs: 600,
+            max_hashrate_drop_percent: 10.0,
+            max_temp_rise_c: 8.0,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MinerSample {
+    pub hashrate_th: Option<f64>,
+    pub temperature_c: Option<f64>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum RolloutPhase {
+    Applying,
+    Soaking { until: Instant, next_check: Instant },
+    Checking { until: Instant },
+    Halted(String),
+    Completed,
+    Aborted,
+}
+
+type WriteResults = Vec<(String, Result<Vec<String>, String>)>;
+
+pub enum RolloutAction {
+    Continue,
+    Abort,
+    Dismiss,
+}
+
+pub struct StagedRollout {
+    pub title: String,
+    pub settings: RolloutSettings,
+    pub waves: Vec<Vec<(String, MinerOptionSettings)>>,
+    pub current_wave: usize,
+    pub phase: RolloutPhase,
+    baselines: HashMap<String, MinerSample>,
+    /// Miners that failed and were let through with Continue; never checked again.
+    held_out: HashSet<String>,
+    /// Miners of a halted wave that did not fail, re-checked with the next wave.
+    recheck: Vec<String>,
+    /// Miners behind the current halt.
+    halted_ips: Vec<String>,
+    recorder: SnapshotRecorder,
+    locations: Arc<HashMap<String, MinerLocation>>,
+    /// Per-miner write results of the current wave, once it has been applied.
+    wave_results: Arc<Mutex<Option<WriteResults>>>,
+    samples: Arc<Mutex<Option<HashMap<String, MinerSample>>>>,
+}
+
+/// Splits targets into a canary wave followed by fixed-size waves.
+pub fn plan_waves<T>(targets: Vec<T>, canary_count: usize, wave_size: usize) -> Vec<Vec<T>> {
+    let mut waves = Vec::new();
+    let mut remaining = targets.into_iter();
+
+    let canaries: Vec<T> = remaining.by_ref().take(canary_count.max(1)).collect();
+    if !canaries.is_empty() {
+        waves.push(canaries);
+    }
+
+    loop {
+        let wave: Vec<T> = remaining.by_ref().take(wave_size.max(1)).collect();
+        if wave.is_empty() {
+            break;
+        }
+        waves.push(wave);
+    }
+
+    waves
+}
+
+/// Describes every miner of a wave whose option write failed.
+pub fn failed_writes(results: &[(String, Result<Vec<String>, String>)]) -> Vec<String> {
+    let mut failed: Vec<String> = results
+        .iter()
+        .filter_map(|(ip, result)| {
+            result
+                .as_ref()
+                .err()
+                .map(|err| format!("{ip}: write failed: {err}"))
+        })
+        .collect();
+    failed.sort();
+    failed
+}
+
+/// Compares post-apply samples to the pre-rollout baseline and describes, by
+/// IP, every miner that dropped out, lost too much hashrate or heated up too much.
+pub fn find_regressions(
+    ips: &[String],
+    baselines: &HashMap<String, MinerSample>,
+    samples: &HashMap<String, MinerSample>,
+    settings: &RolloutSettings,
+) -> Vec<(String, String)> {
+    let mut regressions = Vec::new();
+
+    for ip in ips {
+        let mut push = |description: String| regressions.push((ip.clone(), description));
+        let Some(sample) = samples.get(ip) else {
+            push(format!("{ip}: unreachable after apply"));
+            continue;
+        };
+        let baseline = baselines.get(ip).copied().unwrap_or(MinerSample {
+            hashrate_th: None,
+            temperature_c: None,
+        });
+
+        if let (Some(before), Some(after)) = (baseline.hashrate_th, sample.hashrate_th) {
+            if before > 0.0 {
+                let drop_percent = (before - after) / before * 100.0;
+                if drop_percent > settings.max_hashrate_drop_percent {
+                    push(format!(
+                        "{ip}: hashrate {before:.2} → {after:.2} TH/s (-{drop_percent:.1}%)"
+                    ));
+                }
+            }
+        } else if baseline.hashrate_th.is_some() {
+            push(format!("{ip}: no hashrate reported after apply"));
+        }
+
+        if let (Some(before), Some(after)) = (baseline.temperature_c, sample.temperature_c) {
+            if after - before > settings.max_temp_rise_c {
+                push(format!(
+                    "{ip}: temperature {before:.1} → {after:.1}°C (+{:.1}°C)",
+                    after - before
+                ));
+            }
+        }
+    }
+
+    regressions
+}
+
+async fn sample_miners(ips: Vec<String>) -> HashMap<String, MinerSample> {
+    let concurrency = ips.len().clamp(1, 24);
+
+    stream::iter(ips)
+        .map(|ip| async move {
+            let miner = credentials::connect(&ip).await.ok()?;
+            let data = miner.get_data().await;
+            let sample = MinerSample {
+                hashrate_th: data
+                    .hashrate
+                    .map(|hr| hr.as_unit(HashRateUnit::TeraHash).value),
+                temperature_c: data.average_temperature.map(|t| t.as_celsius()),
+            };
+            Some((ip, sample))
+        })
+        .buffer_unordered(concurrency)
+        .filter_map(|result| async move { result })
+        .collect()
+        .await
+}
+
+impl StagedRollout {
+    pub fn start(
+        title: String,
+        settings: RolloutSettings,
+        targets: Vec<(String, MinerOptionSettings)>,
+        miners: &[MinerInfo],
+        recorder: SnapshotRecorder,
+        locations: Arc<HashMap<String, MinerLocation>>,
+    ) -> Self {
+        let baselines = miners
+            .iter()
+            .filter(|m| targets.iter().any(|(ip, _)| ip == &m.ip))
+            .map(|m| {
+                (
+                    m.ip.clone(),
+                    MinerSample {
+                        hashrate_th: m.hashrate_th,
+                        temperature_c: m.temperature_c,
+                    },
+                )
+            })
+            .collect();
+
+        let mut rollout = Self {
+            title,
+            waves: plan_waves(targets, settings.canary_count, settings.wave_size),
+            settings,
+            current_wave: 0,
+            phase: RolloutPhase::Applying,
+            baselines,
+            held_out: HashSet::new(),
+            recheck: Vec::new(),
+            halted_ips: Vec::new(),
+            recorder,
+            locations,
+            wave_results: Arc::new(Mutex::new(None)),
+            samples: Arc::new(Mutex::new(None)),
+        };
+        rollout.apply_current_wave();
+        rollout
+    }
+
+    pub fn total_targets(&self) -> usize {
+        self.waves.iter().map(Vec::len).sum()
+    }
+
+    pub fn is_finished(&self) -> bool {
+        matches!(self.phase, RolloutPhase::Completed | RolloutPhase::Aborted)
+    }
+
+    fn current_wave_ips(&self) -> Vec<String> {
+        self.waves
+            .get(self.current_wave)
+            .map(|wave| wave.iter().map(|(ip, _)| ip.clone()).collect())
+            .unwrap_or_default()
+    }
+
+    /// The current wave plus what is left of a wave let through with Continue.
+    fn checked_ips(&self) -> Vec<String> {
+        let mut ips = self.recheck.clone();
+        ips.extend(self.current_wave_ips());
+        ips.retain(|ip| !self.held_out.contains(ip));
+        ips
+    }
+
+    fn halt(&mut self, ips: Vec<String>, reasons: Vec<String>) {
+        self.halted_ips = ips;
+        self.phase = RolloutPhase::Halted(reasons.join("\n"));
+    }
+
+    fn start_check(&mut self, until: Instant) {
+        self.phase = RolloutPhase::Checking { until };
+        *self.samples.lock().unwrap() = None;
+
+        let ips = self.checked_ips();
+        let samples = Arc::clone(&self.samples);
+        crate::runtime::spawn(async move {
+            let result = sample_miners(ips).await;
+            *samples.lock().unwrap() = Some(result);
+        });
+    }
+
+    fn check_interval(&self) -> Duration {
+        Duration::from_secs(self.settings.soak_secs) / SOAK_CHECKS
+    }
+
+    fn apply_current_wave(&mut self) {
+        let Some(wave) = self.waves.get(self.current_wave).cloned() else {
+            self.phase = RolloutPhase::Completed;
+            return;
+        };
+
+        self.phase = RolloutPhase::Applying;
+        *self.wave_results.lock().unwrap() = None;
+
+        let recorder = self.recorder.clone();
+        let locations = Arc::clone(&self.locations);
+        let wave_results = Arc::clone(&self.wave_results);
+        crate::runtime::spawn(async move {
+            let results = options::apply_options_to_many(wave, recorder, locations).await;
+            *wave_results.lock().unwrap() = Some(results);
+        });
+    }
+
+    /// Advances the rollout; called once per frame from the update loop.
+    pub fn tick(&mut self) {
+        match self.phase.clone() {
+            RolloutPhase::Applying => {
+                let Some(results) = self.wave_results.lock().unwrap().take() else {
+                    return;
+                };
+
+                // An unchanged miner never looks regressed, so failed writes
+                // must stop the rollout before the soak
+                let failed = failed_writes(&results);
+                if failed.is_empty() {
+                    let now = Instant::now();
+                    self.phase = RolloutPhase::Soaking {
+                        until: now + Duration::from_secs(self.settings.soak_secs),
+                        next_check: now + self.check_interval(),
+                    };
+                } else {
+                    let ips = results
+                        .iter()
+                        .filter(|(_, result)| result.is_err())
+                        .map(|(ip, _)| ip.clone())
+                        .collect();
+                    self.halt(ips, failed);
+                }
+            }
+            RolloutPhase::Soaking { until, next_check } => {
+                if Instant::now() >= next_check.min(until) {
+                    self.start_check(until);
+                }
+            }
+            RolloutPhase::Checking { until } => {
+                let Some(samples) = self.samples.lock().unwrap().take() else {
+                    return;
+                };
+
+                let regressions = find_regressions(
+                    &self.checked_ips(),
+                    &self.baselines,
+                    &samples,
+                    &self.settings,
+                );
+
+                // The last check runs at the end of the soak; earlier ones
+                // only catch a miner that falls over mid-soak
+                if !regressions.is_empty() {
+                    let mut ips: Vec<String> =
+                        regressions.iter().map(|(ip, _)| ip.clone()).collect();
+                    ips.dedup();
+                    let reasons = regressions.into_iter().map(|(_, reason)| reason).collect();
+                    self.halt(ips, reasons);
+                } else if Instant::now() >= until {
+                    self.recheck.clear();
+                    self.current_wave += 1;
+                    self.apply_current_wave();
+                } else {
+                    self.phase = RolloutPhase::Soaking {
+                        until,
+                        next_check: Instant::now() + self.check_interval(),
+                    };
+                }
+            }
+            RolloutPhase::Halted(_) | RolloutPhase::Completed | RolloutPhase::Aborted => {}
+        }
+    }
+
+    pub fn handle_action(&mut self, action: RolloutAction) {
+        match action {
+            RolloutAction::Continue => {
+                if matches!(self.phase, RolloutPhase::Halted(_)) {
+                    // The rest of the halted wave is watched alongside the next
+                    let halted: Vec<String> = self.halted_ips.drain(..).collect();
+                    self.held_out.extend(halted);
+                    self.recheck = self.checked_ips();
+                    self.current_wave += 1;
+                    self.apply_current_wave();
+                }
+            }
+            RolloutAction::Abort => {
+                if !self.is_finished() {
+                    self.phase = RolloutPhase::Aborted;
+                }
+            }
+            RolloutAction::Dismiss => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{failed_writes, find_regressions, plan_waves, MinerSample, RolloutSettings};
+    use std::collections::HashMap;
+
+    #[test]
+    fn plan_waves_starts_with_canaries() {
+        let waves = plan_waves((1..=8).collect(), 2, 3);
+        assert_eq!(waves, vec![vec![1, 2], vec![3, 4, 5], vec![6, 7, 8]]);
+    }
+
+    #[test]
+    fn failed_writes_lists_only_errors() {
+        let results = vec![
+            (
+                "10.0.0.2".to_string(),
+                Err("Failed power limit".to_string()),
+            ),
+            ("10.0.0.1".to_string(), Ok(vec!["power=3000W".to_string()])),
+        ];
+        assert_eq!(
+            failed_writes(&results),
+            vec!["10.0.0.2: write failed: Failed power limit"]
+        );
+    }
+
+    #[test]
+    fn plan_waves_handles_fewer_targets_than_canaries() {
+        let waves = plan_waves(vec![1], 3, 10);
+        assert_eq!(waves, vec![vec![1]]);
+    }
+
+    #[test]
+    fn find_regressions_flags_hashrate_drop_and_missing_miners() {
+        let ips = vec!["10.0.0.1".to_string(), "10.0.0.2".to_string()];
+        let baseline = MinerSample {
+            hashrate_th: Some(100.0),
+            temperature_c: Some(60.0),
+        };
+        let baselines: HashMap<String, MinerSample> =
+            ips.iter().map(|ip| (ip.clone(), baseline)).collect();
+        let samples = HashMap::from([(
+            "10.0.0.1".to_string(),
+            MinerSample {
+                hashrate_th: Some(80.0),
+                temperature_c: Some(62.0),
+            },
+        )]);
+
+        let regressions = find_regressions(&ips, &baselines, &samples, &RolloutSettings::default());
+        assert_eq!(regressions.len(), 2);
+        assert_eq!(regressions[0].0, "10.0.0.1");
+        assert!(regressions[0].1.contains("-20.0%"));
+        assert!(regressions[1].1.contains("unreachable"));
+    }
+}
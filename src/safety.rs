@@ -0,0 +1,173 @@
+use crate::actions::MinerAction;
+use crate::models::MinerInfo;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct SafetySettings {
+    /// Ask before a bulk action from the table toolbar runs.
+    pub confirm_bulk_actions: bool,
+    /// Bulk actions and applies reaching this many miners must be typed out; 0 turns it off.
+    pub typed_confirmation_min_miners: usize,
+}
+
+impl Default for SafetySettings {
+    fn default() -> Self {
+        Self {
+            confirm_bulk_actions: true,
+            typed_confirmation_min_miners: 20,
+        }
+    }
+}
+
+impl SafetySettings {
+    pub fn requires_typing(&self, miner_count: usize) -> bool {
+        self.typed_confirmation_min_miners > 0 && miner_count >= self.typed_confirmation_min_miners
+    }
+}
+
+/// Splits `ips` into the miners a bulk action may touch and the protected
+/// ones it must skip. `protected` holds `MinerInfo::identity()` values.
+pub fn split_protected(
+    ips: Vec<String>,
+    miners: &[MinerInfo],
+    protected: &HashSet<String>,
+) -> (Vec<String>, Vec<String>) {
+    if protected.is_empty() {
+        return (ips, Vec::new());
+    }
+    let protected_ips = protected_ips(miners, protected);
+    ips.into_iter().partition(|ip| !protected_ips.contains(ip))
+}
+
+/// IPs of the scanned miners whose identity is protected.
+pub fn protected_ips(miners: &[MinerInfo], protected: &HashSet<String>) -> HashSet<String> {
+    miners
+        .iter()
+        .filter(|miner| protected.contains(&miner.identity()))
+        .map(|miner| miner.ip.clone())
+        .collect()
+}
+
+/// Miner count per model among `ips`, largest group first.
+pub fn model_summary(ips: &[String], miners: &[MinerInfo]) -> Vec<(String, usize)> {
+    let ips: HashSet<&String> = ips.iter().collect();
+    let mut counts: BTreeMap<String, usize> = BTreeMap::new();
+    for miner in miners.iter().filter(|miner| ips.contains(&miner.ip)) {
+        let model = if miner.model.is_empty() {
+            "Unknown".to_string()
+        } else {
+            miner.model.clone()
+        };
+        *counts.entry(model).or_default() += 1;
+    }
+    let mut counts: Vec<(String, usize)> = counts.into_iter().collect();
+    counts.sort_by(|a, b| b.1.cmp(&a.1));
+    counts
+}
+
+/// Phrase an operator types to confirm touching `miner_count` miners.
+pub fn confirmation_phrase(action: &str, miner_count: usize) -> String {
+    format!("{} {}", action.to_uppercase(), miner_count)
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BulkAction {
+    Miner(MinerAction),
+    ToggleFaultLight,
+}
+
+impl BulkAction {
+    pub fn label(self) -> &'static str {
+        match self {
+            BulkAction::Miner(MinerAction::Pause) => "Stop",
+            BulkAction::Miner(MinerAction::Resume) => "Start",
+            BulkAction::Miner(MinerAction::Restart) => "Restart",
+            BulkAction::ToggleFaultLight => "Toggle fault light",
+        }
+    }
+}
+
+/// A bulk action waiting for the operator to confirm it.
+#[derive(Clone, Debug)]
+pub struct BulkConfirmation {
+    pub action: BulkAction,
+    pub targets: Vec<String>,
+    pub skipped_protected: Vec<String>,
+    pub models: Vec<(String, usize)>,
+    pub typed: String,
+}
+
+impl BulkConfirmation {
+    pub fn new(
+        action: BulkAction,
+        ips: Vec<String>,
+        miners: &[MinerInfo],
+        protected: &HashSet<String>,
+    ) -> Self {
+        let (mut targets, skipped_protected) = split_protected(ips, miners, protected);
+        targets.sort();
+        Self {
+            action,
+            models: model_summary(&targets, miners),
+            targets,
+            skipped_protected,
+            typed: String::new(),
+        }
+    }
+
+    pub fn phrase(&self) -> String {
+        confirmation_phrase(self.action.label(), self.targets.len())
+    }
+
+    pub fn needs_dialog(&self, settings: &SafetySettings) -> bool {
+        settings.confirm_bulk_actions
+            || settings.requires_typing(self.targets.len())
+            || !self.skipped_protected.is_empty()
+    }
+
+    pub fn is_confirmed(&self, settings: &SafetySettings) -> bool {
+        !self.targets.is_empty()
+            && (!settings.requires_typing(self.targets.len()) || self.typed.trim() == self.phrase())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BulkAction, BulkConfirmation, SafetySettings};
+    use crate::actions::MinerAction;
+    use crate::models::MinerInfo;
+    use std::collections::HashSet;
+
+    #[test]
+    fn protected_miners_are_skipped_and_large_actions_must_be_typed() {
+        let miners: Vec<MinerInfo> = (1..=3)
+            .map(|n| MinerInfo {
+                ip: format!("10.0.0.{n}"),
+                model: "S19".to_string(),
+                ..Default::default()
+            })
+            .collect();
+        let ips = miners.iter().map(|m| m.ip.clone()).collect();
+        let protected = HashSet::from(["10.0.0.2".to_string()]);
+
+        let mut confirmation = BulkConfirmation::new(
+            BulkAction::Miner(MinerAction::Pause),
+            ips,
+            &miners,
+            &protected,
+        );
+        assert_eq!(confirmation.targets, vec!["10.0.0.1", "10.0.0.3"]);
+        assert_eq!(confirmation.skipped_protected, vec!["10.0.0.2"]);
+        assert_eq!(confirmation.models, vec![("S19".to_string(), 2)]);
+
+        let settings = SafetySettings {
+            confirm_bulk_actions: false,
+            typed_confirmation_min_miners: 2,
+        };
+        assert!(!confirmation.is_confirmed(&settings));
+        confirmation.typed = "STOP 2".to_string();
+        assert!(confirmation.is_confirmed(&settings));
+    }
+}
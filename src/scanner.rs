@@ -1,10 +1,12 @@
+use crate::baselines;
 use crate::models::{HashratePoint, MinerCapabilities, MinerInfo, ScanProgress};
+use crate::options;
 use asic_rs::MinerFactory;
 use asic_rs_core::data::hashrate::{HashRate, HashRateUnit};
-use asic_rs_core::data::miner::MinerData;
+use asic_rs_core::data::miner::{MinerData, TuningTarget};
 use asic_rs_core::data::pool::PoolData;
 use futures::stream::{self, StreamExt};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::net::Ipv4Addr;
 use std::sync::{Arc, Mutex};
 use std::thread;
@@ -73,6 +75,30 @@ pub fn calculate_total_ips(range: &str) -> usize {
     0
 }
 
+pub fn range_contains_ip(range: &str, ip: &str) -> bool {
+    let Ok(addr) = ip.parse::<Ipv4Addr>() else {
+        return false;
+    };
+
+    // Parse "192.168.1.1-254" format
+    if let Some(dash_pos) = range.rfind('-') {
+        let Ok(start) = range[..dash_pos].parse::<Ipv4Addr>() else {
+            return false;
+        };
+        let Ok(end_last) = range[dash_pos + 1..].parse::<u8>() else {
+            return false;
+        };
+
+        let start_octets = start.octets();
+        let octets = addr.octets();
+        return start_octets[..3] == octets[..3]
+            && octets[3] >= start_octets[3]
+            && octets[3] <= end_last;
+    }
+
+    range.parse::<Ipv4Addr>().is_ok_and(|single| single == addr)
+}
+
 fn hashrate_to_terahash(hashrate: Option<&HashRate>) -> Option<f64> {
     hashrate
         .cloned()
@@ -94,7 +120,7 @@ fn average_fan_rpm(data: &MinerData) -> Option<f64> {
     }
 }
 
-fn primary_pool(data: &MinerData) -> Option<&PoolData> {
+pub fn primary_pool(data: &MinerData) -> Option<&PoolData> {
     data.pools
         .iter()
         .flat_map(|group| group.pools.iter())
@@ -190,9 +216,11 @@ fn build_miner_info(ip: String, data: MinerData, capabilities: MinerCapabilities
         active_boards_count: (total_boards > 0).then_some(active_boards),
         total_boards_count: (total_boards > 0).then_some(total_boards),
         capabilities,
+        mining_mode: None,
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn scan_ranges(
     ranges: Vec<String>,
     miners: Arc<Mutex<Vec<MinerInfo>>>,
@@ -201,6 +229,7 @@ pub fn scan_ranges(
     identification_timeout_secs: u64,
     connectivity_timeout_secs: u64,
     connectivity_retries: u32,
+    mode_specific_models: HashSet<String>,
 ) {
     thread::spawn(move || {
         let rt = tokio::runtime::Runtime::new().unwrap();
@@ -249,18 +278,35 @@ pub fn scan_ranges(
             }
 
             let data_fetch_concurrency = discovered_miners.len().clamp(1, 64);
-            let miner_infos: Vec<MinerInfo> = stream::iter(discovered_miners.into_iter())
-                .map(|miner| async move {
+            let mode_specific_models = &mode_specific_models;
+            let miner_infos: Vec<MinerInfo> = stream::iter(discovered_miners)
+                .map(|mut miner| async move {
+                    let prefetched = crate::credentials::authenticate(&mut miner).await;
                     let capabilities = MinerCapabilities {
                         set_power_limit: miner.supports_set_power_limit(),
                         fan_config: miner.supports_fan_config(),
                         tuning_config: miner.supports_tuning_config(),
                         scaling_config: miner.supports_scaling_config(),
                         pools_config: miner.supports_pools_config(),
+                        restart: miner.supports_restart(),
                     };
                     let ip = miner.get_ip().to_string();
-                    let data = miner.get_data().await;
-                    build_miner_info(ip, data, capabilities)
+                    let data = match prefetched {
+                        Some(data) => data,
+                        None => miner.get_data().await,
+                    };
+                    let mut info = build_miner_info(ip, data, capabilities);
+                    // Only worth a call when a baseline depends on the mode
+                    if capabilities.tuning_config
+                        && mode_specific_models.contains(&baselines::normalize_model(&info.model))
+                    {
+                        if let Some(TuningTarget::MiningMode(mode)) =
+                            miner.get_tuning_target().await
+                        {
+                            info.mining_mode = Some(options::map_mining_mode_selection(mode));
+                        }
+                    }
+                    info
                 })
                 .buffer_unordered(data_fetch_concurrency)
                 .collect()
@@ -323,7 +369,7 @@ pub fn scan_ranges(
 
 #[cfg(test)]
 mod tests {
-    use super::{calculate_total_ips, parse_ip_range};
+    use super::{calculate_total_ips, parse_ip_range, range_contains_ip};
 
     #[test]
     fn parse_ip_range_supports_single_ip() {
@@ -348,4 +394,12 @@ mod tests {
         assert_eq!(calculate_total_ips("10.0.81.1-254"), 254);
         assert_eq!(calculate_total_ips("10.0.81.42"), 1);
     }
+
+    #[test]
+    fn range_contains_ip_checks_subnet_and_last_octet() {
+        assert!(range_contains_ip("10.0.81.1-254", "10.0.81.10"));
+        assert!(!range_contains_ip("10.0.81.1-254", "10.0.82.10"));
+        assert!(!range_contains_ip("10.0.81.10-20", "10.0.81.21"));
+        assert!(range_contains_ip("10.0.81.42", "10.0.81.42"));
+    }
 }
@@ -0,0 +1,337 @@
+use crate::actions::MinerAction;
+use crate::models::ProfileMatchRules;
+use chrono::{DateTime, Datelike, Duration, Local, TimeZone, Timelike};
+use serde::{Deserialize, Serialize};
+use std::fs;
+
+const SCHEDULE_LOG_FILE: &str = "schedule_log.json";
+const MAX_LOG_ENTRIES: usize = 500;
+// How far back missed minutes are caught up after a stall or while minimized
+const MAX_CATCH_UP_MINUTES: i64 = 120;
+
+/// Standard 5-field cron expression: minute hour day-of-month month day-of-week.
+/// Fields accept `*`, numbers, lists (`1,15`), ranges (`9-17`) and steps (`*/15`).
+#[derive(Clone, Debug, PartialEq)]
+pub struct CronSchedule {
+    minutes: Vec<bool>,
+    hours: Vec<bool>,
+    days_of_month: Vec<bool>,
+    months: Vec<bool>,
+    days_of_week: Vec<bool>,
+    day_of_month_restricted: bool,
+    day_of_week_restricted: bool,
+}
+
+fn parse_cron_field(field: &str, min: u32, max: u32, name: &str) -> Result<Vec<bool>, String> {
+    let mut allowed = vec![false; max as usize + 1];
+
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => {
+                let step = step
+                    .parse::<u32>()
+                    .ok()
+                    .filter(|s| *s > 0)
+                    .ok_or_else(|| format!("Invalid step '{step}' in {name}"))?;
+                (range, step)
+            }
+            None => (part, 1),
+        };
+
+        let (start, end) = if range == "*" {
+            (min, max)
+        } else if let Some((start, end)) = range.split_once('-') {
+            let start = start
+                .parse::<u32>()
+                .map_err(|_| format!("Invalid {name} '{range}'"))?;
+            let end = end
+                .parse::<u32>()
+                .map_err(|_| format!("Invalid {name} '{range}'"))?;
+            (start, end)
+        } else {
+            let value = range
+                .parse::<u32>()
+                .map_err(|_| format!("Invalid {name} '{range}'"))?;
+            // "5/10" means "from 5 every 10"
+            if part.contains('/') {
+                (value, max)
+            } else {
+                (value, value)
+            }
+        };
+
+        if start < min || end > max || start > end {
+            return Err(format!("{name} '{range}' is outside {min}-{max}"));
+        }
+        for value in (start..=end).step_by(step as usize) {
+            allowed[value as usize] = true;
+        }
+    }
+
+    Ok(allowed)
+}
+
+impl CronSchedule {
+    pub fn parse(expression: &str) -> Result<Self, String> {
+        let fields: Vec<&str> = expression.split_whitespace().collect();
+        let [minute, hour, day_of_month, month, day_of_week] = fields[..] else {
+            return Err("Expected 5 fields: minute hour day month weekday".to_string());
+        };
+
+        let mut days_of_week = parse_cron_field(day_of_week, 0, 7, "weekday")?;
+        // Both 0 and 7 mean Sunday
+        if days_of_week[7] {
+            days_of_week[0] = true;
+        }
+
+        Ok(Self {
+            minutes: parse_cron_field(minute, 0, 59, "minute")?,
+            hours: parse_cron_field(hour, 0, 23, "hour")?,
+            days_of_month: parse_cron_field(day_of_month, 1, 31, "day")?,
+            months: parse_cron_field(month, 1, 12, "month")?,
+            days_of_week,
+            day_of_month_restricted: day_of_month != "*",
+            day_of_week_restricted: day_of_week != "*",
+        })
+    }
+
+    fn matches_day(&self, time: &DateTime<Local>) -> bool {
+        if !self.months[time.month() as usize] {
+            return false;
+        }
+
+        let dom = self.days_of_month[time.day() as usize];
+        let dow = self.days_of_week[time.weekday().num_days_from_sunday() as usize];
+        // Cron matches either day field when both are restricted
+        match (self.day_of_month_restricted, self.day_of_week_restricted) {
+            (true, true) => dom || dow,
+            (true, false) => dom,
+            (false, true) => dow,
+            (false, false) => true,
+        }
+    }
+
+    pub fn matches(&self, time: &DateTime<Local>) -> bool {
+        self.matches_day(time)
+            && self.hours[time.hour() as usize]
+            && self.minutes[time.minute() as usize]
+    }
+
+    /// Occurrences strictly after `after`, up to `limit` of them within `horizon`.
+    pub fn upcoming(
+        &self,
+        after: DateTime<Local>,
+        horizon: Duration,
+        limit: usize,
+    ) -> Vec<DateTime<Local>> {
+        let mut occurrences = Vec::new();
+        let end = after + horizon;
+        let Some(mut time) = after
+            .with_second(0)
+            .and_then(|t| t.with_nanosecond(0))
+            .map(|t| t + Duration::minutes(1))
+        else {
+            return occurrences;
+        };
+
+        while time <= end && occurrences.len() < limit {
+            if !self.matches_day(&time) {
+                let next_day = (time + Duration::days(1))
+                    .with_hour(0)
+                    .and_then(|t| t.with_minute(0));
+                match next_day {
+                    Some(next) if next > time => time = next,
+                    _ => time += Duration::hours(1),
+                }
+                continue;
+            }
+            if !self.hours[time.hour() as usize] {
+                time = time.with_minute(0).unwrap_or(time) + Duration::hours(1);
+                continue;
+            }
+            if self.minutes[time.minute() as usize] {
+                occurrences.push(time);
+            }
+            time += Duration::minutes(1);
+        }
+
+        occurrences
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub enum ScheduledTask {
+    Miner(MinerAction),
+    ApplyProfile(String),
+}
+
+impl ScheduledTask {
+    pub fn describe(&self) -> String {
+        match self {
+            ScheduledTask::Miner(action) => action.label().to_string(),
+            ScheduledTask::ApplyProfile(name) => format!("Apply profile '{name}'"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct ScheduledAction {
+    pub name: String,
+    pub enabled: bool,
+    pub cron: String,
+    pub task: ScheduledTask,
+    #[serde(default)]
+    pub targets: ProfileMatchRules,
+}
+
+impl Default for ScheduledAction {
+    fn default() -> Self {
+        Self {
+            name: "Peak curtailment".to_string(),
+            enabled: false,
+            cron: "0 17 * * 1-5".to_string(),
+            task: ScheduledTask::Miner(MinerAction::Pause),
+            targets: ProfileMatchRules::default(),
+        }
+    }
+}
+
+/// Enabled actions whose schedule matches the given minute.
+pub fn due_actions<'a>(
+    actions: &'a [ScheduledAction],
+    minute: &DateTime<Local>,
+) -> Vec<&'a ScheduledAction> {
+    actions
+        .iter()
+        .filter(|action| action.enabled)
+        .filter(|action| {
+            CronSchedule::parse(&action.cron)
+                .map(|schedule| schedule.matches(minute))
+                .unwrap_or(false)
+        })
+        .collect()
+}
+
+/// Actions due in any minute after `last_minute` up to and including
+/// `now_minute` (both minutes since the epoch), so minutes skipped while the
+/// UI was not updating still run. Each action runs at most once per catch-up.
+pub fn due_since(
+    actions: &[ScheduledAction],
+    last_minute: i64,
+    now_minute: i64,
+) -> Vec<&ScheduledAction> {
+    let first = (last_minute + 1).max(now_minute - MAX_CATCH_UP_MINUTES + 1);
+    let mut due: Vec<&ScheduledAction> = Vec::new();
+    for minute in first..=now_minute {
+        let Some(at) = Local.timestamp_opt(minute * 60, 0).single() else {
+            continue;
+        };
+        for action in due_actions(actions, &at) {
+            if !due.iter().any(|d| std::ptr::eq(*d, action)) {
+                due.push(action);
+            }
+        }
+    }
+    due
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ScheduleRunRecord {
+    pub at: i64,
+    pub action_name: String,
+    pub task: String,
+    pub targets: usize,
+    pub failures: Vec<String>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct ScheduleLog {
+    pub runs: Vec<ScheduleRunRecord>,
+}
+
+impl ScheduleLog {
+    pub fn load() -> Self {
+        crate::config::app_data_path(SCHEDULE_LOG_FILE)
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+            .unwrap_or_default()
+    }
+
+    fn save(&self) {
+        if let Some(path) = crate::config::app_data_path(SCHEDULE_LOG_FILE) {
+            if let Ok(json) = serde_json::to_string_pretty(self) {
+                if let Err(e) = fs::write(path, json) {
+                    eprintln!("✗ Failed to save schedule log: {e}");
+                }
+            }
+        }
+    }
+
+    pub fn record(&mut self, run: ScheduleRunRecord) {
+        self.runs.push(run);
+        if self.runs.len() > MAX_LOG_ENTRIES {
+            self.runs.drain(0..self.runs.len() - MAX_LOG_ENTRIES);
+        }
+        self.save();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{due_since, CronSchedule, ScheduledAction};
+    use chrono::{Duration, Local, TimeZone};
+
+    #[test]
+    fn cron_parses_lists_ranges_and_steps() {
+        let schedule = CronSchedule::parse("*/15 9-17 * * 1-5").unwrap();
+        // 2026-10-19 is a Monday
+        let monday = Local.with_ymd_and_hms(2026, 10, 19, 9, 30, 0).unwrap();
+        let saturday = Local.with_ymd_and_hms(2026, 10, 24, 9, 30, 0).unwrap();
+        assert!(schedule.matches(&monday));
+        assert!(!schedule.matches(&saturday));
+        assert!(!schedule.matches(&(monday + Duration::minutes(1))));
+
+        assert!(CronSchedule::parse("0 17 * *").is_err());
+        assert!(CronSchedule::parse("61 * * * *").is_err());
+    }
+
+    #[test]
+    fn cron_upcoming_skips_to_next_matching_day() {
+        let schedule = CronSchedule::parse("0 17 * * 1-5").unwrap();
+        let friday_evening = Local.with_ymd_and_hms(2026, 10, 23, 18, 0, 0).unwrap();
+        let next = schedule.upcoming(friday_evening, Duration::days(7), 2);
+        assert_eq!(
+            next,
+            vec![
+                Local.with_ymd_and_hms(2026, 10, 26, 17, 0, 0).unwrap(),
+                Local.with_ymd_and_hms(2026, 10, 27, 17, 0, 0).unwrap(),
+            ]
+        );
+    }
+
+    #[test]
+    fn due_since_catches_up_skipped_minutes_once() {
+        let action = |name: &str, cron: &str| ScheduledAction {
+            name: name.to_string(),
+            enabled: true,
+            cron: cron.to_string(),
+            ..Default::default()
+        };
+        let actions = vec![action("curtail", "0 17 * * *"), action("tick", "* * * * *")];
+        let minute = |h, m| {
+            Local
+                .with_ymd_and_hms(2026, 10, 19, h, m, 0)
+                .unwrap()
+                .timestamp()
+                / 60
+        };
+
+        // The UI stalled from 16:58 to 17:03
+        let due = due_since(&actions, minute(16, 58), minute(17, 3));
+        let names: Vec<&str> = due.iter().map(|a| a.name.as_str()).collect();
+        assert_eq!(names, vec!["tick", "curtail"]);
+        assert!(due_since(&actions, minute(17, 1), minute(17, 3))
+            .iter()
+            .all(|a| a.name != "curtail"));
+    }
+}
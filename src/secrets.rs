@@ -0,0 +1,447 @@
+use crate::models::MinerOptionSettings;
+use aes_gcm::aead::rand_core::RngCore;
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+
+const SECRETS_FILE: &str = "secrets.json";
+const SECRET_REF_PREFIX: &str = "secret:";
+const KDF_ROUNDS: u32 = 600_000;
+const MIN_PASSPHRASE_LEN: usize = 8;
+/// Encrypted with the derived key so a wrong passphrase is caught on unlock.
+const VERIFIER: &str = "asic-miner-scanner";
+
+/// How a value written to the config points at a stored secret.
+pub fn secret_ref(id: &str) -> String {
+    format!("{SECRET_REF_PREFIX}{id}")
+}
+
+pub fn parse_secret_ref(value: &str) -> Option<&str> {
+    value.strip_prefix(SECRET_REF_PREFIX)
+}
+
+pub fn is_secret_ref(value: &str) -> bool {
+    parse_secret_ref(value).is_some()
+}
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub enum SecretKind {
+    PoolPassword,
+    MinerCredential,
+    WebhookToken,
+}
+
+impl SecretKind {
+    pub fn label(self) -> &'static str {
+        match self {
+            SecretKind::PoolPassword => "Pool password",
+            SecretKind::MinerCredential => "Miner credential",
+            SecretKind::WebhookToken => "Webhook token",
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct Sealed {
+    nonce: String,
+    ciphertext: String,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SecretRecord {
+    pub kind: SecretKind,
+    /// Where the secret is used, e.g. "Global · group 1 pool 2".
+    pub label: String,
+    pub updated_at: i64,
+    sealed: Sealed,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+struct Vault {
+    salt: String,
+    kdf_rounds: u32,
+    verifier: Option<Sealed>,
+    secrets: BTreeMap<String, SecretRecord>,
+}
+
+/// Secrets encrypted with AES-256-GCM under a key derived from a master
+/// passphrase (PBKDF2-SHA256). Only the ciphertext is written to disk; the
+/// key lives in memory while the store is unlocked.
+#[derive(Default)]
+pub struct SecretStore {
+    vault: Vault,
+    key: Option<[u8; 32]>,
+}
+
+fn derive_key(passphrase: &str, salt: &[u8], rounds: u32) -> [u8; 32] {
+    let mut key = [0u8; 32];
+    pbkdf2::pbkdf2_hmac::<sha2::Sha256>(passphrase.as_bytes(), salt, rounds, &mut key);
+    key
+}
+
+fn seal(key: &[u8; 32], plaintext: &str) -> Sealed {
+    let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key));
+    let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+    let ciphertext = cipher
+        .encrypt(&nonce, plaintext.as_bytes())
+        .expect("AES-GCM encryption does not fail for in-memory buffers");
+    Sealed {
+        nonce: BASE64.encode(nonce),
+        ciphertext: BASE64.encode(ciphertext),
+    }
+}
+
+fn open(key: &[u8; 32], sealed: &Sealed) -> Option<String> {
+    let nonce = BASE64.decode(&sealed.nonce).ok()?;
+    let ciphertext = BASE64.decode(&sealed.ciphertext).ok()?;
+    if nonce.len() != 12 {
+        return None;
+    }
+    let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key));
+    let plaintext = cipher
+        .decrypt(Nonce::from_slice(&nonce), ciphertext.as_ref())
+        .ok()?;
+    String::from_utf8(plaintext).ok()
+}
+
+fn check_passphrase(passphrase: &str) -> Result<(), String> {
+    if passphrase.chars().count() < MIN_PASSPHRASE_LEN {
+        return Err(format!(
+            "Passphrase must be at least {MIN_PASSPHRASE_LEN} characters"
+        ));
+    }
+    Ok(())
+}
+
+fn random_bytes<const N: usize>() -> [u8; N] {
+    let mut bytes = [0u8; N];
+    OsRng.fill_bytes(&mut bytes);
+    bytes
+}
+
+impl SecretStore {
+    pub fn load() -> Self {
+        let vault = crate::config::app_data_path(SECRETS_FILE)
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+            .unwrap_or_default();
+        Self { vault, key: None }
+    }
+
+    pub fn save(&self) {
+        if let Some(path) = crate::config::app_data_path(SECRETS_FILE) {
+            if let Ok(json) = serde_json::to_string_pretty(&self.vault) {
+                if let Err(e) = fs::write(path, json) {
+                    eprintln!("✗ Failed to save secret store: {e}");
+                }
+            }
+        }
+    }
+
+    /// A master passphrase has been set.
+    pub fn is_initialized(&self) -> bool {
+        self.vault.verifier.is_some()
+    }
+
+    pub fn is_unlocked(&self) -> bool {
+        self.key.is_some()
+    }
+
+    pub fn records(&self) -> impl Iterator<Item = (&String, &SecretRecord)> {
+        self.vault.secrets.iter()
+    }
+
+    pub fn create(&mut self, passphrase: &str) -> Result<(), String> {
+        self.create_with_rounds(passphrase, KDF_ROUNDS)
+    }
+
+    fn create_with_rounds(&mut self, passphrase: &str, rounds: u32) -> Result<(), String> {
+        if self.is_initialized() {
+            return Err("A master passphrase is already set".to_string());
+        }
+        check_passphrase(passphrase)?;
+        let salt = random_bytes::<16>();
+        let key = derive_key(passphrase, &salt, rounds);
+        self.vault = Vault {
+            salt: BASE64.encode(salt),
+            kdf_rounds: rounds,
+            verifier: Some(seal(&key, VERIFIER)),
+            secrets: BTreeMap::new(),
+        };
+        self.key = Some(key);
+        Ok(())
+    }
+
+    pub fn unlock(&mut self, passphrase: &str) -> Result<(), String> {
+        let verifier = self
+            .vault
+            .verifier
+            .as_ref()
+            .ok_or("No master passphrase has been set")?;
+        let salt = BASE64
+            .decode(&self.vault.salt)
+            .map_err(|e| format!("Secret store is damaged: {e}"))?;
+        let key = derive_key(passphrase, &salt, self.vault.kdf_rounds);
+        if open(&key, verifier).as_deref() != Some(VERIFIER) {
+            return Err("Wrong passphrase".to_string());
+        }
+        self.key = Some(key);
+        Ok(())
+    }
+
+    pub fn lock(&mut self) {
+        self.key = None;
+    }
+
+    /// Re-encrypts every secret under a new passphrase and salt.
+    pub fn change_passphrase(&mut self, passphrase: &str) -> Result<(), String> {
+        let key = self.key.ok_or("Unlock the secret store first")?;
+        check_passphrase(passphrase)?;
+        let mut secrets = Vec::new();
+        for (id, record) in &self.vault.secrets {
+            let value =
+                open(&key, &record.sealed).ok_or(format!("Could not decrypt {}", record.label))?;
+            secrets.push((id.clone(), record.clone(), value));
+        }
+
+        let rounds = self.vault.kdf_rounds;
+        self.vault.verifier = None;
+        self.create_with_rounds(passphrase, rounds)?;
+        let key = self.key.expect("create sets the key");
+        for (id, mut record, value) in secrets {
+            record.sealed = seal(&key, &value);
+            self.vault.secrets.insert(id, record);
+        }
+        Ok(())
+    }
+
+    pub fn get(&self, id: &str) -> Option<String> {
+        let key = self.key.as_ref()?;
+        open(key, &self.vault.secrets.get(id)?.sealed)
+    }
+
+    /// Stores `value` under the secret of this kind and label, creating it if
+    /// needed, and returns its id. Unchanged values are not re-encrypted.
+    pub fn put(&mut self, kind: SecretKind, label: &str, value: &str) -> Result<String, String> {
+        let key = self.key.ok_or("Unlock the secret store first")?;
+        let existing = self
+            .vault
+            .secrets
+            .iter()
+            .find(|(_, record)| record.kind == kind && record.label == label)
+            .map(|(id, _)| id.clone());
+        if let Some(id) = &existing {
+            if self.get(id).as_deref() == Some(value) {
+                return Ok(id.clone());
+            }
+        }
+
+        let id = existing.unwrap_or_else(|| {
+            random_bytes::<8>()
+                .iter()
+                .map(|b| format!("{b:02x}"))
+                .collect()
+        });
+        self.vault.secrets.insert(
+            id.clone(),
+            SecretRecord {
+                kind,
+                label: label.to_string(),
+                updated_at: Local::now().timestamp(),
+                sealed: seal(&key, value),
+            },
+        );
+        Ok(id)
+    }
+
+    /// Drops secrets of `kind` that nothing references any more.
+    pub fn retain_referenced(&mut self, kind: SecretKind, referenced: &HashSet<String>) {
+        self.vault
+            .secrets
+            .retain(|id, record| record.kind != kind || referenced.contains(id));
+    }
+}
+
+/// Swaps a plain value for a reference into the store. Returns the id it
+/// now references, or `None` when there is nothing to keep.
+///
+/// Without a master passphrase the value stays in plain text. While the store
+/// is locked a new value cannot be encrypted and is cleared.
+pub fn seal_value(
+    value: &mut String,
+    kind: SecretKind,
+    label: &str,
+    store: &mut SecretStore,
+) -> Option<String> {
+    if let Some(id) = parse_secret_ref(value) {
+        return Some(id.to_string());
+    }
+    if value.is_empty() || !store.is_initialized() {
+        return None;
+    }
+    match store.put(kind, label, value) {
+        Ok(id) => {
+            *value = secret_ref(&id);
+            Some(id)
+        }
+        Err(err) => {
+            eprintln!("✗ {} for {label} not saved: {err}", kind.label());
+            value.clear();
+            None
+        }
+    }
+}
+
+/// Swaps a reference back to the value it points at. Returns false when the
+/// store is locked or the secret is gone.
+pub fn reveal_value(value: &mut String, store: &SecretStore) -> bool {
+    let Some(id) = parse_secret_ref(value) else {
+        return true;
+    };
+    match store.get(id) {
+        Some(secret) => {
+            *value = secret;
+            true
+        }
+        None => false,
+    }
+}
+
+/// The stratum placeholder password, not worth encrypting.
+fn is_placeholder_password(password: &str) -> bool {
+    password.is_empty() || password == "x"
+}
+
+/// Replaces pool passwords with references into the store before the
+/// settings are written to the config. `scope` names the settings in secret
+/// labels, e.g. "Global". Returns the ids referenced.
+pub fn seal_pool_passwords(
+    settings: &mut MinerOptionSettings,
+    scope: &str,
+    store: &mut SecretStore,
+) -> HashSet<String> {
+    let mut referenced = HashSet::new();
+    for (group_idx, group) in settings.pool_groups.iter_mut().enumerate() {
+        for (pool_idx, pool) in group.pools.iter_mut().enumerate() {
+            if is_placeholder_password(&pool.password) {
+                continue;
+            }
+            let label = format!("{scope} · group {} pool {}", group_idx + 1, pool_idx + 1);
+            referenced.extend(seal_value(
+                &mut pool.password,
+                SecretKind::PoolPassword,
+                &label,
+                store,
+            ));
+        }
+    }
+    referenced
+}
+
+/// Whether any pool password would be written out in plain text.
+pub fn has_plain_pool_password(settings: &MinerOptionSettings) -> bool {
+    settings
+        .pool_groups
+        .iter()
+        .flat_map(|group| group.pools.iter())
+        .any(|pool| !is_placeholder_password(&pool.password) && !is_secret_ref(&pool.password))
+}
+
+/// Blanks pool passwords that are neither the placeholder nor references
+/// into the store. Returns whether any was blanked.
+pub fn clear_plain_pool_passwords(settings: &mut MinerOptionSettings) -> bool {
+    let mut cleared = false;
+    for pool in settings
+        .pool_groups
+        .iter_mut()
+        .flat_map(|group| group.pools.iter_mut())
+    {
+        if !is_placeholder_password(&pool.password) && !is_secret_ref(&pool.password) {
+            pool.password.clear();
+            cleared = true;
+        }
+    }
+    cleared
+}
+
+/// Swaps secret references back to the passwords they point at. Returns how
+/// many could not be resolved because the store is locked or the secret is gone.
+pub fn reveal_pool_passwords(settings: &mut MinerOptionSettings, store: &SecretStore) -> usize {
+    settings
+        .pool_groups
+        .iter_mut()
+        .flat_map(|group| group.pools.iter_mut())
+        .map(|pool| reveal_value(&mut pool.password, store))
+        .filter(|revealed| !revealed)
+        .count()
+}
+
+/// What the operator typed into the secrets window.
+#[derive(Clone, Debug, Default)]
+pub struct SecretsForm {
+    pub passphrase: String,
+    pub confirm: String,
+    pub error: Option<String>,
+}
+
+impl SecretsForm {
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum SecretsRequest {
+    Create(String),
+    Unlock(String),
+    Lock,
+    ChangePassphrase(String),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{reveal_pool_passwords, seal_pool_passwords, SecretStore, Vault};
+    use crate::models::{MinerOptionSettings, PoolInput};
+
+    #[test]
+    fn pool_passwords_round_trip_through_the_encrypted_store() {
+        let mut store = SecretStore::default();
+        store.create_with_rounds("correct horse", 10).unwrap();
+
+        let mut settings = MinerOptionSettings::default();
+        settings.pool_groups[0].pools = vec![
+            PoolInput {
+                url: "stratum+tcp://pool.example:3333".to_string(),
+                username: "acct.worker".to_string(),
+                password: "hunter2".to_string(),
+            },
+            PoolInput::default(),
+        ];
+
+        let referenced = seal_pool_passwords(&mut settings, "Global", &mut store);
+        assert_eq!(referenced.len(), 1);
+        assert!(settings.pool_groups[0].pools[0]
+            .password
+            .starts_with("secret:"));
+        assert_eq!(settings.pool_groups[0].pools[1].password, "x");
+
+        // Only ciphertext reaches disk
+        let json = serde_json::to_string(&store.vault).unwrap();
+        assert!(!json.contains("hunter2"));
+
+        let mut reopened = SecretStore {
+            vault: serde_json::from_str::<Vault>(&json).unwrap(),
+            key: None,
+        };
+        assert_eq!(reveal_pool_passwords(&mut settings.clone(), &reopened), 1);
+        assert!(reopened.unlock("wrong horse").is_err());
+        reopened.unlock("correct horse").unwrap();
+        assert_eq!(reveal_pool_passwords(&mut settings, &reopened), 0);
+        assert_eq!(settings.pool_groups[0].pools[0].password, "hunter2");
+    }
+}
@@ -0,0 +1,327 @@
+use crate::models::MinerOptionSettings;
+use crate::secrets::{self, SecretStore};
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::sync::{Arc, Mutex};
+
+const SNAPSHOTS_FILE: &str = "option_snapshots.json";
+const MAX_STORED_JOBS: usize = 200;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ConfigSnapshot {
+    pub miner_identity: String,
+    pub ip: String,
+    pub taken_at: i64,
+    pub settings: MinerOptionSettings,
+    /// Pool passwords were blanked on disk because they could not be sealed.
+    #[serde(default)]
+    pub passwords_withheld: bool,
+}
+
+impl ConfigSnapshot {
+    /// Rolling back would write blank pool passwords.
+    pub fn pool_passwords_lost(&self) -> bool {
+        self.settings.apply_pool_config && self.passwords_withheld
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ApplyJobRecord {
+    pub id: String,
+    pub title: String,
+    pub started_at: i64,
+    pub snapshots: Vec<ConfigSnapshot>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct SnapshotStore {
+    pub jobs: Vec<ApplyJobRecord>,
+    /// Snapshots recorded since the file was last written.
+    #[serde(skip)]
+    dirty: bool,
+    /// Snapshots holding pool passwords not yet sealed into the secret store.
+    #[serde(skip)]
+    unsealed: bool,
+}
+
+impl SnapshotStore {
+    pub fn load() -> Self {
+        crate::config::app_data_path(SNAPSHOTS_FILE)
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|contents| serde_json::from_str::<serde_json::Value>(&contents).ok())
+            .and_then(|mut value| {
+                let jobs = value.get_mut("jobs").and_then(|jobs| jobs.as_array_mut());
+                for job in jobs.into_iter().flatten() {
+                    let snapshots = job.get_mut("snapshots").and_then(|s| s.as_array_mut());
+                    for snapshot in snapshots.into_iter().flatten() {
+                        if let Some(settings) = snapshot.get_mut("settings") {
+                            MinerOptionSettings::upgrade_legacy_json(settings);
+                        }
+                    }
+                }
+                serde_json::from_value(value).ok()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Pool passwords only reach the file sealed; one still in plain text is
+    /// written blank.
+    fn save(&self) {
+        let stored = self.stored_copy();
+        if let Some(path) = crate::config::app_data_path(SNAPSHOTS_FILE) {
+            if let Ok(json) = serde_json::to_string_pretty(&stored) {
+                if let Err(e) = fs::write(path, json) {
+                    eprintln!("✗ Failed to save option snapshots: {e}");
+                }
+            }
+        }
+    }
+
+    fn stored_copy(&self) -> SnapshotStore {
+        let mut stored = SnapshotStore {
+            jobs: self.jobs.clone(),
+            ..Default::default()
+        };
+        for snapshot in stored
+            .jobs
+            .iter_mut()
+            .flat_map(|job| job.snapshots.iter_mut())
+        {
+            snapshot.passwords_withheld |=
+                secrets::clear_plain_pool_passwords(&mut snapshot.settings);
+        }
+        stored
+    }
+
+    fn start_job(&mut self, id: &str, title: &str) {
+        if self.jobs.iter().any(|job| job.id == id) {
+            return;
+        }
+
+        self.jobs.push(ApplyJobRecord {
+            id: id.to_string(),
+            title: title.to_string(),
+            started_at: Local::now().timestamp(),
+            snapshots: Vec::new(),
+        });
+
+        if self.jobs.len() > MAX_STORED_JOBS {
+            self.jobs.drain(0..self.jobs.len() - MAX_STORED_JOBS);
+        }
+        self.dirty = true;
+    }
+
+    fn add_snapshot(&mut self, job_id: &str, snapshot: ConfigSnapshot) {
+        if let Some(job) = self.jobs.iter_mut().find(|job| job.id == job_id) {
+            self.unsealed |= secrets::has_plain_pool_password(&snapshot.settings);
+            job.snapshots.push(snapshot);
+            self.dirty = true;
+        }
+    }
+
+    pub fn has_unsealed_passwords(&self) -> bool {
+        self.unsealed
+    }
+
+    /// Moves plain pool passwords into the secret store. Returns the ids the
+    /// snapshots reference, so the store keeps them; nothing while it is locked.
+    pub fn seal_passwords(&mut self, store: &mut SecretStore) -> HashSet<String> {
+        let mut referenced = HashSet::new();
+        if !store.is_unlocked() {
+            return referenced;
+        }
+        for job in &mut self.jobs {
+            for snapshot in &mut job.snapshots {
+                self.dirty |= secrets::has_plain_pool_password(&snapshot.settings);
+                referenced.extend(secrets::seal_pool_passwords(
+                    &mut snapshot.settings,
+                    &format!("Snapshot {} {}", job.id, snapshot.ip),
+                    store,
+                ));
+            }
+        }
+        self.unsealed = false;
+        self.save_if_dirty();
+        referenced
+    }
+
+    fn save_if_dirty(&mut self) {
+        if self.dirty {
+            self.save();
+            self.dirty = false;
+        }
+    }
+
+    /// Most recent snapshot taken for a miner, across all jobs.
+    pub fn latest_for(&self, miner_identity: &str) -> Option<&ConfigSnapshot> {
+        self.jobs
+            .iter()
+            .rev()
+            .flat_map(|job| job.snapshots.iter())
+            .find(|snapshot| snapshot.miner_identity == miner_identity)
+    }
+}
+
+/// Writes the store once the last handle on a job's recorder is gone.
+struct FlushOnDrop(Arc<Mutex<SnapshotStore>>);
+
+// Jobs flush explicitly when their writes finish; this only catches a job
+// dropped early, so it must never block or panic inside drop.
+impl Drop for FlushOnDrop {
+    fn drop(&mut self) {
+        if let Ok(mut store) = self.0.try_lock() {
+            store.save_if_dirty();
+        }
+    }
+}
+
+/// Records the pre-apply state of every miner touched by one apply job. The
+/// snapshots are kept in memory and written to disk in one go when the job's
+/// writes are done, instead of rewriting the file per miner.
+#[derive(Clone)]
+pub struct SnapshotRecorder {
+    store: Arc<Mutex<SnapshotStore>>,
+    job_id: String,
+    identities: HashMap<String, String>,
+    _flush: Arc<FlushOnDrop>,
+}
+
+impl SnapshotRecorder {
+    pub fn start_job(
+        store: &Arc<Mutex<SnapshotStore>>,
+        title: &str,
+        identities: HashMap<String, String>,
+    ) -> Self {
+        let job_id = format!("{}-{}", Local::now().format("%Y%m%d-%H%M%S%.3f"), title);
+        store.lock().unwrap().start_job(&job_id, title);
+
+        Self {
+            store: Arc::clone(store),
+            job_id,
+            identities,
+            _flush: Arc::new(FlushOnDrop(Arc::clone(store))),
+        }
+    }
+
+    /// Writes what has been recorded so far, for jobs that stay open across
+    /// several batches.
+    pub fn flush(&self) {
+        self.store.lock().unwrap().save_if_dirty();
+    }
+
+    pub fn record(&self, ip: &str, current: &MinerOptionSettings, requested: &MinerOptionSettings) {
+        let snapshot = ConfigSnapshot {
+            miner_identity: self
+                .identities
+                .get(ip)
+                .cloned()
+                .unwrap_or_else(|| ip.to_string()),
+            ip: ip.to_string(),
+            taken_at: Local::now().timestamp(),
+            settings: rollback_settings(current, requested),
+            passwords_withheld: false,
+        };
+
+        self.store
+            .lock()
+            .unwrap()
+            .add_snapshot(&self.job_id, snapshot);
+    }
+}
+
+/// Restricts a snapshot to the sections the job is about to write, so a rollback
+/// does not touch settings the job never changed.
+pub fn rollback_settings(
+    current: &MinerOptionSettings,
+    requested: &MinerOptionSettings,
+) -> MinerOptionSettings {
+    let mut settings = current.clone();
+    settings.apply_power_limit &= requested.apply_power_limit;
+    settings.apply_fan_config &= requested.apply_fan_config;
+    settings.apply_tuning_config &= requested.apply_tuning_config;
+    settings.apply_scaling_config &= requested.apply_scaling_config;
+    settings.apply_pool_config &= requested.apply_pool_config;
+    if !settings.apply_pool_config {
+        secrets::clear_plain_pool_passwords(&mut settings);
+    }
+    settings
+}
+
+pub fn format_timestamp(timestamp: i64) -> String {
+    use chrono::TimeZone;
+
+    Local
+        .timestamp_opt(timestamp, 0)
+        .single()
+        .map(|dt| dt.format("%Y-%m-%d %H:%M:%S").to_string())
+        .unwrap_or_else(|| "N/A".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{rollback_settings, ApplyJobRecord, ConfigSnapshot, SnapshotStore};
+    use crate::models::MinerOptionSettings;
+
+    #[test]
+    fn blanked_pool_passwords_block_a_pool_rollback() {
+        let mut settings = MinerOptionSettings {
+            apply_pool_config: true,
+            ..Default::default()
+        };
+        settings.pool_groups[0].pools[0].password = "hunter2".to_string();
+        let store = SnapshotStore {
+            jobs: vec![ApplyJobRecord {
+                id: "job".to_string(),
+                title: "Apply".to_string(),
+                started_at: 0,
+                snapshots: vec![ConfigSnapshot {
+                    miner_identity: "10.0.0.1".to_string(),
+                    ip: "10.0.0.1".to_string(),
+                    taken_at: 0,
+                    settings,
+                    passwords_withheld: false,
+                }],
+            }],
+            ..Default::default()
+        };
+        assert!(!store.jobs[0].snapshots[0].pool_passwords_lost());
+
+        // What a restart reads back from disk
+        let json = serde_json::to_string(&store.stored_copy()).unwrap();
+        assert!(!json.contains("hunter2"));
+        let reloaded: SnapshotStore = serde_json::from_str(&json).unwrap();
+        let snapshot = &reloaded.jobs[0].snapshots[0];
+        assert!(snapshot.settings.pool_groups[0].pools[0]
+            .password
+            .is_empty());
+        assert!(snapshot.pool_passwords_lost());
+    }
+
+    #[test]
+    fn rollback_settings_only_keeps_sections_being_written() {
+        let mut current = MinerOptionSettings {
+            apply_power_limit: true,
+            apply_fan_config: true,
+            apply_pool_config: true,
+            power_limit_watts: 3100.0,
+            ..Default::default()
+        };
+        current.pool_groups[0].pools[0].password = "hunter2".to_string();
+        let requested = MinerOptionSettings {
+            apply_power_limit: true,
+            apply_scaling_config: true,
+            ..Default::default()
+        };
+
+        let rollback = rollback_settings(&current, &requested);
+        assert!(rollback.apply_power_limit);
+        assert_eq!(rollback.power_limit_watts, 3100.0);
+        assert!(!rollback.apply_fan_config);
+        assert!(!rollback.apply_pool_config);
+        assert!(!rollback.apply_scaling_config);
+        // A password the rollback will never write is not kept around
+        assert!(rollback.pool_groups[0].pools[0].password.is_empty());
+    }
+}
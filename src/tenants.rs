@@ -0,0 +1,295 @@
+use crate::energy::EnergyLedger;
+use crate::export::csv_field;
+use crate::models::{MinerInfo, MinerLocation, ProfileMatchRules, SavedRange};
+use crate::profiles;
+use chrono::{Datelike, Days, Local, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+pub const UNASSIGNED: &str = "(unassigned)";
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct Tenant {
+    pub name: String,
+    /// Claim every miner matching `rules`. Off means only miners tagged with
+    /// this tenant's name belong to it.
+    #[serde(default)]
+    pub assign_by_rules: bool,
+    #[serde(default)]
+    pub rules: ProfileMatchRules,
+}
+
+fn tagged_tenant(identity: &str, locations: &HashMap<String, MinerLocation>) -> Option<String> {
+    locations
+        .get(identity)
+        .map(|location| location.tenant.trim().to_string())
+        .filter(|tenant| !tenant.is_empty())
+}
+
+/// A miner's own tenant tag wins; otherwise the first tenant, in list order,
+/// whose rules match.
+pub fn tenant_for(
+    miner: &MinerInfo,
+    tenants: &[Tenant],
+    locations: &HashMap<String, MinerLocation>,
+    saved_ranges: &[SavedRange],
+) -> Option<String> {
+    tagged_tenant(&miner.identity(), locations).or_else(|| {
+        tenants
+            .iter()
+            .filter(|tenant| tenant.assign_by_rules)
+            .find(|tenant| profiles::rules_match(&tenant.rules, miner, saved_ranges))
+            .map(|tenant| tenant.name.clone())
+    })
+}
+
+/// Tenant of every identity in a persisted per-miner record, such as the
+/// energy ledger. Miners not in the current scan can only be placed by their tag.
+pub fn tenants_by_identity<'a>(
+    identities: impl IntoIterator<Item = &'a String>,
+    miners: &[MinerInfo],
+    tenants: &[Tenant],
+    locations: &HashMap<String, MinerLocation>,
+    saved_ranges: &[SavedRange],
+) -> HashMap<String, String> {
+    let scanned: HashMap<String, &MinerInfo> = miners
+        .iter()
+        .map(|miner| (miner.identity(), miner))
+        .collect();
+
+    identities
+        .into_iter()
+        .map(|identity| {
+            let tenant = match scanned.get(identity) {
+                Some(miner) => tenant_for(miner, tenants, locations, saved_ranges),
+                None => tagged_tenant(identity, locations),
+            };
+            (
+                identity.clone(),
+                tenant.unwrap_or_else(|| UNASSIGNED.to_string()),
+            )
+        })
+        .collect()
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReportFormat {
+    Csv,
+    Html,
+}
+
+/// Inclusive report period as `YYYY-MM-DD` dates.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReportPeriod {
+    pub from: String,
+    pub to: String,
+}
+
+impl ReportPeriod {
+    fn from_dates(from: NaiveDate, to: NaiveDate) -> Self {
+        Self {
+            from: from.format("%Y-%m-%d").to_string(),
+            to: to.format("%Y-%m-%d").to_string(),
+        }
+    }
+
+    pub fn this_month() -> Self {
+        let today = Local::now().date_naive();
+        Self::from_dates(today.with_day(1).unwrap_or(today), today)
+    }
+
+    pub fn last_month() -> Self {
+        let today = Local::now().date_naive();
+        let end = today
+            .with_day(1)
+            .and_then(|d| d.pred_opt())
+            .unwrap_or(today);
+        Self::from_dates(end.with_day(1).unwrap_or(end), end)
+    }
+
+    pub fn last_days(days: u64) -> Self {
+        let today = Local::now().date_naive();
+        let from = today
+            .checked_sub_days(Days::new(days.saturating_sub(1)))
+            .unwrap_or(today);
+        Self::from_dates(from, today)
+    }
+
+    pub fn validation_message(&self) -> Option<String> {
+        let parse = |value: &str| NaiveDate::parse_from_str(value.trim(), "%Y-%m-%d");
+        match (parse(&self.from), parse(&self.to)) {
+            (Ok(from), Ok(to)) if from <= to => None,
+            (Ok(_), Ok(_)) => Some("Period ends before it starts".to_string()),
+            _ => Some("Dates must be YYYY-MM-DD".to_string()),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TenantReportRow {
+    pub tenant: String,
+    pub miners: usize,
+    pub energy_kwh: f64,
+    /// Sum of each miner's average hashrate while it was being sampled.
+    pub average_hashrate_th: f64,
+    pub hashing_secs: i64,
+    pub tracked_secs: i64,
+}
+
+impl TenantReportRow {
+    pub fn uptime_percent(&self) -> f64 {
+        if self.tracked_secs == 0 {
+            0.0
+        } else {
+            self.hashing_secs as f64 / self.tracked_secs as f64 * 100.0
+        }
+    }
+}
+
+/// Uptime, hashrate and energy per tenant for the inclusive `YYYY-MM-DD` period.
+pub fn tenant_report(
+    ledger: &EnergyLedger,
+    tenant_of: &HashMap<String, String>,
+    from: &str,
+    to: &str,
+) -> Vec<TenantReportRow> {
+    let mut rows: BTreeMap<String, TenantReportRow> = BTreeMap::new();
+
+    for (identity, entry) in &ledger.miners {
+        let totals = entry.period_totals(from, to);
+        if totals.tracked_secs() == 0 {
+            continue;
+        }
+        let tenant = tenant_of
+            .get(identity)
+            .cloned()
+            .unwrap_or_else(|| UNASSIGNED.to_string());
+        let row = rows
+            .entry(tenant.clone())
+            .or_insert_with(|| TenantReportRow {
+                tenant,
+                ..Default::default()
+            });
+        row.miners += 1;
+        row.energy_kwh += totals.wh / 1000.0;
+        if totals.covered_secs > 0 {
+            row.average_hashrate_th += totals.th_hours * 3600.0 / totals.covered_secs as f64;
+        }
+        row.hashing_secs += totals.hashing_secs;
+        row.tracked_secs += totals.tracked_secs();
+    }
+
+    rows.into_values().collect()
+}
+
+pub fn tenant_report_csv(from: &str, to: &str, rows: &[TenantReportRow]) -> String {
+    let mut csv =
+        String::from("From,To,Tenant,Miners,Uptime (%),Average Hashrate (TH/s),Energy (kWh)\n");
+    for row in rows {
+        csv.push_str(&format!(
+            "{},{},{},{},{:.2},{:.2},{:.3}\n",
+            from,
+            to,
+            csv_field(&row.tenant),
+            row.miners,
+            row.uptime_percent(),
+            row.average_hashrate_th,
+            row.energy_kwh
+        ));
+    }
+    csv
+}
+
+fn escape_html(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Self-contained page that prints cleanly from a browser.
+pub fn tenant_report_html(from: &str, to: &str, rows: &[TenantReportRow]) -> String {
+    let mut body = String::new();
+    for row in rows {
+        body.push_str(&format!(
+            "<tr><td>{}</td><td>{}</td><td>{:.2}%</td><td>{:.2}</td><td>{:.1}</td></tr>\n",
+            escape_html(&row.tenant),
+            row.miners,
+            row.uptime_percent(),
+            row.average_hashrate_th,
+            row.energy_kwh
+        ));
+    }
+
+    format!(
+        r#"<!DOCTYPE html>
+<html>
+<head>
+<meta charset="utf-8">
+<title>Tenant report {from} to {to}</title>
+<style>
+body {{ font-family: sans-serif; margin: 2em; }}
+table {{ border-collapse: collapse; }}
+th, td {{ border: 1px solid #999; padding: 4px 10px; text-align: right; }}
+th:first-child, td:first-child {{ text-align: left; }}
+</style>
+</head>
+<body>
+<h1>Tenant report</h1>
+<p>{from} to {to}</p>
+<table>
+<tr><th>Tenant</th><th>Miners</th><th>Uptime</th><th>Avg TH/s</th><th>kWh</th></tr>
+{body}</table>
+</body>
+</html>
+"#
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{tenant_for, Tenant};
+    use crate::models::{MinerInfo, MinerLocation, ProfileMatchRules, SavedRange};
+    use std::collections::HashMap;
+
+    #[test]
+    fn tag_overrides_rules_and_rules_follow_list_order() {
+        let ranges = vec![SavedRange {
+            name: "Hall B".to_string(),
+            range: "10.0.2.1-254".to_string(),
+        }];
+        let tenants = vec![
+            Tenant {
+                name: "Acme".to_string(),
+                assign_by_rules: true,
+                rules: ProfileMatchRules {
+                    saved_range: "Hall B".to_string(),
+                    ..Default::default()
+                },
+            },
+            Tenant {
+                name: "Globex".to_string(),
+                assign_by_rules: false,
+                rules: ProfileMatchRules::default(),
+            },
+        ];
+        let miner = |ip: &str| MinerInfo {
+            ip: ip.to_string(),
+            ..Default::default()
+        };
+        let locations = HashMap::from([(
+            "10.0.2.9".to_string(),
+            MinerLocation {
+                tenant: "Globex".to_string(),
+                ..Default::default()
+            },
+        )]);
+
+        let assign = |ip: &str| tenant_for(&miner(ip), &tenants, &locations, &ranges);
+        assert_eq!(assign("10.0.2.5").as_deref(), Some("Acme"));
+        assert_eq!(assign("10.0.2.9").as_deref(), Some("Globex"));
+        // Globex has empty rules but does not claim miners by rule
+        assert_eq!(assign("10.0.3.5"), None);
+    }
+}
@@ -0,0 +1,588 @@
+use crate::actions::{self, MinerAction};
+use crate::audit::{self, AuditEntry, AuditLog};
+use crate::models::{
+    FanModeSelection, MinerCapabilities, MinerInfo, MinerLocation, MinerOptionSettings,
+    MiningModeSelection, TuningTargetSelection,
+};
+use crate::options;
+use crate::snapshots::SnapshotRecorder;
+use chrono::Local;
+use futures::stream::{self, StreamExt};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+const THERMAL_STATE_FILE: &str = "thermal_state.json";
+const MAX_LOG_ENTRIES: usize = 500;
+const ORIGINAL_READ_ATTEMPTS: u32 = 3;
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub enum ThermalStep {
+    MaxFans,
+    LowerPower { percent: f64 },
+    LowMiningMode,
+    Pause,
+}
+
+impl ThermalStep {
+    pub fn describe(&self) -> String {
+        match self {
+            ThermalStep::MaxFans => "Fans to manual 100%".to_string(),
+            ThermalStep::LowerPower { percent } => format!("Lower power {percent:.0}%"),
+            ThermalStep::LowMiningMode => "Low mining mode".to_string(),
+            ThermalStep::Pause => "Pause".to_string(),
+        }
+    }
+
+    fn is_supported(&self, reading: &ThermalReading) -> bool {
+        let caps = reading.capabilities;
+        match self {
+            ThermalStep::MaxFans => caps.fan_config,
+            ThermalStep::LowerPower { .. } => {
+                reading.wattage_w.is_some() && (caps.set_power_limit || caps.tuning_config)
+            }
+            ThermalStep::LowMiningMode => caps.tuning_config,
+            ThermalStep::Pause => true,
+        }
+    }
+
+    /// Option writes for this step, or `None` for steps that are not config changes.
+    fn settings(
+        &self,
+        wattage_w: Option<f64>,
+        caps: MinerCapabilities,
+    ) -> Option<MinerOptionSettings> {
+        match self {
+            ThermalStep::MaxFans => Some(MinerOptionSettings {
+                apply_fan_config: true,
+                fan_mode: FanModeSelection::Manual,
+                fan_speed_percent: 100,
+                ..Default::default()
+            }),
+            ThermalStep::LowerPower { percent } => {
+                let watts = (wattage_w? * (1.0 - percent / 100.0)).round();
+                Some(if caps.set_power_limit {
+                    MinerOptionSettings {
+                        apply_power_limit: true,
+                        power_limit_watts: watts,
+                        ..Default::default()
+                    }
+                } else {
+                    MinerOptionSettings {
+                        apply_tuning_config: true,
+                        tuning_target: TuningTargetSelection::Power,
+                        tuning_power_watts: watts,
+                        ..Default::default()
+                    }
+                })
+            }
+            ThermalStep::LowMiningMode => Some(MinerOptionSettings {
+                apply_tuning_config: true,
+                tuning_target: TuningTargetSelection::MiningMode,
+                mining_mode: MiningModeSelection::Low,
+                ..Default::default()
+            }),
+            ThermalStep::Pause => None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct ThermalSettings {
+    pub enabled: bool,
+    pub limit_c: f64,
+    pub consecutive_refreshes: u32,
+    /// Escalation order; each further run of hot refreshes takes the next step.
+    pub steps: Vec<ThermalStep>,
+}
+
+impl Default for ThermalSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            limit_c: 85.0,
+            consecutive_refreshes: 2,
+            steps: vec![
+                ThermalStep::MaxFans,
+                ThermalStep::LowerPower { percent: 15.0 },
+                ThermalStep::Pause,
+            ],
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ThermalReading {
+    pub identity: String,
+    pub ip: String,
+    pub hottest_c: Option<f64>,
+    pub wattage_w: Option<f64>,
+    pub capabilities: MinerCapabilities,
+}
+
+/// Hottest of the miner's average temperature and every board temperature.
+pub fn hottest_temperature(miner: &MinerInfo) -> Option<f64> {
+    let boards = miner
+        .full_data
+        .iter()
+        .flat_map(|data| data.hashboards.iter())
+        .filter_map(|board| board.board_temperature.map(|t| t.as_celsius()));
+
+    miner
+        .temperature_c
+        .into_iter()
+        .chain(boards)
+        .reduce(f64::max)
+}
+
+impl ThermalReading {
+    pub fn from_miner(miner: &MinerInfo) -> Self {
+        Self {
+            identity: miner.identity(),
+            ip: miner.ip.clone(),
+            hottest_c: hottest_temperature(miner),
+            wattage_w: miner.wattage_w,
+            capabilities: miner.capabilities,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ThermalTrigger {
+    pub identity: String,
+    pub ip: String,
+    pub step_index: usize,
+    pub step: ThermalStep,
+    pub temperature_c: f64,
+    pub wattage_w: Option<f64>,
+    pub capabilities: MinerCapabilities,
+}
+
+/// A miner the automation has acted on, keyed by `MinerInfo::identity()`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ThermalEscalation {
+    pub ip: String,
+    /// Number of configured steps already taken.
+    pub level: usize,
+    pub paused: bool,
+    pub started_at: i64,
+    /// Fan, power and tuning settings read before the first step.
+    pub original: Option<MinerOptionSettings>,
+    /// A step has written fan, power or tuning settings.
+    #[serde(default)]
+    pub settings_changed: bool,
+}
+
+impl ThermalEscalation {
+    /// Settings were changed but the originals could not be read, so a revert
+    /// cannot restore them.
+    pub fn original_unknown(&self) -> bool {
+        self.settings_changed && self.original.is_none()
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ThermalLogEntry {
+    pub at: i64,
+    pub ip: String,
+    pub action: String,
+    pub temperature_c: Option<f64>,
+    pub error: Option<String>,
+}
+
+/// Counts hot refreshes per miner and returns the next escalation step for every
+/// miner that has stayed over the limit long enough. Steps the miner cannot
+/// perform are skipped.
+pub fn evaluate_thermal(
+    settings: &ThermalSettings,
+    readings: &[ThermalReading],
+    hot_streaks: &mut HashMap<String, u32>,
+    escalations: &HashMap<String, ThermalEscalation>,
+) -> Vec<ThermalTrigger> {
+    let mut triggers = Vec::new();
+
+    for reading in readings {
+        let Some(temperature) = reading.hottest_c.filter(|t| *t > settings.limit_c) else {
+            hot_streaks.remove(&reading.identity);
+            continue;
+        };
+
+        let streak = hot_streaks.entry(reading.identity.clone()).or_insert(0);
+        *streak += 1;
+        if *streak < settings.consecutive_refreshes.max(1) {
+            continue;
+        }
+        *streak = 0;
+
+        let level = escalations.get(&reading.identity).map_or(0, |e| e.level);
+        let next = settings
+            .steps
+            .iter()
+            .enumerate()
+            .skip(level)
+            .find(|(_, step)| step.is_supported(reading));
+        if let Some((step_index, step)) = next {
+            triggers.push(ThermalTrigger {
+                identity: reading.identity.clone(),
+                ip: reading.ip.clone(),
+                step_index,
+                step: step.clone(),
+                temperature_c: temperature,
+                wattage_w: reading.wattage_w,
+                capabilities: reading.capabilities,
+            });
+        }
+    }
+
+    triggers
+}
+
+/// Settings that put fan, power and tuning back the way they were found.
+fn revert_settings(original: &MinerOptionSettings) -> MinerOptionSettings {
+    MinerOptionSettings {
+        apply_scaling_config: false,
+        apply_pool_config: false,
+        ..original.clone()
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct ThermalState {
+    pub escalations: HashMap<String, ThermalEscalation>,
+    pub log: Vec<ThermalLogEntry>,
+    #[serde(skip)]
+    pub hot_streaks: HashMap<String, u32>,
+    #[serde(skip)]
+    pub in_flight: HashSet<String>,
+}
+
+impl ThermalState {
+    pub fn load() -> Self {
+        crate::config::app_data_path(THERMAL_STATE_FILE)
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+            .unwrap_or_default()
+    }
+
+    fn save(&self) {
+        if let Some(path) = crate::config::app_data_path(THERMAL_STATE_FILE) {
+            if let Ok(json) = serde_json::to_string_pretty(self) {
+                if let Err(e) = fs::write(path, json) {
+                    eprintln!("✗ Failed to save thermal state: {e}");
+                }
+            }
+        }
+    }
+
+    /// Drops an escalation the operator has restored by hand.
+    pub fn forget(&mut self, identity: &str) {
+        if let Some(escalation) = self.escalations.remove(identity) {
+            self.hot_streaks.remove(identity);
+            self.push_log(ThermalLogEntry {
+                at: Local::now().timestamp(),
+                ip: escalation.ip,
+                action: "Forgotten after manual restore".to_string(),
+                temperature_c: None,
+                error: None,
+            });
+            self.save();
+        }
+    }
+
+    fn push_log(&mut self, entry: ThermalLogEntry) {
+        match &entry.error {
+            None => println!("✓ Thermal: {} {}", entry.ip, entry.action),
+            Some(err) => eprintln!("✗ Thermal: {} {}: {err}", entry.ip, entry.action),
+        }
+        self.log.push(entry);
+        if self.log.len() > MAX_LOG_ENTRIES {
+            self.log.drain(0..self.log.len() - MAX_LOG_ENTRIES);
+        }
+    }
+}
+
+async fn execute_trigger(
+    trigger: &ThermalTrigger,
+    state: &Arc<Mutex<ThermalState>>,
+    recorder: Option<&SnapshotRecorder>,
+    locations: &HashMap<String, MinerLocation>,
+) -> Result<(), String> {
+    let existing = state
+        .lock()
+        .unwrap()
+        .escalations
+        .get(&trigger.identity)
+        .cloned();
+    let (original, read_error) = match existing.as_ref() {
+        Some(escalation) => (escalation.original.clone(), None),
+        None => match read_original(&trigger.ip).await {
+            Ok(original) => (Some(original), None),
+            Err(err) => (None, Some(err)),
+        },
+    };
+
+    // Escalate even when the write fails so a stuck step cannot hold back a pause
+    {
+        let mut state = state.lock().unwrap();
+        let escalation =
+            state
+                .escalations
+                .entry(trigger.identity.clone())
+                .or_insert(ThermalEscalation {
+                    ip: trigger.ip.clone(),
+                    level: 0,
+                    paused: false,
+                    started_at: Local::now().timestamp(),
+                    original,
+                    settings_changed: false,
+                });
+        escalation.ip = trigger.ip.clone();
+        escalation.level = trigger.step_index + 1;
+    }
+
+    let result = match trigger
+        .step
+        .settings(trigger.wattage_w, trigger.capabilities)
+    {
+        Some(settings) => {
+            let recorder = recorder.ok_or("No snapshot job for thermal change")?;
+            if let Some(escalation) = state.lock().unwrap().escalations.get_mut(&trigger.identity) {
+                escalation.settings_changed = true;
+            }
+            options::apply_options_to_miner(trigger.ip.clone(), settings, recorder, locations)
+                .await
+                .map(|_| ())
+        }
+        None => {
+            actions::run_action(&trigger.ip, MinerAction::Pause).await?;
+            if let Some(escalation) = state.lock().unwrap().escalations.get_mut(&trigger.identity) {
+                escalation.paused = true;
+            }
+            Ok(())
+        }
+    };
+
+    // Protecting the miner comes first, but the log must say revert cannot
+    // restore what it had
+    match (result, read_error) {
+        (Ok(()), Some(err)) => Err(format!(
+            "Applied, but original settings could not be read ({err}); revert will need a manual restore"
+        )),
+        (result, _) => result,
+    }
+}
+
+/// Reads the settings an escalation should later restore, retrying briefly
+/// since a hot miner's API is often slow to answer.
+async fn read_original(ip: &str) -> Result<MinerOptionSettings, String> {
+    let mut attempt = 1;
+    loop {
+        match options::fetch_current_options(ip.to_string(), Default::default()).await {
+            Ok(original) => return Ok(original),
+            Err(err) if attempt >= ORIGINAL_READ_ATTEMPTS => return Err(err),
+            Err(_) => {
+                attempt += 1;
+                tokio::time::sleep(Duration::from_secs(2)).await;
+            }
+        }
+    }
+}
+
+/// Runs escalation steps in the background, logging each one.
+pub fn execute_triggers(
+    state: Arc<Mutex<ThermalState>>,
+    triggers: Vec<ThermalTrigger>,
+    recorder: Option<SnapshotRecorder>,
+    locations: Arc<HashMap<String, MinerLocation>>,
+    audit_log: Arc<Mutex<AuditLog>>,
+    audit_entry: AuditEntry,
+) {
+    state
+        .lock()
+        .unwrap()
+        .in_flight
+        .extend(triggers.iter().map(|t| t.identity.clone()));
+
+    crate::runtime::spawn(async move {
+        let concurrency = triggers.len().clamp(1, 24);
+        let results: Vec<(String, Result<(), String>)> = stream::iter(triggers)
+            .map(|trigger| {
+                let state = Arc::clone(&state);
+                let recorder = recorder.clone();
+                let locations = Arc::clone(&locations);
+                async move {
+                    let result =
+                        execute_trigger(&trigger, &state, recorder.as_ref(), &locations).await;
+                    let mut state = state.lock().unwrap();
+                    state.push_log(ThermalLogEntry {
+                        at: Local::now().timestamp(),
+                        ip: trigger.ip.clone(),
+                        action: trigger.step.describe(),
+                        temperature_c: Some(trigger.temperature_c),
+                        error: result.clone().err(),
+                    });
+                    state.in_flight.remove(&trigger.identity);
+                    (trigger.ip, result)
+                }
+            })
+            .buffer_unordered(concurrency)
+            .collect()
+            .await;
+        if let Some(recorder) = &recorder {
+            recorder.flush();
+        }
+        audit::record(&audit_log, audit_entry.with_results(&results));
+
+        state.lock().unwrap().save();
+    });
+}
+
+/// Resumes a paused miner and writes back its original fan, power and tuning
+/// settings, then clears the escalation.
+pub fn revert_escalation(
+    state: Arc<Mutex<ThermalState>>,
+    identity: String,
+    recorder: SnapshotRecorder,
+    locations: Arc<HashMap<String, MinerLocation>>,
+    audit_log: Arc<Mutex<AuditLog>>,
+    audit_entry: AuditEntry,
+) {
+    let Some(escalation) = state.lock().unwrap().escalations.get(&identity).cloned() else {
+        return;
+    };
+    state.lock().unwrap().in_flight.insert(identity.clone());
+
+    crate::runtime::spawn(async move {
+        let mut result = Ok(());
+        let mut resumed = false;
+        if escalation.paused {
+            result = actions::run_action(&escalation.ip, MinerAction::Resume).await;
+            resumed = result.is_ok();
+        }
+        if result.is_ok() && escalation.original_unknown() {
+            result = Err("original settings unknown; restore manually".to_string());
+        }
+        if result.is_ok() {
+            if let Some(original) = escalation.original.as_ref().map(revert_settings) {
+                if original.has_any_enabled() {
+                    result = options::apply_options_to_miner(
+                        escalation.ip.clone(),
+                        original,
+                        &recorder,
+                        &locations,
+                    )
+                    .await
+                    .map(|_| ());
+                }
+            }
+        }
+        recorder.flush();
+
+        audit::record(
+            &audit_log,
+            audit_entry.with_results(&[(escalation.ip.clone(), result.clone())]),
+        );
+
+        let mut state = state.lock().unwrap();
+        if result.is_ok() {
+            state.escalations.remove(&identity);
+            state.hot_streaks.remove(&identity);
+        } else if resumed {
+            if let Some(escalation) = state.escalations.get_mut(&identity) {
+                escalation.paused = false;
+            }
+        }
+        state.push_log(ThermalLogEntry {
+            at: Local::now().timestamp(),
+            ip: escalation.ip.clone(),
+            action: "Reverted".to_string(),
+            temperature_c: None,
+            error: result.err(),
+        });
+        state.in_flight.remove(&identity);
+        state.save();
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{evaluate_thermal, ThermalReading, ThermalSettings, ThermalStep};
+    use crate::models::MinerCapabilities;
+    use std::collections::HashMap;
+
+    fn reading(temp: f64, fan_config: bool) -> ThermalReading {
+        ThermalReading {
+            identity: "aa:bb".to_string(),
+            ip: "10.0.0.5".to_string(),
+            hottest_c: Some(temp),
+            wattage_w: Some(3000.0),
+            capabilities: MinerCapabilities {
+                set_power_limit: true,
+                fan_config,
+                ..Default::default()
+            },
+        }
+    }
+
+    #[test]
+    fn thermal_waits_for_consecutive_hot_refreshes() {
+        let settings = ThermalSettings {
+            enabled: true,
+            ..Default::default()
+        };
+        let mut streaks = HashMap::new();
+        let escalations = HashMap::new();
+
+        assert!(evaluate_thermal(
+            &settings,
+            &[reading(90.0, true)],
+            &mut streaks,
+            &escalations
+        )
+        .is_empty());
+        // A cool refresh resets the streak
+        assert!(evaluate_thermal(
+            &settings,
+            &[reading(70.0, true)],
+            &mut streaks,
+            &escalations
+        )
+        .is_empty());
+        assert!(evaluate_thermal(
+            &settings,
+            &[reading(90.0, true)],
+            &mut streaks,
+            &escalations
+        )
+        .is_empty());
+        let triggers = evaluate_thermal(
+            &settings,
+            &[reading(90.0, true)],
+            &mut streaks,
+            &escalations,
+        );
+        assert_eq!(triggers.len(), 1);
+        assert_eq!(triggers[0].step, ThermalStep::MaxFans);
+    }
+
+    #[test]
+    fn thermal_skips_steps_the_miner_cannot_do() {
+        let settings = ThermalSettings {
+            enabled: true,
+            consecutive_refreshes: 1,
+            ..Default::default()
+        };
+        let mut streaks = HashMap::new();
+        let triggers = evaluate_thermal(
+            &settings,
+            &[reading(95.0, false)],
+            &mut streaks,
+            &HashMap::new(),
+        );
+        assert_eq!(triggers[0].step_index, 1);
+        assert_eq!(triggers[0].step, ThermalStep::LowerPower { percent: 15.0 });
+    }
+}
@@ -0,0 +1,272 @@
+use crate::models::{MinerInfo, OptionsApplyPreview};
+use crate::safety::{self, SafetySettings};
+use eframe::egui;
+use egui::Color32;
+use std::sync::{Arc, Mutex};
+
+pub fn draw_apply_preview_window(
+    ctx: &egui::Context,
+    apply_preview: Arc<Mutex<Option<OptionsApplyPreview>>>,
+    miners: &[MinerInfo],
+    safety_settings: &SafetySettings,
+    on_confirm_clicked: &mut bool,
+) {
+    let mut preview_lock = apply_preview.lock().unwrap();
+    let Some(preview) = preview_lock.as_mut() else {
+        return;
+    };
+
+    let mut is_open = true;
+    let mut cancel_clicked = false;
+
+    egui::Window::new(
+        egui::RichText::new(format!("🔎 Review Changes - {}", preview.title))
+            .size(12.0)
+            .monospace(),
+    )
+    .id(egui::Id::new("apply_preview_window"))
+    .default_width(760.0)
+    .default_height(480.0)
+    .resizable(true)
+    .collapsible(false)
+    .open(&mut is_open)
+    .show(ctx, |ui| {
+        if preview.loading {
+            ui.horizontal(|ui| {
+                ui.spinner();
+                ui.label(
+                    egui::RichText::new(format!(
+                        "Reading current settings from {} miner(s)...",
+                        preview.total_targets
+                    ))
+                    .size(11.0)
+                    .color(Color32::from_rgb(160, 160, 160)),
+                );
+            });
+            ui.ctx().request_repaint();
+            ui.add_space(8.0);
+            if ui.button("Cancel").clicked() {
+                cancel_clicked = true;
+            }
+            return;
+        }
+
+        let writing: Vec<String> = preview
+            .entries
+            .iter()
+            .filter(|e| e.will_write())
+            .map(|e| e.ip.clone())
+            .collect();
+        let changing = writing.len();
+        let unchanged = preview
+            .entries
+            .iter()
+            .filter(|e| e.error.is_none() && e.changes.is_empty())
+            .count();
+        let failed = preview.entries.iter().filter(|e| e.error.is_some()).count();
+
+        ui.label(
+            egui::RichText::new(format!(
+                "{changing} will change  •  {unchanged} already match  •  {failed} unreachable"
+            ))
+            .size(11.0)
+            .color(Color32::from_rgb(200, 200, 200))
+            .monospace(),
+        );
+        ui.label(
+            egui::RichText::new(if preview.staged {
+                "Nothing is written until you confirm below - changes roll out in stages starting with canaries"
+            } else {
+                "Nothing is written until you confirm below"
+            })
+            .size(10.0)
+            .color(Color32::from_rgb(130, 130, 130)),
+        );
+        if changing > 1 {
+            let models = safety::model_summary(&writing, miners)
+                .into_iter()
+                .map(|(model, count)| format!("{count}× {model}"))
+                .collect::<Vec<_>>()
+                .join(", ");
+            ui.label(
+                egui::RichText::new(format!("Writing to {models}"))
+                    .size(10.0)
+                    .color(Color32::from_rgb(200, 200, 200))
+                    .monospace(),
+            );
+        }
+        if !preview.skipped_protected.is_empty() {
+            ui.label(
+                egui::RichText::new(format!(
+                    "🛡 {} protected miner(s) skipped",
+                    preview.skipped_protected.len()
+                ))
+                .size(10.0)
+                .color(Color32::from_rgb(255, 200, 100)),
+            )
+            .on_hover_text(preview.skipped_protected.join("\n"));
+        }
+
+        let pool_checks_failed = preview.pool_checks_failed();
+        if !preview.pool_checks.is_empty() {
+            ui.add_space(8.0);
+            ui.label(
+                egui::RichText::new("Pool pre-flight")
+                    .size(11.0)
+                    .color(Color32::from_rgb(100, 200, 255))
+                    .strong()
+                    .monospace(),
+            );
+            for check in &preview.pool_checks {
+                let (text, color) = match &check.result {
+                    Ok(detail) => (
+                        format!("✓ {} - {detail}", check.url),
+                        Color32::from_rgb(120, 200, 120),
+                    ),
+                    Err(err) => (
+                        format!("✗ {} - {err}", check.url),
+                        Color32::from_rgb(255, 120, 120),
+                    ),
+                };
+                ui.label(egui::RichText::new(text).size(10.0).color(color).monospace());
+            }
+            if pool_checks_failed {
+                ui.label(
+                    egui::RichText::new("Fix the failing pools before applying")
+                        .size(10.0)
+                        .color(Color32::from_rgb(255, 165, 0)),
+                );
+            }
+        }
+
+        ui.add_space(8.0);
+
+        egui::ScrollArea::vertical()
+            .id_salt("apply_preview_scroll")
+            .max_height(360.0)
+            .auto_shrink([false, true])
+            .show(ui, |ui| {
+                for entry in &preview.entries {
+                    let model = miners
+                        .iter()
+                        .find(|m| m.ip == entry.ip)
+                        .map(|m| m.model.as_str())
+                        .unwrap_or("N/A");
+
+                    ui.label(
+                        egui::RichText::new(format!("{} - {}", entry.ip, model))
+                            .size(11.0)
+                            .color(Color32::from_rgb(100, 200, 255))
+                            .strong()
+                            .monospace(),
+                    );
+
+                    ui.indent(format!("apply_preview_{}", entry.ip), |ui| {
+                        if let Some(err) = &entry.error {
+                            ui.label(
+                                egui::RichText::new(format!("✗ {err}"))
+                                    .size(10.0)
+                                    .color(Color32::from_rgb(255, 120, 120)),
+                            );
+                            return;
+                        }
+
+                        if entry.changes.is_empty() {
+                            ui.label(
+                                egui::RichText::new("✓ No changes")
+                                    .size(10.0)
+                                    .color(Color32::from_rgb(120, 200, 120)),
+                            );
+                        } else {
+                            egui::Grid::new(format!("apply_preview_grid_{}", entry.ip))
+                                .num_columns(3)
+                                .spacing([20.0, 4.0])
+                                .striped(true)
+                                .show(ui, |ui| {
+                                    for change in &entry.changes {
+                                        ui.label(
+                                            egui::RichText::new(&change.field)
+                                                .size(10.0)
+                                                .monospace(),
+                                        );
+                                        ui.label(
+                                            egui::RichText::new(&change.current)
+                                                .size(10.0)
+                                                .color(Color32::from_rgb(160, 160, 160))
+                                                .monospace(),
+                                        );
+                                        ui.label(
+                                            egui::RichText::new(format!("→ {}", change.requested))
+                                                .size(10.0)
+                                                .color(Color32::from_rgb(255, 200, 100))
+                                                .monospace(),
+                                        );
+                                        ui.end_row();
+                                    }
+                                });
+                        }
+
+                        if !entry.unsupported.is_empty() {
+                            ui.label(
+                                egui::RichText::new(format!(
+                                    "⚠ Not supported, skipped: {}",
+                                    entry.unsupported.join(", ")
+                                ))
+                                .size(10.0)
+                                .color(Color32::from_rgb(255, 165, 0)),
+                            );
+                        }
+                    });
+
+                    ui.add_space(6.0);
+                }
+            });
+
+        ui.add_space(8.0);
+        ui.separator();
+        let phrase = safety::confirmation_phrase("Apply", changing);
+        let typed_ok = !safety_settings.requires_typing(changing)
+            || preview.confirm_text.trim() == phrase;
+        if safety_settings.requires_typing(changing) {
+            ui.horizontal(|ui| {
+                ui.label(
+                    egui::RichText::new(format!("Type {phrase} to confirm"))
+                        .size(11.0)
+                        .color(Color32::from_rgb(255, 200, 100))
+                        .monospace(),
+                );
+                ui.add(
+                    egui::TextEdit::singleline(&mut preview.confirm_text)
+                        .desired_width(140.0)
+                        .font(egui::TextStyle::Monospace),
+                );
+            });
+        }
+        ui.horizontal(|ui| {
+            if ui
+                .add_enabled(
+                    changing > 0 && !pool_checks_failed && typed_ok,
+                    egui::Button::new(
+                        egui::RichText::new(format!("Confirm Apply ({changing})"))
+                            .size(11.0)
+                            .color(Color32::WHITE)
+                            .monospace(),
+                    )
+                    .fill(Color32::from_rgb(100, 160, 100))
+                    .corner_radius(5.0),
+                )
+                .clicked()
+            {
+                *on_confirm_clicked = true;
+            }
+
+            if ui.button("Cancel").clicked() {
+                cancel_clicked = true;
+            }
+        });
+    });
+
+    if !is_open || cancel_clicked {
+        *preview_lock = None;
+    }
+}
@@ -0,0 +1,154 @@
+use crate::audit::{self, AuditFilter, AuditLog};
+use crate::snapshots::format_timestamp;
+use eframe::egui;
+use egui::Color32;
+use std::collections::BTreeSet;
+
+const MAX_SHOWN_ENTRIES: usize = 1000;
+
+fn targets_label(targets: &[String]) -> String {
+    match targets {
+        [] => "—".to_string(),
+        [ip] => ip.clone(),
+        _ => format!("{} miners", targets.len()),
+    }
+}
+
+pub fn draw_audit_window(
+    ctx: &egui::Context,
+    open: &mut bool,
+    log: &AuditLog,
+    filter: &mut AuditFilter,
+    export_clicked: &mut bool,
+) {
+    egui::Window::new(egui::RichText::new("📝 Audit Log").size(12.0).monospace())
+        .id(egui::Id::new("audit_window"))
+        .default_width(860.0)
+        .default_height(520.0)
+        .resizable(true)
+        .collapsible(true)
+        .open(open)
+        .show(ctx, |ui| {
+            let sources: BTreeSet<&str> = log
+                .entries
+                .iter()
+                .map(|entry| entry.source.as_str())
+                .collect();
+            let entries = audit::filtered_entries(log, filter);
+
+            ui.horizontal(|ui| {
+                ui.add(
+                    egui::TextEdit::singleline(&mut filter.text)
+                        .hint_text("IP, action, operator, error...")
+                        .desired_width(220.0),
+                );
+                egui::ComboBox::from_id_salt("audit_source")
+                    .selected_text(filter.source.as_deref().unwrap_or("All sources"))
+                    .show_ui(ui, |ui| {
+                        ui.selectable_value(&mut filter.source, None, "All sources");
+                        for source in &sources {
+                            ui.selectable_value(
+                                &mut filter.source,
+                                Some(source.to_string()),
+                                *source,
+                            );
+                        }
+                    });
+                ui.checkbox(&mut filter.failures_only, "Failures only");
+                ui.separator();
+                ui.label(format!("{} of {}", entries.len(), log.entries.len()));
+                if ui
+                    .add_enabled(!entries.is_empty(), egui::Button::new("Export CSV"))
+                    .on_hover_text("Exports the entries matching the filter")
+                    .clicked()
+                {
+                    *export_clicked = true;
+                }
+            });
+            ui.add_space(4.0);
+
+            if entries.is_empty() {
+                ui.label("No matching entries");
+                return;
+            }
+            if entries.len() > MAX_SHOWN_ENTRIES {
+                ui.label(
+                    egui::RichText::new(format!(
+                        "Showing the newest {MAX_SHOWN_ENTRIES}; export for the rest"
+                    ))
+                    .size(10.0)
+                    .color(Color32::from_rgb(130, 130, 130)),
+                );
+            }
+
+            egui::ScrollArea::vertical()
+                .id_salt("audit_scroll")
+                .auto_shrink([false, false])
+                .show(ui, |ui| {
+                    egui::Grid::new("audit_grid")
+                        .num_columns(7)
+                        .spacing([14.0, 4.0])
+                        .striped(true)
+                        .show(ui, |ui| {
+                            for header in [
+                                "TIME",
+                                "OPERATOR",
+                                "SOURCE",
+                                "ACTION",
+                                "TARGETS",
+                                "PARAMETERS",
+                                "RESULT",
+                            ] {
+                                ui.label(
+                                    egui::RichText::new(header).size(10.0).strong().monospace(),
+                                );
+                            }
+                            ui.end_row();
+
+                            for entry in entries.iter().take(MAX_SHOWN_ENTRIES) {
+                                ui.label(
+                                    egui::RichText::new(format_timestamp(entry.at))
+                                        .size(10.0)
+                                        .monospace(),
+                                );
+                                ui.label(
+                                    egui::RichText::new(&entry.operator).size(10.0).monospace(),
+                                )
+                                .on_hover_text(&entry.host);
+                                ui.label(egui::RichText::new(&entry.source).size(10.0).monospace());
+                                ui.label(egui::RichText::new(&entry.action).size(10.0).monospace());
+                                let targets = ui.label(
+                                    egui::RichText::new(targets_label(&entry.targets))
+                                        .size(10.0)
+                                        .monospace(),
+                                );
+                                if entry.targets.len() > 1 {
+                                    targets.on_hover_text(entry.targets.join("\n"));
+                                }
+                                ui.label(
+                                    egui::RichText::new(&entry.parameters)
+                                        .size(10.0)
+                                        .monospace(),
+                                );
+                                let color = if !entry.failures.is_empty() {
+                                    Color32::from_rgb(255, 120, 120)
+                                } else if entry.dispatched {
+                                    Color32::from_rgb(130, 130, 130)
+                                } else {
+                                    Color32::from_rgb(150, 200, 150)
+                                };
+                                let result = ui.label(
+                                    egui::RichText::new(entry.result_label())
+                                        .size(10.0)
+                                        .color(color)
+                                        .monospace(),
+                                );
+                                if !entry.failures.is_empty() {
+                                    result.on_hover_text(entry.failures.join("\n"));
+                                }
+                                ui.end_row();
+                            }
+                        });
+                });
+        });
+}
@@ -0,0 +1,226 @@
+use crate::availability::{MinerState, SlaGrouping, SlaRow, SlaSettings};
+use crate::snapshots::format_timestamp;
+use crate::tenants::ReportPeriod;
+use crate::ui::tenants::draw_period_picker;
+use eframe::egui;
+use egui::Color32;
+
+fn format_duration(secs: i64) -> String {
+    let minutes = secs / 60;
+    if minutes >= 60 {
+        format!("{}h {:02}m", minutes / 60, minutes % 60)
+    } else {
+        format!("{minutes}m")
+    }
+}
+
+fn availability_color(availability: Option<f64>, target: f64) -> Color32 {
+    match availability {
+        Some(a) if a >= target => Color32::from_rgb(150, 200, 150),
+        Some(_) => Color32::from_rgb(255, 120, 120),
+        None => Color32::from_rgb(130, 130, 130),
+    }
+}
+
+fn draw_incidents(ui: &mut egui::Ui, row: &SlaRow) {
+    egui::Grid::new(format!("sla_incidents_{}", row.name))
+        .num_columns(6)
+        .spacing([14.0, 2.0])
+        .striped(true)
+        .show(ui, |ui| {
+            for header in ["IP", "MODEL", "STATE", "START", "DURATION", "REBOOT"] {
+                ui.label(egui::RichText::new(header).size(10.0).strong().monospace());
+            }
+            ui.end_row();
+            for incident in &row.incidents {
+                let color = if incident.state == MinerState::Offline {
+                    Color32::from_rgb(255, 120, 120)
+                } else {
+                    Color32::from_rgb(255, 200, 100)
+                };
+                ui.label(egui::RichText::new(&incident.ip).size(10.0).monospace())
+                    .on_hover_text(&incident.identity);
+                ui.label(egui::RichText::new(&incident.model).size(10.0).monospace());
+                ui.label(
+                    egui::RichText::new(incident.state.label())
+                        .size(10.0)
+                        .color(color)
+                        .monospace(),
+                );
+                ui.label(
+                    egui::RichText::new(format_timestamp(incident.start))
+                        .size(10.0)
+                        .monospace(),
+                );
+                ui.label(
+                    egui::RichText::new(format_duration(incident.duration_secs()))
+                        .size(10.0)
+                        .monospace(),
+                );
+                ui.label(
+                    egui::RichText::new(if incident.rebooted { "yes" } else { "" })
+                        .size(10.0)
+                        .monospace(),
+                );
+                ui.end_row();
+            }
+        });
+}
+
+pub fn draw_availability_window(
+    ctx: &egui::Context,
+    open: &mut bool,
+    settings: &mut SlaSettings,
+    period: &mut ReportPeriod,
+    grouping: &mut SlaGrouping,
+    rows: &[SlaRow],
+    export_clicked: &mut bool,
+) {
+    egui::Window::new(
+        egui::RichText::new("⏱ Availability & SLA")
+            .size(12.0)
+            .monospace(),
+    )
+    .id(egui::Id::new("availability_window"))
+    .default_width(720.0)
+    .default_height(560.0)
+    .resizable(true)
+    .collapsible(true)
+    .open(open)
+    .show(ctx, |ui| {
+        ui.label(
+            egui::RichText::new(
+                "Each scan attributes the time since the previous one to the state it finds. Miners missing from a scan count as offline; time the app was closed is not counted. A reboot is a jump in boot time derived from uptime.",
+            )
+            .size(10.0)
+            .color(Color32::from_rgb(130, 130, 130)),
+        );
+        ui.add_space(6.0);
+
+        ui.horizontal(|ui| {
+            ui.label("Target");
+            ui.add(
+                egui::DragValue::new(&mut settings.target_percent)
+                    .range(0.0..=100.0)
+                    .speed(0.01)
+                    .max_decimals(2)
+                    .suffix(" %"),
+            );
+            ui.checkbox(&mut settings.exclude_paused, "Exclude paused time")
+                .on_hover_text("Deliberate pauses such as curtailment do not count against the SLA");
+        });
+        if !draw_period_picker(ui, period) {
+            return;
+        }
+
+        ui.horizontal(|ui| {
+            ui.label("Per");
+            for option in [SlaGrouping::Miner, SlaGrouping::Range, SlaGrouping::Tenant] {
+                ui.selectable_value(grouping, option, option.label());
+            }
+            ui.separator();
+            if ui
+                .add_enabled(!rows.is_empty(), egui::Button::new("Export CSV"))
+                .clicked()
+            {
+                *export_clicked = true;
+            }
+        });
+        ui.add_space(4.0);
+
+        if rows.is_empty() {
+            ui.label("No scans recorded in this period");
+            return;
+        }
+
+        egui::ScrollArea::vertical()
+            .id_salt("availability_scroll")
+            .auto_shrink([false, false])
+            .show(ui, |ui| {
+                egui::Grid::new("availability_grid")
+                    .num_columns(6)
+                    .spacing([16.0, 4.0])
+                    .striped(true)
+                    .show(ui, |ui| {
+                        for header in [
+                            grouping.label(),
+                            "MINERS",
+                            "AVAILABILITY",
+                            "DOWNTIME",
+                            "PAUSED",
+                            "REBOOTS",
+                        ] {
+                            ui.label(
+                                egui::RichText::new(header.to_uppercase())
+                                    .size(10.0)
+                                    .strong()
+                                    .monospace(),
+                            );
+                        }
+                        ui.end_row();
+
+                        for row in rows {
+                            let availability =
+                                row.totals.availability_percent(settings.exclude_paused);
+                            ui.label(egui::RichText::new(&row.name).size(10.0).monospace());
+                            ui.label(
+                                egui::RichText::new(row.miners.to_string())
+                                    .size(10.0)
+                                    .monospace(),
+                            );
+                            ui.label(
+                                egui::RichText::new(
+                                    availability.map_or("—".to_string(), |a| format!("{a:.3}%")),
+                                )
+                                .size(10.0)
+                                .color(availability_color(availability, settings.target_percent))
+                                .monospace(),
+                            );
+                            ui.label(
+                                egui::RichText::new(format_duration(row.totals.downtime_secs()))
+                                    .size(10.0)
+                                    .monospace(),
+                            )
+                            .on_hover_text(format!(
+                                "{} offline, {} not hashing",
+                                format_duration(row.totals.offline_secs),
+                                format_duration(row.totals.online_secs)
+                            ));
+                            ui.label(
+                                egui::RichText::new(format_duration(row.totals.paused_secs))
+                                    .size(10.0)
+                                    .monospace(),
+                            );
+                            ui.label(
+                                egui::RichText::new(row.totals.reboots.to_string())
+                                    .size(10.0)
+                                    .monospace(),
+                            );
+                            ui.end_row();
+                        }
+                    });
+
+                ui.add_space(8.0);
+                ui.label(
+                    egui::RichText::new("DOWNTIME INCIDENTS")
+                        .size(11.0)
+                        .strong()
+                        .monospace(),
+                );
+                let mut any = false;
+                for row in rows.iter().filter(|row| !row.incidents.is_empty()) {
+                    any = true;
+                    egui::CollapsingHeader::new(format!(
+                        "{} — {} incident(s)",
+                        row.name,
+                        row.incidents.len()
+                    ))
+                    .id_salt(format!("sla_group_{}", row.name))
+                    .show(ui, |ui| draw_incidents(ui, row));
+                }
+                if !any {
+                    ui.label("None in this period");
+                }
+            });
+    });
+}
@@ -0,0 +1,171 @@
+use crate::baselines::{BaselineDatabase, ModelBaseline, Nominal};
+use eframe::egui;
+use egui::Color32;
+use std::collections::HashMap;
+
+pub fn draw_baselines_window(
+    ctx: &egui::Context,
+    open: &mut bool,
+    database: &mut BaselineDatabase,
+    learned: &HashMap<String, Nominal>,
+    changed: &mut bool,
+    reload: &mut bool,
+) {
+    egui::Window::new(
+        egui::RichText::new("📐 Model Baselines")
+            .size(12.0)
+            .monospace(),
+    )
+    .id(egui::Id::new("baselines_window"))
+    .default_width(680.0)
+    .default_height(520.0)
+    .resizable(true)
+    .collapsible(true)
+    .open(open)
+    .show(ctx, |ui| {
+        ui.label(
+            egui::RichText::new(
+                "Nominal figures drive the % NOMINAL column. Each miner uses the entry for its reported mining mode (Low, Normal, High), else the Normal (or blank) one. Stored in model_baselines.json.",
+            )
+            .size(10.0)
+            .color(Color32::from_rgb(130, 130, 130)),
+        );
+        ui.add_space(6.0);
+
+        let mut remove = None;
+        egui::ScrollArea::vertical()
+            .id_salt("baselines_scroll")
+            .max_height(280.0)
+            .auto_shrink([false, true])
+            .show(ui, |ui| {
+                egui::Grid::new("baselines_grid")
+                    .num_columns(6)
+                    .spacing([12.0, 4.0])
+                    .striped(true)
+                    .show(ui, |ui| {
+                        for header in ["MODEL", "MODE", "TH/s", "WATTS", "W/TH", ""] {
+                            ui.label(egui::RichText::new(header).size(10.0).strong().monospace());
+                        }
+                        ui.end_row();
+
+                        for (index, baseline) in database.models.iter_mut().enumerate() {
+                            *changed |= ui
+                                .add(
+                                    egui::TextEdit::singleline(&mut baseline.model)
+                                        .desired_width(110.0),
+                                )
+                                .changed();
+                            *changed |= ui
+                                .add(
+                                    egui::TextEdit::singleline(&mut baseline.mode)
+                                        .desired_width(90.0),
+                                )
+                                .changed();
+                            *changed |= ui
+                                .add(
+                                    egui::DragValue::new(&mut baseline.nominal_hashrate_th)
+                                        .range(0.0..=2000.0)
+                                        .speed(0.5),
+                                )
+                                .changed();
+                            *changed |= ui
+                                .add(
+                                    egui::DragValue::new(&mut baseline.nominal_power_w)
+                                        .range(0.0..=20000.0)
+                                        .speed(10.0),
+                                )
+                                .changed();
+                            ui.label(
+                                egui::RichText::new(
+                                    baseline
+                                        .efficiency_w_th()
+                                        .map(|e| format!("{e:.1}"))
+                                        .unwrap_or_else(|| "-".to_string()),
+                                )
+                                .size(10.0)
+                                .monospace(),
+                            );
+                            if ui.small_button("✕").clicked() {
+                                remove = Some(index);
+                            }
+                            ui.end_row();
+                        }
+                    });
+            });
+        if let Some(index) = remove {
+            database.models.remove(index);
+            *changed = true;
+        }
+
+        ui.horizontal(|ui| {
+            if ui.button("+ Add Model").clicked() {
+                database.models.push(ModelBaseline {
+                    model: String::new(),
+                    mode: "Normal".to_string(),
+                    nominal_hashrate_th: 0.0,
+                    nominal_power_w: 0.0,
+                });
+                *changed = true;
+            }
+            if ui
+                .button("Reload File")
+                .on_hover_text("Pick up changes made to model_baselines.json by hand")
+                .clicked()
+            {
+                *reload = true;
+            }
+        });
+
+        ui.add_space(8.0);
+        ui.label(
+            egui::RichText::new("LEARNED FROM FLEET")
+                .size(11.0)
+                .strong()
+                .monospace(),
+        );
+        let mut learned: Vec<_> = learned.iter().collect();
+        learned.sort_by(|(a, _), (b, _)| a.cmp(b));
+        if learned.is_empty() {
+            ui.label("Needs at least three hashing miners of a model");
+        }
+        egui::Grid::new("learned_baselines_grid")
+            .num_columns(4)
+            .spacing([12.0, 4.0])
+            .striped(true)
+            .show(ui, |ui| {
+                for (model, nominal) in learned {
+                    ui.label(egui::RichText::new(model).size(10.0).monospace());
+                    ui.label(
+                        egui::RichText::new(format!("{:.1} TH/s", nominal.hashrate_th))
+                            .size(10.0)
+                            .monospace(),
+                    );
+                    ui.label(
+                        egui::RichText::new(
+                            nominal
+                                .power_w
+                                .map(|w| format!("{w:.0} W"))
+                                .unwrap_or_else(|| "-".to_string()),
+                        )
+                        .size(10.0)
+                        .monospace(),
+                    );
+                    let known = database.find(model, None).is_some();
+                    if ui
+                        .add_enabled(!known, egui::Button::new("Use as Baseline").small())
+                        .on_disabled_hover_text("Already configured above")
+                        .clicked()
+                    {
+                        database.models.push(ModelBaseline {
+                            model: model.clone(),
+                            mode: "Normal".to_string(),
+                            nominal_hashrate_th: (nominal.hashrate_th * 10.0).round() / 10.0,
+                            nominal_power_w: nominal.power_w.unwrap_or(0.0).round(),
+                        });
+                        *changed = true;
+                    }
+                    ui.end_row();
+                }
+            });
+    });
+}
@@ -0,0 +1,256 @@
+use crate::credentials::{self, CredentialScope, MinerCredential};
+use crate::models::{MinerInfo, SavedRange};
+use eframe::egui;
+use egui::Color32;
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+fn draw_scope_picker(
+    ui: &mut egui::Ui,
+    scope: &mut CredentialScope,
+    models: &BTreeSet<&str>,
+    saved_ranges: &[SavedRange],
+) {
+    egui::ComboBox::from_id_salt("new_credential_scope")
+        .selected_text(scope.kind_label())
+        .width(80.0)
+        .show_ui(ui, |ui| {
+            let first_model = models.iter().next().map(|m| m.to_string());
+            let first_range = saved_ranges.first().map(|r| r.name.clone());
+            if ui
+                .selectable_label(matches!(scope, CredentialScope::Model(_)), "Model")
+                .clicked()
+            {
+                *scope = CredentialScope::Model(first_model.unwrap_or_default());
+            }
+            if ui
+                .selectable_label(matches!(scope, CredentialScope::Range(_)), "Range")
+                .clicked()
+            {
+                *scope = CredentialScope::Range(first_range.unwrap_or_default());
+            }
+            if ui
+                .selectable_label(matches!(scope, CredentialScope::Miner(_)), "Miner")
+                .clicked()
+            {
+                *scope = CredentialScope::Miner(String::new());
+            }
+        });
+
+    match scope {
+        CredentialScope::Model(model) => {
+            egui::ComboBox::from_id_salt("new_credential_model")
+                .selected_text(model.as_str())
+                .width(160.0)
+                .show_ui(ui, |ui| {
+                    for option in models {
+                        ui.selectable_value(model, option.to_string(), *option);
+                    }
+                });
+        }
+        CredentialScope::Range(name) => {
+            egui::ComboBox::from_id_salt("new_credential_range")
+                .selected_text(name.as_str())
+                .width(160.0)
+                .show_ui(ui, |ui| {
+                    for range in saved_ranges {
+                        ui.selectable_value(name, range.name.clone(), &range.name);
+                    }
+                });
+        }
+        CredentialScope::Miner(identity) => {
+            ui.add(
+                egui::TextEdit::singleline(identity)
+                    .desired_width(160.0)
+                    .hint_text("MAC or IP"),
+            );
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn draw_credentials_window(
+    ctx: &egui::Context,
+    open: &mut bool,
+    miner_credentials: &mut Vec<MinerCredential>,
+    new_credential: &mut MinerCredential,
+    saved_ranges: &[SavedRange],
+    miners: &[MinerInfo],
+    selected_miners: &HashSet<String>,
+    auth_failures: &HashMap<String, String>,
+) {
+    egui::Window::new(egui::RichText::new("🔑 Credentials").size(12.0).monospace())
+        .id(egui::Id::new("credentials_window"))
+        .default_width(640.0)
+        .default_height(480.0)
+        .resizable(true)
+        .collapsible(true)
+        .open(open)
+        .show(ctx, |ui| {
+            ui.label(
+                egui::RichText::new(
+                    "Web UI logins used for data fetches, control actions and option applies. A miner entry beats a range entry, which beats a model entry; miners without one use the firmware defaults. Passwords are encrypted once a 🔐 SECRETS passphrase is set.",
+                )
+                .size(10.0)
+                .color(Color32::from_rgb(130, 130, 130)),
+            );
+            ui.add_space(6.0);
+
+            let models: BTreeSet<&str> = miners
+                .iter()
+                .map(|m| m.model.as_str())
+                .filter(|m| !m.is_empty())
+                .collect();
+            ui.horizontal(|ui| {
+                draw_scope_picker(ui, &mut new_credential.scope, &models, saved_ranges);
+                ui.add(
+                    egui::TextEdit::singleline(&mut new_credential.username)
+                        .desired_width(90.0)
+                        .hint_text("username"),
+                );
+                ui.add(
+                    egui::TextEdit::singleline(&mut new_credential.password)
+                        .password(true)
+                        .desired_width(110.0)
+                        .hint_text("password"),
+                );
+            });
+            ui.horizontal(|ui| {
+                let scope_taken = miner_credentials
+                    .iter()
+                    .any(|c| c.scope == new_credential.scope);
+                let ready = !new_credential.scope.value().trim().is_empty()
+                    && !new_credential.username.trim().is_empty()
+                    && !scope_taken;
+                if ui
+                    .add_enabled(ready, egui::Button::new("+ Add"))
+                    .on_disabled_hover_text("Pick a target and username; one entry per target")
+                    .clicked()
+                {
+                    miner_credentials.push(new_credential.clone());
+                    new_credential.password.clear();
+                }
+
+                let selected: Vec<&MinerInfo> = miners
+                    .iter()
+                    .filter(|m| selected_miners.contains(&m.ip))
+                    .collect();
+                if ui
+                    .add_enabled(
+                        !selected.is_empty() && !new_credential.username.trim().is_empty(),
+                        egui::Button::new(format!("+ Add for Selected ({})", selected.len())),
+                    )
+                    .on_hover_text("One miner entry per selected miner with this username and password")
+                    .clicked()
+                {
+                    for miner in selected {
+                        let scope = CredentialScope::Miner(miner.identity());
+                        miner_credentials.retain(|c| c.scope != scope);
+                        miner_credentials.push(MinerCredential {
+                            scope,
+                            ..new_credential.clone()
+                        });
+                    }
+                    new_credential.password.clear();
+                }
+            });
+            ui.add_space(6.0);
+
+            let mut in_use: HashMap<usize, usize> = HashMap::new();
+            for miner in miners {
+                if let Some(credential) = credentials::resolve(miner_credentials, miner, saved_ranges) {
+                    if let Some(idx) = miner_credentials
+                        .iter()
+                        .position(|c| std::ptr::eq(c, credential))
+                    {
+                        *in_use.entry(idx).or_default() += 1;
+                    }
+                }
+            }
+
+            let mut remove = None;
+            egui::ScrollArea::vertical()
+                .id_salt("credentials_scroll")
+                .max_height(260.0)
+                .auto_shrink([false, true])
+                .show(ui, |ui| {
+                    if miner_credentials.is_empty() {
+                        ui.label("No credentials yet");
+                    }
+                    egui::Grid::new("credentials_grid")
+                        .num_columns(6)
+                        .spacing([12.0, 4.0])
+                        .striped(true)
+                        .show(ui, |ui| {
+                            for (idx, credential) in miner_credentials.iter_mut().enumerate() {
+                                ui.label(
+                                    egui::RichText::new(credential.scope.kind_label())
+                                        .size(10.0)
+                                        .monospace(),
+                                );
+                                ui.label(
+                                    egui::RichText::new(credential.scope.value())
+                                        .size(10.0)
+                                        .monospace(),
+                                );
+                                ui.add(
+                                    egui::TextEdit::singleline(&mut credential.username)
+                                        .desired_width(90.0),
+                                );
+                                if credential.is_locked() {
+                                    ui.label(
+                                        egui::RichText::new("🔒 locked")
+                                            .size(10.0)
+                                            .color(Color32::from_rgb(255, 200, 100)),
+                                    )
+                                    .on_hover_text("Unlock 🔐 SECRETS to use or change this password");
+                                } else {
+                                    ui.add(
+                                        egui::TextEdit::singleline(&mut credential.password)
+                                            .password(true)
+                                            .desired_width(110.0),
+                                    );
+                                }
+                                ui.label(
+                                    egui::RichText::new(format!(
+                                        "{} miner(s)",
+                                        in_use.get(&idx).copied().unwrap_or(0)
+                                    ))
+                                    .size(10.0)
+                                    .color(Color32::from_rgb(160, 160, 160))
+                                    .monospace(),
+                                );
+                                if ui.small_button("🗑").on_hover_text("Delete").clicked() {
+                                    remove = Some(idx);
+                                }
+                                ui.end_row();
+                            }
+                        });
+                });
+            if let Some(idx) = remove {
+                miner_credentials.remove(idx);
+            }
+
+            if !auth_failures.is_empty() {
+                ui.separator();
+                ui.label(
+                    egui::RichText::new(format!(
+                        "AUTH FAILED ON {} MINER(S)",
+                        auth_failures.len()
+                    ))
+                    .size(11.0)
+                    .color(Color32::from_rgb(255, 120, 120))
+                    .strong()
+                    .monospace(),
+                );
+                let mut failures: Vec<(&String, &String)> = auth_failures.iter().collect();
+                failures.sort();
+                for (ip, error) in failures {
+                    ui.label(
+                        egui::RichText::new(format!("{ip}: {error}"))
+                            .size(10.0)
+                            .monospace(),
+                    );
+                }
+            }
+        });
+}
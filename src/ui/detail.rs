@@ -1,10 +1,16 @@
+use super::scan_control::draw_pool_groups_editor;
+use crate::actions::{self, MinerAction};
+use crate::audit::{self, AuditEntry, AuditLog};
+use crate::credentials;
 use crate::models::{
-    FanModeSelection, MetricsHistory, MinerInfo, MinerOptionSettings, MiningModeSelection,
-    PoolInput, RecordingState, TuningTargetSelection, EPIC_TUNING_ALGO_OPTIONS,
-    HASHRATE_ALGO_OPTIONS,
+    FanModeSelection, MetricsHistory, MinerInfo, MinerLocation, MinerOptionSettings,
+    MiningModeSelection, OptionsApplyPreview, RecordingState, TuningTargetSelection,
+    EPIC_TUNING_ALGO_OPTIONS, HASHRATE_ALGO_OPTIONS,
 };
 use crate::options;
-use asic_rs::MinerFactory;
+use crate::restarts::{self, RestartPhase};
+use crate::snapshots::{format_timestamp, SnapshotStore};
+use crate::workers::{self, worker_template_help, WorkerNameVars};
 use asic_rs_core::data::hashrate::{HashRate, HashRateUnit};
 use asic_rs_core::data::miner::MinerData;
 use eframe::egui;
@@ -26,6 +32,10 @@ pub fn draw_miner_detail_modal(
     global_options: &MinerOptionSettings,
     miner_option_overrides: Arc<Mutex<HashMap<String, MinerOptionSettings>>>,
     miner_options_prefill_pending: Arc<Mutex<HashSet<String>>>,
+    apply_preview: Arc<Mutex<Option<OptionsApplyPreview>>>,
+    option_snapshots: Arc<Mutex<SnapshotStore>>,
+    miner_locations: &mut HashMap<String, MinerLocation>,
+    audit_log: &Arc<Mutex<AuditLog>>,
 ) {
     let mut miners_to_close = Vec::new();
 
@@ -62,6 +72,8 @@ pub fn draw_miner_detail_modal(
                                 if let Some(data) = &miner.full_data {
                                     draw_basic_info(ui, data);
                                     ui.add_space(15.0);
+                                    draw_location_editor(ui, miner, miner_locations);
+                                    ui.add_space(15.0);
                                     draw_performance_info(ui, data);
                                     ui.add_space(15.0);
                                     draw_fans_info(ui, data);
@@ -93,6 +105,10 @@ pub fn draw_miner_detail_modal(
                                     global_options,
                                     Arc::clone(&miner_option_overrides),
                                     Arc::clone(&miner_options_prefill_pending),
+                                    Arc::clone(&apply_preview),
+                                    Arc::clone(&option_snapshots),
+                                    miner_locations,
+                                    audit_log,
                                 );
                             });
                     });
@@ -207,6 +223,46 @@ fn draw_basic_info(ui: &mut egui::Ui, data: &MinerData) {
         });
 }
 
+fn draw_location_editor(
+    ui: &mut egui::Ui,
+    miner: &MinerInfo,
+    miner_locations: &mut HashMap<String, MinerLocation>,
+) {
+    ui.heading("Location");
+    ui.separator();
+    ui.add_space(5.0);
+
+    let identity = miner.identity();
+    let mut location = miner_locations.get(&identity).cloned().unwrap_or_default();
+
+    ui.horizontal(|ui| {
+        ui.label(egui::RichText::new("Rack:").strong());
+        ui.add(
+            egui::TextEdit::singleline(&mut location.rack)
+                .desired_width(60.0)
+                .hint_text("R1"),
+        );
+        ui.label(egui::RichText::new("Slot:").strong());
+        ui.add(
+            egui::TextEdit::singleline(&mut location.slot)
+                .desired_width(60.0)
+                .hint_text("01"),
+        );
+        ui.label(egui::RichText::new("Tenant:").strong());
+        ui.add(
+            egui::TextEdit::singleline(&mut location.tenant)
+                .desired_width(120.0)
+                .hint_text("by rule"),
+        );
+    });
+
+    if location.is_empty() {
+        miner_locations.remove(&identity);
+    } else {
+        miner_locations.insert(identity, location);
+    }
+}
+
 fn draw_performance_info(ui: &mut egui::Ui, data: &MinerData) {
     ui.heading("Performance");
     ui.separator();
@@ -375,6 +431,10 @@ fn draw_controls_and_graphs(
     global_options: &MinerOptionSettings,
     miner_option_overrides: Arc<Mutex<HashMap<String, MinerOptionSettings>>>,
     miner_options_prefill_pending: Arc<Mutex<HashSet<String>>>,
+    apply_preview: Arc<Mutex<Option<OptionsApplyPreview>>>,
+    option_snapshots: Arc<Mutex<SnapshotStore>>,
+    miner_locations: &HashMap<String, MinerLocation>,
+    audit_log: &Arc<Mutex<AuditLog>>,
 ) {
     // Graph rolling update logic (every ~33ms for 30fps smooth rolling)
     let should_update_graph = if let Some(last_update) = detail_graph_update_times.get(&miner.ip) {
@@ -395,8 +455,7 @@ fn draw_controls_and_graphs(
         let ip = miner.ip.clone();
         let miners = Arc::clone(&miners_arc);
         crate::runtime::spawn(async move {
-            let factory = MinerFactory::new();
-            if let Ok(Some(miner_obj)) = factory.get_miner(ip.parse().unwrap()).await {
+            if let Ok(miner_obj) = credentials::connect(&ip).await {
                 let data = miner_obj.get_data().await;
 
                 let mut miners_list = miners.lock().unwrap();
@@ -500,8 +559,7 @@ fn draw_controls_and_graphs(
             let ip = miner.ip.clone();
             let miners = Arc::clone(&miners_arc);
             crate::runtime::spawn(async move {
-                let factory = MinerFactory::new();
-                if let Ok(Some(miner_obj)) = factory.get_miner(ip.parse().unwrap()).await {
+                if let Ok(miner_obj) = credentials::connect(&ip).await {
                     let data = miner_obj.get_data().await;
 
                     let mut miners_list = miners.lock().unwrap();
@@ -530,15 +588,20 @@ fn draw_controls_and_graphs(
 
     ui.add_space(10.0);
 
-    draw_miner_options_panel(
-        ui,
-        miner,
-        global_options,
-        Arc::clone(&miner_option_overrides),
-        Arc::clone(&miner_options_prefill_pending),
-    );
+    if !actions::is_read_only() {
+        draw_miner_options_panel(
+            ui,
+            miner,
+            global_options,
+            Arc::clone(&miner_option_overrides),
+            Arc::clone(&miner_options_prefill_pending),
+            &apply_preview,
+            &option_snapshots,
+            miner_locations,
+        );
 
-    ui.add_space(10.0);
+        ui.add_space(10.0);
+    }
 
     // Web interface and control buttons
     draw_control_buttons(
@@ -547,18 +610,23 @@ fn draw_controls_and_graphs(
         miners_arc,
         detail_refresh_times,
         recording_states,
+        audit_log,
     );
 
     // Draw graphs
     draw_metrics_graphs(ui, miner, detail_metrics_history);
 }
 
+#[allow(clippy::too_many_arguments)]
 fn draw_miner_options_panel(
     ui: &mut egui::Ui,
     miner: &MinerInfo,
     global_options: &MinerOptionSettings,
     miner_option_overrides: Arc<Mutex<HashMap<String, MinerOptionSettings>>>,
     miner_options_prefill_pending: Arc<Mutex<HashSet<String>>>,
+    apply_preview: &Arc<Mutex<Option<OptionsApplyPreview>>>,
+    option_snapshots: &Arc<Mutex<SnapshotStore>>,
+    miner_locations: &HashMap<String, MinerLocation>,
 ) {
     ui.heading("Miner Options");
     ui.add_space(4.0);
@@ -622,6 +690,7 @@ fn draw_miner_options_panel(
         capability_chip(ui, "TUNING", miner.capabilities.tuning_config);
         capability_chip(ui, "SCALING", miner.capabilities.scaling_config);
         capability_chip(ui, "POOLS", miner.capabilities.pools_config);
+        capability_chip(ui, "RESTART", miner.capabilities.restart);
     });
 
     ui.add_space(6.0);
@@ -859,66 +928,68 @@ fn draw_miner_options_panel(
     ui.horizontal(|ui| {
         ui.checkbox(&mut options_state.apply_pool_config, "");
         ui.label("Pools");
-        ui.add_enabled_ui(miner.capabilities.pools_config, |ui| {
-            ui.label("Group");
-            ui.add(
-                egui::TextEdit::singleline(&mut options_state.pool_group_name)
-                    .desired_width(100.0)
-                    .hint_text("Primary"),
-            );
-            ui.add(
-                egui::DragValue::new(&mut options_state.pool_group_quota)
-                    .range(1..=100)
-                    .suffix(" %"),
-            );
-        });
+        ui.label(
+            egui::RichText::new("groups fail over top to bottom")
+                .size(10.0)
+                .color(Color32::from_rgb(130, 130, 130)),
+        );
     });
 
     ui.add_enabled_ui(miner.capabilities.pools_config, |ui| {
-        let mut remove_idx: Option<usize> = None;
-        let can_remove_pool = options_state.pool_inputs.len() > 1;
-        for (idx, pool) in options_state.pool_inputs.iter_mut().enumerate() {
-            ui.horizontal(|ui| {
-                ui.label(format!("{}.", idx + 1));
-                ui.add(
-                    egui::TextEdit::singleline(&mut pool.url)
-                        .desired_width(260.0)
-                        .hint_text("stratum+tcp://pool.example.com:3333"),
-                );
-                ui.add(
-                    egui::TextEdit::singleline(&mut pool.username)
-                        .desired_width(180.0)
-                        .hint_text("wallet.worker"),
-                );
-                ui.add(
-                    egui::TextEdit::singleline(&mut pool.password)
-                        .desired_width(70.0)
-                        .hint_text("x"),
-                );
-                if ui
-                    .add_enabled(can_remove_pool, egui::Button::new("-"))
-                    .clicked()
-                {
-                    remove_idx = Some(idx);
-                }
-            });
-        }
-
-        if let Some(idx) = remove_idx {
-            options_state.pool_inputs.remove(idx);
-        }
+        draw_pool_groups_editor(
+            ui,
+            &format!("detail_{}", miner.ip),
+            &mut options_state.pool_groups,
+            [260.0, 180.0, 70.0],
+        );
 
         ui.horizontal(|ui| {
-            if ui.button("+ Add Pool").clicked() {
-                options_state.pool_inputs.push(PoolInput::default());
-            }
-            ui.label(
-                egui::RichText::new(format!("{} configured", options_state.pool_inputs.len()))
-                    .size(10.0)
-                    .color(Color32::from_rgb(130, 130, 130)),
-            );
+            ui.label("Account");
+            ui.add(
+                egui::TextEdit::singleline(&mut options_state.pool_account)
+                    .desired_width(120.0)
+                    .hint_text("account"),
+            )
+            .on_hover_text(worker_template_help());
+            ui.checkbox(&mut options_state.verify_pools, "Pre-flight")
+                .on_hover_text("Connect to each pool and send mining.subscribe before applying");
         });
 
+        // Show what templated worker names become on this miner
+        if let Some(data) = &miner.full_data {
+            let vars = WorkerNameVars::from_data(
+                &miner.ip,
+                data,
+                &options_state.pool_account,
+                miner_locations,
+            );
+            for (group_idx, group) in options_state.pool_groups.iter().enumerate() {
+                for (idx, pool) in group.pools.iter().enumerate() {
+                    if !workers::has_worker_template(&pool.username) {
+                        continue;
+                    }
+                    let label = format!("G{}.{}", group_idx + 1, idx + 1);
+                    let (text, color) =
+                        match workers::resolve_worker_template(&pool.username, &vars) {
+                            Ok(name) => (
+                                format!("{label} worker → {name}"),
+                                Color32::from_rgb(160, 160, 160),
+                            ),
+                            Err(err) => (
+                                format!("{label} worker: {err}"),
+                                Color32::from_rgb(255, 165, 0),
+                            ),
+                        };
+                    ui.label(
+                        egui::RichText::new(text)
+                            .size(10.0)
+                            .color(color)
+                            .monospace(),
+                    );
+                }
+            }
+        }
+
         if options_state.apply_pool_config {
             if let Some(message) = options_state.pool_validation_message() {
                 ui.label(
@@ -977,16 +1048,42 @@ fn draw_miner_options_panel(
             if !settings.has_any_enabled() {
                 eprintln!("✗ No option toggles are enabled for {}", ip);
             } else {
-                crate::runtime::spawn(async move {
-                    match options::apply_options_to_miner(ip.clone(), settings).await {
-                        Ok(applied) => {
-                            println!("✓ Applied options to {} ({})", ip, applied.join(", "));
-                        }
-                        Err(err) => {
-                            eprintln!("✗ {}", err);
-                        }
-                    }
-                });
+                options::start_options_preview(
+                    apply_preview,
+                    format!("Apply to {ip}"),
+                    vec![(ip, settings)],
+                    false,
+                    miner_locations,
+                    Vec::new(),
+                );
+            }
+        }
+
+        let latest_snapshot = option_snapshots
+            .lock()
+            .unwrap()
+            .latest_for(&miner.identity())
+            .cloned();
+        if let Some(snapshot) = latest_snapshot {
+            if ui
+                .add_enabled(
+                    snapshot.settings.has_any_enabled(),
+                    egui::Button::new("↩ Rollback Last Change"),
+                )
+                .on_hover_text(format!(
+                    "Restore settings captured {}",
+                    format_timestamp(snapshot.taken_at)
+                ))
+                .clicked()
+            {
+                options::start_options_preview(
+                    apply_preview,
+                    format!("Rollback: {}", miner.ip),
+                    vec![(miner.ip.clone(), snapshot.settings)],
+                    false,
+                    miner_locations,
+                    Vec::new(),
+                );
             }
         }
     });
@@ -997,14 +1094,39 @@ fn draw_miner_options_panel(
         .insert(miner.ip.clone(), options_state);
 }
 
+fn run_single_action(ip: &str, action: MinerAction, audit_log: &Arc<Mutex<AuditLog>>) {
+    let ip = ip.to_string();
+    let audit_log = Arc::clone(audit_log);
+    crate::runtime::spawn(async move {
+        let result = actions::run_action(&ip, action).await;
+        match &result {
+            Ok(()) => println!("✓ {} {ip}", action.label()),
+            Err(err) => eprintln!("✗ {err}"),
+        }
+        audit::record(
+            &audit_log,
+            AuditEntry::new("Detail", action.label(), vec![ip.clone()], String::new())
+                .with_results(&[(ip, result)]),
+        );
+    });
+}
+
 fn draw_control_buttons(
     ui: &mut egui::Ui,
     miner: &MinerInfo,
     miners_arc: Arc<Mutex<Vec<MinerInfo>>>,
     detail_refresh_times: &mut HashMap<String, Instant>,
     recording_states: &mut HashMap<String, RecordingState>,
+    audit_log: &Arc<Mutex<AuditLog>>,
 ) {
     let url = format!("http://{}", miner.ip);
+    let record = |action: &str, parameters: String, result: Result<(), String>| {
+        audit::record(
+            audit_log,
+            AuditEntry::new("Detail", action, vec![miner.ip.clone()], parameters)
+                .with_results(&[(miner.ip.clone(), result)]),
+        );
+    };
 
     // Quick Actions heading
     ui.heading("Quick Actions");
@@ -1030,95 +1152,125 @@ fn draw_control_buttons(
 
         ui.add_space(5.0);
 
-        // Miner control actions
-        if ui
-            .add_sized(
-                [120.0, 32.0],
-                egui::Button::new(
-                    egui::RichText::new("▶ START")
-                        .size(13.0)
-                        .color(Color32::WHITE),
+        // Viewer builds get no control actions
+        if !actions::is_read_only() {
+            if ui
+                .add_sized(
+                    [120.0, 32.0],
+                    egui::Button::new(
+                        egui::RichText::new("▶ START")
+                            .size(13.0)
+                            .color(Color32::WHITE),
+                    )
+                    .fill(Color32::from_rgb(100, 200, 100)),
                 )
-                .fill(Color32::from_rgb(100, 200, 100)),
-            )
-            .clicked()
-        {
-            let ip = miner.ip.clone();
-            crate::runtime::spawn(async move {
-                let factory = MinerFactory::new();
-                if let Ok(Some(miner)) = factory.get_miner(ip.parse().unwrap()).await {
-                    match miner.resume(None).await {
-                        Ok(_) => println!("✓ Started miner: {ip}"),
-                        Err(e) => eprintln!("✗ Failed to start {ip}: {e}"),
-                    }
-                }
-            });
-        }
+                .clicked()
+            {
+                run_single_action(&miner.ip, MinerAction::Resume, audit_log);
+            }
 
-        if ui
-            .add_sized(
-                [120.0, 32.0],
-                egui::Button::new(
-                    egui::RichText::new("■ STOP")
-                        .size(13.0)
-                        .color(Color32::WHITE),
+            if ui
+                .add_sized(
+                    [120.0, 32.0],
+                    egui::Button::new(
+                        egui::RichText::new("■ STOP")
+                            .size(13.0)
+                            .color(Color32::WHITE),
+                    )
+                    .fill(Color32::from_rgb(255, 100, 100)),
                 )
-                .fill(Color32::from_rgb(255, 100, 100)),
-            )
-            .clicked()
-        {
-            let ip = miner.ip.clone();
-            crate::runtime::spawn(async move {
-                let factory = MinerFactory::new();
-                if let Ok(Some(miner)) = factory.get_miner(ip.parse().unwrap()).await {
-                    match miner.pause(None).await {
-                        Ok(_) => println!("✓ Stopped miner: {ip}"),
-                        Err(e) => eprintln!("✗ Failed to stop {ip}: {e}"),
-                    }
-                }
-            });
-        }
+                .clicked()
+            {
+                run_single_action(&miner.ip, MinerAction::Pause, audit_log);
+            }
 
-        if ui
-            .add_sized(
-                [130.0, 32.0],
-                egui::Button::new(
-                    egui::RichText::new("💡 FAULT LIGHT")
-                        .size(13.0)
-                        .color(Color32::WHITE),
+            if ui
+                .add_sized(
+                    [130.0, 32.0],
+                    egui::Button::new(
+                        egui::RichText::new("💡 FAULT LIGHT")
+                            .size(13.0)
+                            .color(Color32::WHITE),
+                    )
+                    .fill(Color32::from_rgb(255, 165, 0)),
                 )
-                .fill(Color32::from_rgb(255, 165, 0)),
-            )
-            .clicked()
-        {
-            let ip = miner.ip.clone();
-            let current_state = miner.light_flashing;
-            let miners = Arc::clone(&miners_arc);
-            crate::runtime::spawn(async move {
-                let factory = MinerFactory::new();
-                if let Ok(Some(miner_obj)) = factory.get_miner(ip.parse().unwrap()).await {
+                .clicked()
+            {
+                let ip = miner.ip.clone();
+                let current_state = miner.light_flashing;
+                let miners = Arc::clone(&miners_arc);
+                let audit_log = Arc::clone(audit_log);
+                crate::runtime::spawn(async move {
                     let new_state = !current_state;
-                    match miner_obj.set_fault_light(new_state).await {
-                        Ok(_) => {
+                    let result = actions::set_fault_light(&ip, new_state).await;
+                    match &result {
+                        Ok(()) => {
                             println!(
                                 "✓ Set fault light to {}: {ip}",
                                 if new_state { "ON" } else { "OFF" }
                             );
-                            let data = miner_obj.get_data().await;
-                            let mut miners_list = miners.lock().unwrap();
-                            if let Some(existing) = miners_list.iter_mut().find(|m| m.ip == ip) {
-                                existing.full_data = Some(data);
+                            if let Ok(miner_obj) = credentials::connect(&ip).await {
+                                let data = miner_obj.get_data().await;
+                                let mut miners_list = miners.lock().unwrap();
+                                if let Some(existing) = miners_list.iter_mut().find(|m| m.ip == ip)
+                                {
+                                    existing.full_data = Some(data);
+                                }
                             }
                         }
-                        Err(e) => eprintln!("✗ Failed to set fault light on {ip}: {e}"),
+                        Err(err) => eprintln!("✗ {err}"),
                     }
-                }
-            });
-            // Force immediate refresh in UI
-            detail_refresh_times.insert(miner.ip.clone(), Instant::now());
+                    audit::record(
+                        &audit_log,
+                        AuditEntry::new(
+                            "Detail",
+                            "Set fault light",
+                            vec![ip.clone()],
+                            if new_state { "On" } else { "Off" }.to_string(),
+                        )
+                        .with_results(&[(ip, result)]),
+                    );
+                });
+                // Force immediate refresh in UI
+                detail_refresh_times.insert(miner.ip.clone(), Instant::now());
+            }
+
+            if ui
+                .add_enabled(
+                    miner.capabilities.restart,
+                    egui::Button::new(
+                        egui::RichText::new("⟳ RESTART")
+                            .size(13.0)
+                            .color(Color32::WHITE),
+                    )
+                    .fill(Color32::from_rgb(120, 140, 220))
+                    .min_size(egui::vec2(120.0, 32.0)),
+                )
+                .on_disabled_hover_text("This miner does not support restart")
+                .clicked()
+            {
+                run_single_action(&miner.ip, MinerAction::Restart, audit_log);
+            }
         }
     });
 
+    if let Some(status) = restarts::status(&miner.ip) {
+        let color = match status.phase {
+            RestartPhase::WaitingForReboot => Color32::from_rgb(255, 200, 100),
+            RestartPhase::BackOnline { .. } => Color32::from_rgb(150, 200, 150),
+            RestartPhase::TimedOut => Color32::from_rgb(255, 120, 120),
+        };
+        ui.add_space(4.0);
+        ui.label(
+            egui::RichText::new(format!(
+                "⟳ Last restart: {}",
+                status.describe(chrono::Local::now().timestamp())
+            ))
+            .size(11.0)
+            .color(color),
+        );
+    }
+
     // Recording controls - new row
     ui.add_space(15.0);
     ui.heading("Metrics Recording");
@@ -1154,6 +1306,11 @@ fn draw_control_buttons(
                 {
                     if let Some(recording) = recording_states.get_mut(&miner.ip) {
                         crate::recording::stop_recording(recording);
+                        record(
+                            "Stop recording",
+                            format!("{} rows", recording.row_count),
+                            Ok(()),
+                        );
                     }
                 }
 
@@ -1180,9 +1337,15 @@ fn draw_control_buttons(
                 match crate::recording::start_recording(miner) {
                     Ok(recording_state) => {
                         recording_states.insert(miner.ip.clone(), recording_state);
+                        record("Start recording", String::new(), Ok(()));
                     }
                     Err(e) => {
                         eprintln!("✗ Failed to start recording: {e}");
+                        record(
+                            "Start recording",
+                            String::new(),
+                            Err(format!("Failed to start recording: {e}")),
+                        );
                     }
                 }
             }
@@ -1209,13 +1372,19 @@ fn draw_control_buttons(
                             .save_file()
                         {
                             if let Some(recording) = recording_states.get(&miner.ip) {
-                                match crate::recording::export_recording(
+                                let result = crate::recording::export_recording(
                                     recording,
                                     path.to_str().unwrap(),
-                                ) {
+                                );
+                                match &result {
                                     Ok(_) => println!("✓ Exported recording to: {path:?}"),
                                     Err(e) => eprintln!("✗ Failed to export: {e}"),
                                 }
+                                record(
+                                    "Export recording",
+                                    path.display().to_string(),
+                                    result.map_err(|e| format!("Failed to export: {e}")),
+                                );
                             }
                         }
                     }
@@ -0,0 +1,129 @@
+use crate::energy::{StatementGrouping, StatementRow};
+use eframe::egui;
+use egui::Color32;
+
+pub fn draw_energy_window(
+    ctx: &egui::Context,
+    open: &mut bool,
+    months: &[String],
+    month: &mut String,
+    grouping: &mut StatementGrouping,
+    rows: &[StatementRow],
+    export_clicked: &mut bool,
+) {
+    egui::Window::new(
+        egui::RichText::new("🔋 Energy Accounting")
+            .size(12.0)
+            .monospace(),
+    )
+    .id(egui::Id::new("energy_window"))
+    .default_width(640.0)
+    .default_height(480.0)
+    .resizable(true)
+    .collapsible(true)
+    .open(open)
+    .show(ctx, |ui| {
+        ui.label(
+            egui::RichText::new(
+                "Power is integrated between consecutive scans. Time a miner was offline or reported no power is counted as a gap, not estimated. Tenants come from a miner's tenant tag or the tenant rules.",
+            )
+            .size(10.0)
+            .color(Color32::from_rgb(130, 130, 130)),
+        );
+        ui.add_space(6.0);
+
+        if months.is_empty() {
+            ui.label("No energy recorded yet. Samples are taken after every scan.");
+            return;
+        }
+
+        ui.horizontal(|ui| {
+            ui.label("Month");
+            egui::ComboBox::from_id_salt("energy_month")
+                .selected_text(month.as_str())
+                .show_ui(ui, |ui| {
+                    for option in months {
+                        ui.selectable_value(month, option.clone(), option);
+                    }
+                });
+            ui.separator();
+            ui.label("Per");
+            for option in [
+                StatementGrouping::Miner,
+                StatementGrouping::Range,
+                StatementGrouping::Tenant,
+            ] {
+                ui.selectable_value(grouping, option, option.label());
+            }
+            ui.separator();
+            if ui
+                .add_enabled(!rows.is_empty(), egui::Button::new("Export Statement"))
+                .clicked()
+            {
+                *export_clicked = true;
+            }
+        });
+
+        let total_kwh: f64 = rows.iter().map(|row| row.kwh).sum();
+        ui.label(
+            egui::RichText::new(format!("{total_kwh:.1} kWh in {month}"))
+                .size(11.0)
+                .color(Color32::from_rgb(200, 200, 200))
+                .monospace(),
+        );
+        ui.add_space(4.0);
+
+        egui::ScrollArea::vertical()
+            .id_salt("energy_scroll")
+            .auto_shrink([false, false])
+            .show(ui, |ui| {
+                egui::Grid::new("energy_grid")
+                    .num_columns(4)
+                    .spacing([16.0, 4.0])
+                    .striped(true)
+                    .show(ui, |ui| {
+                        for header in [grouping.label(), "MINERS", "kWh", "COVERAGE"] {
+                            ui.label(
+                                egui::RichText::new(header.to_uppercase())
+                                    .size(10.0)
+                                    .strong()
+                                    .monospace(),
+                            );
+                        }
+                        ui.end_row();
+
+                        for row in rows {
+                            ui.label(egui::RichText::new(&row.name).size(10.0).monospace());
+                            ui.label(
+                                egui::RichText::new(row.miners.to_string())
+                                    .size(10.0)
+                                    .monospace(),
+                            );
+                            ui.label(
+                                egui::RichText::new(format!("{:.2}", row.kwh))
+                                    .size(10.0)
+                                    .monospace(),
+                            );
+                            let coverage = row.coverage_percent();
+                            let color = if coverage < 95.0 {
+                                Color32::from_rgb(255, 200, 100)
+                            } else {
+                                Color32::from_rgb(150, 200, 150)
+                            };
+                            ui.label(
+                                egui::RichText::new(format!("{coverage:.1}%"))
+                                    .size(10.0)
+                                    .color(color)
+                                    .monospace(),
+                            )
+                            .on_hover_text(format!(
+                                "{:.1} h sampled, {:.1} h gaps",
+                                row.covered_secs as f64 / 3600.0,
+                                row.gap_secs as f64 / 3600.0
+                            ));
+                            ui.end_row();
+                        }
+                    });
+            });
+    });
+}
@@ -0,0 +1,232 @@
+use crate::firmware::{self, Compliance};
+use crate::models::MinerInfo;
+use eframe::egui;
+use egui::Color32;
+use std::collections::{BTreeMap, BTreeSet};
+
+fn draw_approved_versions(
+    ui: &mut egui::Ui,
+    miners: &[MinerInfo],
+    approved_firmware: &mut BTreeMap<String, String>,
+) {
+    let mut versions_by_model: BTreeMap<&str, BTreeSet<&str>> = BTreeMap::new();
+    for miner in miners.iter().filter(|m| !m.model.is_empty()) {
+        let versions = versions_by_model.entry(&miner.model).or_default();
+        if !miner.firmware_version.is_empty() {
+            versions.insert(&miner.firmware_version);
+        }
+    }
+    // Models with a policy stay editable while none of them are online
+    let configured: Vec<String> = approved_firmware.keys().cloned().collect();
+    let mut models: BTreeSet<String> = versions_by_model.keys().map(|m| m.to_string()).collect();
+    models.extend(configured);
+
+    egui::Grid::new("firmware_approved_grid")
+        .num_columns(3)
+        .spacing([12.0, 4.0])
+        .show(ui, |ui| {
+            for model in models {
+                ui.label(egui::RichText::new(&model).size(10.0).monospace());
+                let version = approved_firmware.entry(model.clone()).or_default();
+                ui.add(
+                    egui::TextEdit::singleline(version)
+                        .desired_width(180.0)
+                        .hint_text("any version"),
+                );
+                let observed = versions_by_model.get(model.as_str());
+                ui.add_enabled_ui(observed.is_some_and(|v| !v.is_empty()), |ui| {
+                    egui::ComboBox::from_id_salt(("firmware_seen", &model))
+                        .selected_text("seen")
+                        .width(70.0)
+                        .show_ui(ui, |ui| {
+                            for option in observed.into_iter().flatten() {
+                                ui.selectable_value(version, option.to_string(), *option);
+                            }
+                        });
+                });
+                ui.end_row();
+            }
+        });
+    approved_firmware.retain(|_, version| !version.trim().is_empty());
+}
+
+pub fn draw_firmware_window(
+    ctx: &egui::Context,
+    open: &mut bool,
+    miners: &[MinerInfo],
+    approved_firmware: &mut BTreeMap<String, String>,
+    select_ips: &mut Option<Vec<String>>,
+    export_clicked: &mut bool,
+) {
+    egui::Window::new(egui::RichText::new("🧬 Firmware").size(12.0).monospace())
+        .id(egui::Id::new("firmware_window"))
+        .default_width(760.0)
+        .default_height(560.0)
+        .resizable(true)
+        .collapsible(true)
+        .open(open)
+        .show(ctx, |ui| {
+            let groups = firmware::firmware_inventory(miners, approved_firmware);
+            let outdated = firmware::non_compliant(miners, approved_firmware);
+            let unpolicied = groups
+                .iter()
+                .filter(|g| g.compliance == Compliance::NoPolicy)
+                .map(|g| g.ips.len())
+                .sum::<usize>();
+
+            ui.label(
+                egui::RichText::new(format!(
+                    "{} miners  •  {} firmware groups  •  {} not on the approved version  •  {} without a policy",
+                    miners.len(),
+                    groups.len(),
+                    outdated.len(),
+                    unpolicied
+                ))
+                .size(11.0)
+                .color(Color32::from_rgb(200, 200, 200))
+                .monospace(),
+            );
+            ui.add_space(6.0);
+
+            egui::CollapsingHeader::new("Approved versions")
+                .id_salt("firmware_approved")
+                .default_open(approved_firmware.is_empty())
+                .show(ui, |ui| {
+                    ui.label(
+                        egui::RichText::new(
+                            "Miners of a model running anything else are listed as non-compliant. Leave blank to accept any version.",
+                        )
+                        .size(10.0)
+                        .color(Color32::from_rgb(130, 130, 130)),
+                    );
+                    draw_approved_versions(ui, miners, approved_firmware);
+                });
+
+            ui.separator();
+            ui.label(
+                egui::RichText::new("INVENTORY")
+                    .size(11.0)
+                    .strong()
+                    .monospace(),
+            );
+            egui::ScrollArea::vertical()
+                .id_salt("firmware_inventory_scroll")
+                .max_height(220.0)
+                .auto_shrink([false, true])
+                .show(ui, |ui| {
+                    egui::Grid::new("firmware_inventory_grid")
+                        .num_columns(5)
+                        .spacing([16.0, 4.0])
+                        .striped(true)
+                        .show(ui, |ui| {
+                            for header in ["Model", "Firmware", "Control Board", "Miners", ""] {
+                                ui.label(egui::RichText::new(header).size(10.0).strong());
+                            }
+                            ui.end_row();
+
+                            let or_dash = |value: &str| {
+                                if value.is_empty() { "-".to_string() } else { value.to_string() }
+                            };
+                            for group in &groups {
+                                ui.label(egui::RichText::new(or_dash(&group.model)).size(10.0).monospace());
+                                ui.label(
+                                    egui::RichText::new(or_dash(&group.firmware_version))
+                                        .size(10.0)
+                                        .monospace(),
+                                );
+                                ui.label(
+                                    egui::RichText::new(or_dash(&group.control_board))
+                                        .size(10.0)
+                                        .monospace(),
+                                );
+                                ui.label(
+                                    egui::RichText::new(group.ips.len().to_string())
+                                        .size(10.0)
+                                        .monospace(),
+                                );
+                                let (text, color) = match &group.compliance {
+                                    Compliance::Approved => {
+                                        ("✓ approved".to_string(), Color32::from_rgb(150, 200, 150))
+                                    }
+                                    Compliance::NotApproved { approved } => (
+                                        format!("✗ approved is {approved}"),
+                                        Color32::from_rgb(255, 120, 120),
+                                    ),
+                                    Compliance::NoPolicy => {
+                                        ("no policy".to_string(), Color32::from_rgb(130, 130, 130))
+                                    }
+                                };
+                                ui.label(egui::RichText::new(text).size(10.0).color(color));
+                                ui.end_row();
+                            }
+                        });
+                });
+
+            ui.separator();
+            ui.horizontal(|ui| {
+                ui.label(
+                    egui::RichText::new(format!("NON-COMPLIANT ({})", outdated.len()))
+                        .size(11.0)
+                        .strong()
+                        .monospace(),
+                );
+                if ui
+                    .add_enabled(!outdated.is_empty(), egui::Button::new("Select Non-compliant"))
+                    .on_hover_text("Select these miners in the main table")
+                    .clicked()
+                {
+                    *select_ips = Some(outdated.iter().map(|(m, _)| m.ip.clone()).collect());
+                }
+                if ui
+                    .add_enabled(!outdated.is_empty(), egui::Button::new("Export CSV"))
+                    .on_hover_text("Non-compliant miners with rack and slot, for upgrade batches")
+                    .clicked()
+                {
+                    *export_clicked = true;
+                }
+            });
+            egui::ScrollArea::vertical()
+                .id_salt("firmware_outdated_scroll")
+                .auto_shrink([false, false])
+                .show(ui, |ui| {
+                    if outdated.is_empty() {
+                        ui.label(
+                            egui::RichText::new("✓ Every miner with a policy runs its approved version")
+                                .size(11.0)
+                                .color(Color32::from_rgb(120, 200, 120)),
+                        );
+                        return;
+                    }
+                    egui::Grid::new("firmware_outdated_grid")
+                        .num_columns(4)
+                        .spacing([16.0, 4.0])
+                        .striped(true)
+                        .show(ui, |ui| {
+                            for (miner, approved) in &outdated {
+                                ui.label(egui::RichText::new(&miner.ip).size(10.0).monospace());
+                                ui.label(egui::RichText::new(&miner.model).size(10.0).monospace());
+                                ui.label(
+                                    egui::RichText::new(format!(
+                                        "{} → {approved}",
+                                        if miner.firmware_version.is_empty() {
+                                            "unknown"
+                                        } else {
+                                            &miner.firmware_version
+                                        }
+                                    ))
+                                    .size(10.0)
+                                    .color(Color32::from_rgb(255, 200, 100))
+                                    .monospace(),
+                                );
+                                ui.label(
+                                    egui::RichText::new(&miner.control_board)
+                                        .size(10.0)
+                                        .color(Color32::from_rgb(130, 130, 130))
+                                        .monospace(),
+                                );
+                                ui.end_row();
+                            }
+                        });
+                });
+        });
+}
@@ -0,0 +1,200 @@
+use crate::health::{HealthSort, HealthThresholds, MinerHealth};
+use eframe::egui;
+use egui::Color32;
+
+fn board_summary(entry: &MinerHealth) -> String {
+    entry
+        .boards
+        .iter()
+        .map(|board| {
+            let chips = match (board.working_chips, board.expected_chips) {
+                (Some(working), Some(expected)) => format!("{working}/{expected}"),
+                (Some(working), None) => working.to_string(),
+                _ => "?".to_string(),
+            };
+            let temp = board
+                .temperature_c
+                .map(|t| format!(" {t:.0}°"))
+                .unwrap_or_default();
+            format!("B{} {chips}{temp}", board.position + 1)
+        })
+        .collect::<Vec<_>>()
+        .join("  ")
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn draw_health_window(
+    ctx: &egui::Context,
+    open: &mut bool,
+    entries: &[MinerHealth],
+    thresholds: &mut HealthThresholds,
+    sort: &mut HealthSort,
+    only_candidates: &mut bool,
+    select_ips: &mut Option<Vec<String>>,
+    export_clicked: &mut bool,
+) {
+    egui::Window::new(egui::RichText::new("🔬 Chip Health").size(12.0).monospace())
+        .id(egui::Id::new("health_window"))
+        .default_width(820.0)
+        .default_height(520.0)
+        .resizable(true)
+        .collapsible(true)
+        .open(open)
+        .show(ctx, |ui| {
+            let candidates: Vec<&MinerHealth> = entries
+                .iter()
+                .filter(|e| !e.repair_reasons(thresholds).is_empty())
+                .collect();
+            let missing_total: u32 = entries.iter().map(|e| e.missing_chips).sum();
+
+            ui.label(
+                egui::RichText::new(format!(
+                    "{} miners  •  {} missing chips  •  {} repair candidates",
+                    entries.len(),
+                    missing_total,
+                    candidates.len()
+                ))
+                .size(11.0)
+                .color(Color32::from_rgb(200, 200, 200))
+                .monospace(),
+            );
+            ui.label(
+                egui::RichText::new(
+                    "Hashrate deviation is measured against the median of the same model",
+                )
+                .size(10.0)
+                .color(Color32::from_rgb(130, 130, 130)),
+            );
+            ui.add_space(6.0);
+
+            ui.horizontal(|ui| {
+                ui.label("Flag at");
+                ui.add(
+                    egui::DragValue::new(&mut thresholds.min_missing_chips)
+                        .range(1..=500)
+                        .speed(1)
+                        .suffix(" missing"),
+                );
+                ui.label("or spread >");
+                ui.add(
+                    egui::DragValue::new(&mut thresholds.max_temp_spread_c)
+                        .range(1.0..=60.0)
+                        .speed(0.5)
+                        .suffix(" °C"),
+                );
+                ui.label("or hashrate <");
+                ui.add(
+                    egui::DragValue::new(&mut thresholds.max_hashrate_shortfall_percent)
+                        .range(1.0..=90.0)
+                        .speed(0.5)
+                        .prefix("-")
+                        .suffix(" %"),
+                );
+            });
+            ui.horizontal(|ui| {
+                ui.label("Rank by");
+                ui.selectable_value(sort, HealthSort::MissingChips, "Missing chips");
+                ui.selectable_value(sort, HealthSort::TempSpread, "Temp spread");
+                ui.selectable_value(sort, HealthSort::HashrateDeviation, "Hashrate deviation");
+                ui.separator();
+                ui.checkbox(only_candidates, "Only repair candidates");
+            });
+            ui.horizontal(|ui| {
+                if ui
+                    .add_enabled(
+                        !candidates.is_empty(),
+                        egui::Button::new("Select Repair Candidates"),
+                    )
+                    .clicked()
+                {
+                    *select_ips = Some(candidates.iter().map(|e| e.ip.clone()).collect());
+                }
+                if ui
+                    .add_enabled(
+                        !candidates.is_empty(),
+                        egui::Button::new("Export Repair List"),
+                    )
+                    .on_hover_text("CSV with one row per board, including rack and slot")
+                    .clicked()
+                {
+                    *export_clicked = true;
+                }
+            });
+            ui.add_space(6.0);
+
+            egui::ScrollArea::vertical()
+                .id_salt("health_scroll")
+                .auto_shrink([false, false])
+                .show(ui, |ui| {
+                    egui::Grid::new("health_grid")
+                        .num_columns(6)
+                        .spacing([14.0, 4.0])
+                        .striped(true)
+                        .show(ui, |ui| {
+                            for header in
+                                ["IP", "MODEL", "MISSING", "SPREAD", "VS MEDIAN", "BOARDS"]
+                            {
+                                ui.label(
+                                    egui::RichText::new(header).size(10.0).strong().monospace(),
+                                );
+                            }
+                            ui.end_row();
+
+                            for entry in entries {
+                                let reasons = entry.repair_reasons(thresholds);
+                                if *only_candidates && reasons.is_empty() {
+                                    continue;
+                                }
+                                let color = if reasons.is_empty() {
+                                    Color32::from_rgb(200, 200, 200)
+                                } else {
+                                    Color32::from_rgb(255, 120, 120)
+                                };
+
+                                let ip_label = ui.label(
+                                    egui::RichText::new(&entry.ip)
+                                        .size(10.0)
+                                        .color(color)
+                                        .monospace(),
+                                );
+                                if !reasons.is_empty() {
+                                    ip_label.on_hover_text(reasons.join("\n"));
+                                }
+                                ui.label(egui::RichText::new(&entry.model).size(10.0).monospace());
+                                ui.label(
+                                    egui::RichText::new(entry.missing_chips.to_string())
+                                        .size(10.0)
+                                        .monospace(),
+                                );
+                                ui.label(
+                                    egui::RichText::new(
+                                        entry
+                                            .temp_spread_c
+                                            .map(|s| format!("{s:.1}°C"))
+                                            .unwrap_or_else(|| "-".to_string()),
+                                    )
+                                    .size(10.0)
+                                    .monospace(),
+                                );
+                                ui.label(
+                                    egui::RichText::new(
+                                        entry
+                                            .hashrate_deviation_percent
+                                            .map(|d| format!("{d:+.1}%"))
+                                            .unwrap_or_else(|| "-".to_string()),
+                                    )
+                                    .size(10.0)
+                                    .monospace(),
+                                );
+                                ui.label(
+                                    egui::RichText::new(board_summary(entry))
+                                        .size(10.0)
+                                        .color(Color32::from_rgb(160, 160, 160))
+                                        .monospace(),
+                                );
+                                ui.end_row();
+                            }
+                        });
+                });
+        });
+}
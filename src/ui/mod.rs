@@ -1,9 +1,49 @@
+pub mod apply_preview;
+pub mod audit;
+pub mod availability;
+pub mod baselines;
+pub mod credentials;
 pub mod detail;
+pub mod energy;
+pub mod firmware;
+pub mod health;
+pub mod pool_audit;
+pub mod power_cap;
+pub mod profiles;
+pub mod profitability;
+pub mod rollout;
+pub mod safety;
 pub mod scan_control;
+pub mod schedule;
+pub mod secrets;
+pub mod snapshots;
 pub mod stats;
 pub mod table;
+pub mod tenants;
+pub mod thermal;
+pub mod watchdog;
 
+pub use apply_preview::draw_apply_preview_window;
+pub use audit::draw_audit_window;
+pub use availability::draw_availability_window;
+pub use baselines::draw_baselines_window;
+pub use credentials::draw_credentials_window;
 pub use detail::draw_miner_detail_modal;
+pub use energy::draw_energy_window;
+pub use firmware::draw_firmware_window;
+pub use health::draw_health_window;
+pub use pool_audit::draw_pool_audit_window;
+pub use power_cap::draw_power_cap_window;
+pub use profiles::draw_profiles_window;
+pub use profitability::draw_profitability_window;
+pub use rollout::draw_rollout_window;
+pub use safety::{draw_bulk_confirmation_window, draw_safety_window};
 pub use scan_control::{draw_global_options_card, draw_scan_and_ranges_card, ScanControlState};
+pub use schedule::draw_schedule_window;
+pub use secrets::draw_secrets_window;
+pub use snapshots::{draw_snapshots_window, RollbackRequest};
 pub use stats::draw_stats_card;
 pub use table::draw_miners_table;
+pub use tenants::draw_tenants_window;
+pub use thermal::draw_thermal_window;
+pub use watchdog::draw_watchdog_window;
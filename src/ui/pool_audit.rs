@@ -0,0 +1,136 @@
+use crate::pool_audit::{PoolAuditEntry, PoolAuditIssue};
+use eframe::egui;
+use egui::Color32;
+
+fn issue_color(issue: &PoolAuditIssue) -> Color32 {
+    match issue {
+        PoolAuditIssue::NoPolicy | PoolAuditIssue::NoPoolData => Color32::from_rgb(130, 130, 130),
+        PoolAuditIssue::OnBackupPool(_) => Color32::from_rgb(255, 200, 100),
+        PoolAuditIssue::DeadPool(_) | PoolAuditIssue::PolicyError(_) => {
+            Color32::from_rgb(255, 165, 0)
+        }
+        _ => Color32::from_rgb(255, 120, 120),
+    }
+}
+
+pub fn draw_pool_audit_window(
+    ctx: &egui::Context,
+    open: &mut bool,
+    entries: &[PoolAuditEntry],
+    show_unaudited: &mut bool,
+    select_ips: &mut Option<Vec<String>>,
+) {
+    egui::Window::new(
+        egui::RichText::new("🧭 Pool Audit")
+            .size(12.0)
+            .monospace(),
+    )
+    .id(egui::Id::new("pool_audit_window"))
+    .default_width(760.0)
+    .default_height(480.0)
+    .resizable(true)
+    .collapsible(true)
+    .open(open)
+    .show(ctx, |ui| {
+        let count = |check: fn(&PoolAuditIssue) -> bool| {
+            entries
+                .iter()
+                .filter(|e| e.issues.iter().any(check))
+                .count()
+        };
+        let mismatched = count(PoolAuditIssue::is_mismatch);
+        let on_backup = count(|i| matches!(i, PoolAuditIssue::OnBackupPool(_)));
+        let dead = count(|i| matches!(i, PoolAuditIssue::DeadPool(_)));
+        let unaudited = count(|i| matches!(i, PoolAuditIssue::NoPolicy | PoolAuditIssue::NoPoolData));
+        let clean = entries.iter().filter(|e| e.issues.is_empty()).count();
+
+        ui.label(
+            egui::RichText::new(format!(
+                "{clean} ok  •  {mismatched} mismatched  •  {on_backup} on backup  •  {dead} with dead pools  •  {unaudited} not audited"
+            ))
+            .size(11.0)
+            .color(Color32::from_rgb(200, 200, 200))
+            .monospace(),
+        );
+        ui.label(
+            egui::RichText::new(
+                "Expected pools come from each miner's matching option profile",
+            )
+            .size(10.0)
+            .color(Color32::from_rgb(130, 130, 130)),
+        );
+
+        ui.add_space(6.0);
+        ui.horizontal(|ui| {
+            ui.checkbox(show_unaudited, "Show miners without a policy");
+
+            let affected: Vec<String> = entries
+                .iter()
+                .filter(|e| {
+                    e.issues.iter().any(|i| {
+                        !matches!(i, PoolAuditIssue::NoPolicy | PoolAuditIssue::NoPoolData)
+                    })
+                })
+                .map(|e| e.ip.clone())
+                .collect();
+            if ui
+                .add_enabled(
+                    !affected.is_empty(),
+                    egui::Button::new(format!("Select Affected ({})", affected.len())),
+                )
+                .on_hover_text("Select these miners in the main table")
+                .clicked()
+            {
+                *select_ips = Some(affected);
+            }
+        });
+
+        ui.add_space(6.0);
+        egui::ScrollArea::vertical()
+            .id_salt("pool_audit_scroll")
+            .auto_shrink([false, false])
+            .show(ui, |ui| {
+                egui::Grid::new("pool_audit_grid")
+                    .num_columns(4)
+                    .spacing([16.0, 4.0])
+                    .striped(true)
+                    .show(ui, |ui| {
+                        for entry in entries {
+                            let unaudited_only = entry.issues.iter().all(|i| {
+                                matches!(i, PoolAuditIssue::NoPolicy | PoolAuditIssue::NoPoolData)
+                            });
+                            if entry.issues.is_empty() || (unaudited_only && !*show_unaudited) {
+                                continue;
+                            }
+
+                            ui.label(egui::RichText::new(&entry.ip).size(10.0).monospace());
+                            ui.label(egui::RichText::new(&entry.model).size(10.0).monospace());
+                            ui.label(
+                                egui::RichText::new(entry.policy.as_deref().unwrap_or("-"))
+                                    .size(10.0)
+                                    .color(Color32::from_rgb(100, 200, 255))
+                                    .monospace(),
+                            );
+                            ui.vertical(|ui| {
+                                for issue in &entry.issues {
+                                    ui.label(
+                                        egui::RichText::new(issue.describe())
+                                            .size(10.0)
+                                            .color(issue_color(issue)),
+                                    );
+                                }
+                            });
+                            ui.end_row();
+                        }
+                    });
+
+                if clean == entries.len() && !entries.is_empty() {
+                    ui.label(
+                        egui::RichText::new("✓ Every miner matches its pool policy")
+                            .size(11.0)
+                            .color(Color32::from_rgb(120, 200, 120)),
+                    );
+                }
+            });
+    });
+}
@@ -0,0 +1,210 @@
+use crate::power_cap::{CurtailmentKind, PowerCapSettings, PowerCapState};
+use crate::snapshots::format_timestamp;
+use eframe::egui;
+use egui::Color32;
+
+pub fn draw_power_cap_window(
+    ctx: &egui::Context,
+    open: &mut bool,
+    settings: &mut PowerCapSettings,
+    state: &PowerCapState,
+    fleet_watts: f64,
+    on_release_all_clicked: &mut bool,
+) {
+    egui::Window::new(egui::RichText::new("⚡ Site Power Cap").size(12.0).monospace())
+        .id(egui::Id::new("power_cap_window"))
+        .default_width(620.0)
+        .default_height(520.0)
+        .resizable(true)
+        .collapsible(true)
+        .open(open)
+        .show(ctx, |ui| {
+            ui.label(
+                egui::RichText::new(
+                    "Checked after every scan. Over the cap, the least efficient miners step down first; they come back once the fleet is below cap minus margin.",
+                )
+                .size(10.0)
+                .color(Color32::from_rgb(130, 130, 130)),
+            );
+            ui.add_space(6.0);
+
+            egui::Grid::new("power_cap_settings_grid")
+                .num_columns(2)
+                .spacing([12.0, 6.0])
+                .show(ui, |ui| {
+                    ui.label("Enabled");
+                    ui.checkbox(&mut settings.enabled, "");
+                    ui.end_row();
+
+                    ui.label("Site cap");
+                    ui.add(
+                        egui::DragValue::new(&mut settings.cap_watts)
+                            .range(1000.0..=100_000_000.0)
+                            .speed(500.0)
+                            .suffix(" W"),
+                    );
+                    ui.end_row();
+
+                    ui.label("Restore margin");
+                    ui.add(
+                        egui::DragValue::new(&mut settings.restore_margin_watts)
+                            .range(0.0..=10_000_000.0)
+                            .speed(100.0)
+                            .suffix(" W"),
+                    );
+                    ui.end_row();
+
+                    ui.label("Step per miner");
+                    ui.add(
+                        egui::DragValue::new(&mut settings.step_watts)
+                            .range(25.0..=5000.0)
+                            .speed(25.0)
+                            .suffix(" W"),
+                    );
+                    ui.end_row();
+
+                    ui.label("Lowest power limit");
+                    ui.add(
+                        egui::DragValue::new(&mut settings.min_power_limit_watts)
+                            .range(100.0..=9000.0)
+                            .speed(25.0)
+                            .suffix(" W"),
+                    );
+                    ui.end_row();
+
+                    ui.label("Pause at floor");
+                    ui.checkbox(&mut settings.allow_pause, "")
+                        .on_hover_text("Pause miners that cannot be limited any further");
+                    ui.end_row();
+                });
+
+            ui.add_space(8.0);
+            let fraction = if settings.cap_watts > 0.0 {
+                (fleet_watts / settings.cap_watts) as f32
+            } else {
+                0.0
+            };
+            let fill = if fleet_watts > settings.cap_watts {
+                Color32::from_rgb(255, 120, 120)
+            } else {
+                Color32::from_rgb(100, 160, 100)
+            };
+            ui.add(
+                egui::ProgressBar::new(fraction.min(1.0))
+                    .fill(fill)
+                    .text(format!(
+                        "{:.1} / {:.1} kW",
+                        fleet_watts / 1000.0,
+                        settings.cap_watts / 1000.0
+                    )),
+            );
+            if state.in_flight {
+                ui.label(
+                    egui::RichText::new("⏳ Applying power cap changes...")
+                        .size(10.0)
+                        .color(Color32::from_rgb(100, 200, 255)),
+                );
+            }
+
+            ui.add_space(8.0);
+            ui.horizontal(|ui| {
+                ui.label(
+                    egui::RichText::new(format!("CURTAILED ({})", state.curtailed.len()))
+                        .size(11.0)
+                        .strong()
+                        .monospace(),
+                );
+                if ui
+                    .add_enabled(
+                        !state.curtailed.is_empty() && !state.in_flight,
+                        egui::Button::new("Release All"),
+                    )
+                    .on_hover_text("Restore every curtailed miner now, ignoring the cap")
+                    .clicked()
+                {
+                    *on_release_all_clicked = true;
+                }
+            });
+
+            egui::ScrollArea::vertical()
+                .id_salt("power_cap_curtailed_scroll")
+                .max_height(160.0)
+                .auto_shrink([false, true])
+                .show(ui, |ui| {
+                    let mut curtailed: Vec<_> = state.curtailed.values().collect();
+                    curtailed.sort_by(|a, b| a.ip.cmp(&b.ip));
+
+                    egui::Grid::new("power_cap_curtailed_grid")
+                        .num_columns(4)
+                        .spacing([16.0, 4.0])
+                        .striped(true)
+                        .show(ui, |ui| {
+                            for curtailment in curtailed {
+                                let status = match curtailment.kind {
+                                    CurtailmentKind::PowerLimited(watts) => {
+                                        format!("limited to {watts:.0} W")
+                                    }
+                                    CurtailmentKind::Paused { .. } => "paused".to_string(),
+                                };
+                                ui.label(
+                                    egui::RichText::new(&curtailment.ip).size(10.0).monospace(),
+                                );
+                                ui.label(
+                                    egui::RichText::new(status)
+                                        .size(10.0)
+                                        .color(Color32::from_rgb(255, 200, 100))
+                                        .monospace(),
+                                );
+                                ui.label(
+                                    egui::RichText::new(format!(
+                                        "was {:.0} W",
+                                        curtailment.baseline_watts
+                                    ))
+                                    .size(10.0)
+                                    .monospace(),
+                                );
+                                ui.label(
+                                    egui::RichText::new(
+                                        curtailment
+                                            .efficiency_w_th
+                                            .map(|e| format!("{e:.1} W/TH"))
+                                            .unwrap_or_else(|| "-".to_string()),
+                                    )
+                                    .size(10.0)
+                                    .monospace(),
+                                );
+                                ui.end_row();
+                            }
+                        });
+                });
+
+            ui.add_space(8.0);
+            ui.label(egui::RichText::new("ACTIVITY").size(11.0).strong().monospace());
+            egui::ScrollArea::vertical()
+                .id_salt("power_cap_events_scroll")
+                .max_height(160.0)
+                .auto_shrink([false, true])
+                .show(ui, |ui| {
+                    if state.events.is_empty() {
+                        ui.label("No power cap activity this session");
+                    }
+                    for event in state.events.iter().rev() {
+                        let color = if event.ok {
+                            Color32::from_rgb(200, 200, 200)
+                        } else {
+                            Color32::from_rgb(255, 120, 120)
+                        };
+                        ui.label(
+                            egui::RichText::new(format!(
+                                "{}  {}",
+                                format_timestamp(event.at),
+                                event.message
+                            ))
+                            .size(10.0)
+                            .color(color)
+                            .monospace(),
+                        );
+                    }
+                });
+        });
+}
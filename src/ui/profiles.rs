@@ -0,0 +1,241 @@
+use crate::models::{
+    EpicFirmwareFilter, MinerInfo, MinerOptionSettings, OptionProfile, ProfileMatchRules,
+    SavedRange,
+};
+use crate::profiles;
+use eframe::egui;
+use egui::Color32;
+
+fn epic_filter_label(filter: EpicFirmwareFilter) -> &'static str {
+    match filter {
+        EpicFirmwareFilter::Any => "Any firmware",
+        EpicFirmwareFilter::EpicOnly => "ePIC only",
+        EpicFirmwareFilter::StockOnly => "Non-ePIC only",
+    }
+}
+
+pub fn draw_match_rules_editor(
+    ui: &mut egui::Ui,
+    id_salt: &str,
+    rules: &mut ProfileMatchRules,
+    saved_ranges: &[SavedRange],
+) {
+    ui.horizontal(|ui| {
+        ui.label(egui::RichText::new("Model").size(10.0));
+        ui.add(
+            egui::TextEdit::singleline(&mut rules.model_contains)
+                .desired_width(90.0)
+                .hint_text("S21"),
+        );
+        ui.label(egui::RichText::new("Firmware").size(10.0));
+        ui.add(
+            egui::TextEdit::singleline(&mut rules.firmware_contains)
+                .desired_width(90.0)
+                .hint_text("any"),
+        );
+
+        egui::ComboBox::from_id_salt(format!("{id_salt}_epic"))
+            .selected_text(epic_filter_label(rules.epic_firmware))
+            .show_ui(ui, |ui| {
+                for filter in [
+                    EpicFirmwareFilter::Any,
+                    EpicFirmwareFilter::EpicOnly,
+                    EpicFirmwareFilter::StockOnly,
+                ] {
+                    ui.selectable_value(
+                        &mut rules.epic_firmware,
+                        filter,
+                        epic_filter_label(filter),
+                    );
+                }
+            });
+
+        egui::ComboBox::from_id_salt(format!("{id_salt}_range"))
+            .selected_text(if rules.saved_range.is_empty() {
+                "Any range"
+            } else {
+                rules.saved_range.as_str()
+            })
+            .show_ui(ui, |ui| {
+                ui.selectable_value(&mut rules.saved_range, String::new(), "Any range");
+                for range in saved_ranges {
+                    ui.selectable_value(&mut rules.saved_range, range.name.clone(), &range.name);
+                }
+            });
+    });
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn draw_profiles_window(
+    ctx: &egui::Context,
+    open: &mut bool,
+    option_profiles: &mut Vec<OptionProfile>,
+    new_profile_name: &mut String,
+    global_options: &mut MinerOptionSettings,
+    saved_ranges: &[SavedRange],
+    miners: &[MinerInfo],
+    on_apply_matching_clicked: &mut bool,
+) {
+    egui::Window::new(
+        egui::RichText::new("🗂 Option Profiles")
+            .size(12.0)
+            .monospace(),
+    )
+    .id(egui::Id::new("option_profiles_window"))
+    .default_width(680.0)
+    .default_height(480.0)
+    .resizable(true)
+    .collapsible(true)
+    .open(open)
+    .show(ctx, |ui| {
+        ui.label(
+            egui::RichText::new(
+                "Each miner gets the first profile whose rules all match - empty rules match anything",
+            )
+            .size(10.0)
+            .color(Color32::from_rgb(130, 130, 130)),
+        );
+        ui.add_space(6.0);
+
+        ui.horizontal(|ui| {
+            ui.add(
+                egui::TextEdit::singleline(new_profile_name)
+                    .desired_width(180.0)
+                    .hint_text("profile name"),
+            );
+            let name = new_profile_name.trim().to_string();
+            let name_taken = option_profiles.iter().any(|p| p.name == name);
+            if ui
+                .add_enabled(
+                    !name.is_empty() && !name_taken,
+                    egui::Button::new("💾 Save Global Options as Profile"),
+                )
+                .clicked()
+            {
+                option_profiles.push(OptionProfile {
+                    name,
+                    rules: Default::default(),
+                    settings: global_options.clone(),
+                });
+                new_profile_name.clear();
+            }
+            if name_taken {
+                ui.label(
+                    egui::RichText::new("Name already used")
+                        .size(10.0)
+                        .color(Color32::from_rgb(255, 120, 120)),
+                );
+            }
+        });
+
+        ui.add_space(8.0);
+
+        let mut profile_to_remove: Option<usize> = None;
+        let mut profile_to_raise: Option<usize> = None;
+
+        egui::ScrollArea::vertical()
+            .id_salt("option_profiles_scroll")
+            .max_height(340.0)
+            .auto_shrink([false, true])
+            .show(ui, |ui| {
+                if option_profiles.is_empty() {
+                    ui.label("No profiles saved yet");
+                }
+
+                for (idx, profile) in option_profiles.iter_mut().enumerate() {
+                    let match_count = miners
+                        .iter()
+                        .filter(|m| profiles::rules_match(&profile.rules, m, saved_ranges))
+                        .count();
+
+                    egui::Frame::new()
+                        .fill(Color32::from_rgb(28, 28, 28))
+                        .stroke(egui::Stroke::new(1.0, Color32::from_rgb(60, 60, 60)))
+                        .corner_radius(4.0)
+                        .inner_margin(8.0)
+                        .show(ui, |ui| {
+                            ui.horizontal(|ui| {
+                                ui.label(
+                                    egui::RichText::new(format!("{}. {}", idx + 1, profile.name))
+                                        .size(11.0)
+                                        .color(Color32::from_rgb(100, 200, 255))
+                                        .strong()
+                                        .monospace(),
+                                );
+                                ui.label(
+                                    egui::RichText::new(format!("{match_count} miner(s) match"))
+                                        .size(10.0)
+                                        .color(Color32::from_rgb(160, 160, 160))
+                                        .monospace(),
+                                );
+
+                                ui.with_layout(
+                                    egui::Layout::right_to_left(egui::Align::Center),
+                                    |ui| {
+                                        if ui.small_button("🗑").on_hover_text("Delete").clicked() {
+                                            profile_to_remove = Some(idx);
+                                        }
+                                        if idx > 0
+                                            && ui
+                                                .small_button("▲")
+                                                .on_hover_text("Raise priority")
+                                                .clicked()
+                                        {
+                                            profile_to_raise = Some(idx);
+                                        }
+                                        if ui
+                                            .small_button("Update from Global")
+                                            .on_hover_text("Overwrite with the current global options")
+                                            .clicked()
+                                        {
+                                            profile.settings = global_options.clone();
+                                        }
+                                        if ui
+                                            .small_button("Load into Global")
+                                            .on_hover_text("Copy into the global options card for editing")
+                                            .clicked()
+                                        {
+                                            *global_options = profile.settings.clone();
+                                        }
+                                    },
+                                );
+                            });
+
+                            draw_match_rules_editor(
+                                ui,
+                                &format!("profile_{idx}"),
+                                &mut profile.rules,
+                                saved_ranges,
+                            );
+                        });
+                    ui.add_space(4.0);
+                }
+            });
+
+        if let Some(idx) = profile_to_remove {
+            option_profiles.remove(idx);
+        }
+        if let Some(idx) = profile_to_raise {
+            option_profiles.swap(idx - 1, idx);
+        }
+
+        ui.add_space(8.0);
+        ui.separator();
+        if ui
+            .add_enabled(
+                !option_profiles.is_empty() && !miners.is_empty(),
+                egui::Button::new(
+                    egui::RichText::new("Apply Matching Profiles to All")
+                        .size(11.0)
+                        .color(Color32::WHITE)
+                        .monospace(),
+                )
+                .fill(Color32::from_rgb(100, 160, 100))
+                .corner_radius(5.0),
+            )
+            .clicked()
+        {
+            *on_apply_matching_clicked = true;
+        }
+    });
+}
@@ -0,0 +1,343 @@
+use crate::profitability::{
+    EconomicsTotals, HashpriceSource, MinerEconomics, ProfitabilitySettings, TariffKind,
+    TariffPeriod,
+};
+use eframe::egui;
+use egui::Color32;
+
+fn margin_color(margin: f64) -> Color32 {
+    if margin < 0.0 {
+        Color32::from_rgb(255, 120, 120)
+    } else {
+        Color32::from_rgb(150, 200, 150)
+    }
+}
+
+fn draw_tariff_settings(ui: &mut egui::Ui, settings: &mut ProfitabilitySettings) {
+    ui.horizontal(|ui| {
+        ui.label("Tariff");
+        ui.selectable_value(&mut settings.tariff, TariffKind::Flat, "Flat");
+        ui.selectable_value(&mut settings.tariff, TariffKind::TimeOfUse, "Time of use");
+    });
+
+    match settings.tariff {
+        TariffKind::Flat => {
+            ui.horizontal(|ui| {
+                ui.label("Rate");
+                ui.add(
+                    egui::DragValue::new(&mut settings.flat_usd_per_kwh)
+                        .range(0.0..=2.0)
+                        .speed(0.001)
+                        .max_decimals(4)
+                        .prefix("$")
+                        .suffix(" /kWh"),
+                );
+            });
+        }
+        TariffKind::TimeOfUse => {
+            let mut remove = None;
+            egui::Grid::new("tariff_periods_grid")
+                .num_columns(5)
+                .spacing([10.0, 4.0])
+                .show(ui, |ui| {
+                    for (index, period) in settings.periods.iter_mut().enumerate() {
+                        ui.add(egui::TextEdit::singleline(&mut period.name).desired_width(80.0));
+                        ui.add(
+                            egui::DragValue::new(&mut period.start_hour)
+                                .range(0..=23)
+                                .suffix(":00"),
+                        );
+                        ui.add(
+                            egui::DragValue::new(&mut period.end_hour)
+                                .range(0..=24)
+                                .prefix("to ")
+                                .suffix(":00"),
+                        );
+                        ui.add(
+                            egui::DragValue::new(&mut period.usd_per_kwh)
+                                .range(0.0..=2.0)
+                                .speed(0.001)
+                                .max_decimals(4)
+                                .prefix("$")
+                                .suffix(" /kWh"),
+                        );
+                        if ui.small_button("✕").clicked() {
+                            remove = Some(index);
+                        }
+                        ui.end_row();
+                    }
+                });
+            if let Some(index) = remove {
+                settings.periods.remove(index);
+            }
+            ui.horizontal(|ui| {
+                if ui.button("+ Add Period").clicked() {
+                    settings.periods.push(TariffPeriod {
+                        name: format!("Period {}", settings.periods.len() + 1),
+                        start_hour: 0,
+                        end_hour: 6,
+                        usd_per_kwh: settings.base_usd_per_kwh,
+                    });
+                }
+                ui.label("Other hours");
+                ui.add(
+                    egui::DragValue::new(&mut settings.base_usd_per_kwh)
+                        .range(0.0..=2.0)
+                        .speed(0.001)
+                        .max_decimals(4)
+                        .prefix("$")
+                        .suffix(" /kWh"),
+                );
+            });
+        }
+    }
+    ui.label(
+        egui::RichText::new(format!(
+            "Effective average {:.4} $/kWh",
+            settings.usd_per_kw_day() / 24.0
+        ))
+        .size(10.0)
+        .color(Color32::from_rgb(130, 130, 130)),
+    );
+}
+
+fn draw_hashprice_settings(
+    ui: &mut egui::Ui,
+    settings: &mut ProfitabilitySettings,
+    hashprice: &Result<f64, String>,
+    reload_hashprice: &mut bool,
+) {
+    ui.horizontal(|ui| {
+        ui.label("Hashprice");
+        ui.selectable_value(
+            &mut settings.hashprice_source,
+            HashpriceSource::Manual,
+            "Manual",
+        );
+        ui.selectable_value(
+            &mut settings.hashprice_source,
+            HashpriceSource::File,
+            "From file",
+        );
+    });
+    match settings.hashprice_source {
+        HashpriceSource::Manual => {
+            ui.add(
+                egui::DragValue::new(&mut settings.hashprice_usd_per_th_day)
+                    .range(0.0..=10.0)
+                    .speed(0.0005)
+                    .max_decimals(5)
+                    .prefix("$")
+                    .suffix(" /TH/day"),
+            );
+        }
+        HashpriceSource::File => {
+            ui.horizontal(|ui| {
+                ui.add(
+                    egui::TextEdit::singleline(&mut settings.hashprice_file)
+                        .hint_text("path to hashprice file")
+                        .desired_width(260.0),
+                );
+                if ui.button("Browse").clicked() {
+                    if let Some(path) = rfd::FileDialog::new().pick_file() {
+                        settings.hashprice_file = path.display().to_string();
+                        *reload_hashprice = true;
+                    }
+                }
+                if ui
+                    .button("Reload")
+                    .on_hover_text("The file is also re-read after every scan")
+                    .clicked()
+                {
+                    *reload_hashprice = true;
+                }
+            });
+            match hashprice {
+                Ok(value) => ui.label(format!("Read ${value:.5} /TH/day")),
+                Err(err) => ui.colored_label(Color32::from_rgb(255, 120, 120), err),
+            };
+        }
+    }
+    ui.horizontal(|ui| {
+        ui.label("Pool fee");
+        ui.add(
+            egui::DragValue::new(&mut settings.pool_fee_percent)
+                .range(0.0..=20.0)
+                .speed(0.1)
+                .suffix(" %"),
+        );
+    });
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn draw_profitability_window(
+    ctx: &egui::Context,
+    open: &mut bool,
+    settings: &mut ProfitabilitySettings,
+    hashprice: &Result<f64, String>,
+    economics: &[MinerEconomics],
+    by_range: &[EconomicsTotals],
+    only_unprofitable: &mut bool,
+    reload_hashprice: &mut bool,
+    select_ips: &mut Option<Vec<String>>,
+) {
+    egui::Window::new(
+        egui::RichText::new("💲 Profitability")
+            .size(12.0)
+            .monospace(),
+    )
+    .id(egui::Id::new("profitability_window"))
+    .default_width(760.0)
+    .default_height(620.0)
+    .resizable(true)
+    .collapsible(true)
+    .open(open)
+    .show(ctx, |ui| {
+        ui.columns(2, |columns| {
+            draw_tariff_settings(&mut columns[0], settings);
+            draw_hashprice_settings(&mut columns[1], settings, hashprice, reload_hashprice);
+        });
+        ui.separator();
+
+        if hashprice.is_err() {
+            ui.label("Revenue needs a hashprice");
+            return;
+        }
+
+        ui.label(
+            egui::RichText::new("BY RANGE")
+                .size(11.0)
+                .strong()
+                .monospace(),
+        );
+        egui::Grid::new("profitability_ranges_grid")
+            .num_columns(5)
+            .spacing([16.0, 4.0])
+            .striped(true)
+            .show(ui, |ui| {
+                for header in ["RANGE", "MINERS", "REVENUE/DAY", "POWER/DAY", "MARGIN/DAY"] {
+                    ui.label(egui::RichText::new(header).size(10.0).strong().monospace());
+                }
+                ui.end_row();
+                for totals in by_range {
+                    ui.label(egui::RichText::new(&totals.name).size(10.0).monospace());
+                    ui.label(
+                        egui::RichText::new(if totals.unprofitable > 0 {
+                            format!("{} ({} losing)", totals.miners, totals.unprofitable)
+                        } else {
+                            totals.miners.to_string()
+                        })
+                        .size(10.0)
+                        .monospace(),
+                    );
+                    ui.label(
+                        egui::RichText::new(format!("${:.2}", totals.revenue_per_day))
+                            .size(10.0)
+                            .monospace(),
+                    );
+                    ui.label(
+                        egui::RichText::new(format!("${:.2}", totals.cost_per_day))
+                            .size(10.0)
+                            .monospace(),
+                    );
+                    ui.label(
+                        egui::RichText::new(format!("${:.2}", totals.margin_per_day()))
+                            .size(10.0)
+                            .color(margin_color(totals.margin_per_day()))
+                            .monospace(),
+                    );
+                    ui.end_row();
+                }
+            });
+
+        ui.add_space(8.0);
+        let unprofitable: Vec<String> = economics
+            .iter()
+            .filter(|e| e.is_unprofitable())
+            .map(|e| e.ip.clone())
+            .collect();
+        ui.horizontal(|ui| {
+            ui.label(
+                egui::RichText::new("BY MINER")
+                    .size(11.0)
+                    .strong()
+                    .monospace(),
+            );
+            ui.checkbox(only_unprofitable, "Only unprofitable");
+            if ui
+                .add_enabled(
+                    !unprofitable.is_empty(),
+                    egui::Button::new(format!("Select {} Unprofitable", unprofitable.len())),
+                )
+                .clicked()
+            {
+                *select_ips = Some(unprofitable.clone());
+            }
+        });
+
+        let mut rows: Vec<&MinerEconomics> = economics
+            .iter()
+            .filter(|e| !*only_unprofitable || e.is_unprofitable())
+            .collect();
+        rows.sort_by(|a, b| a.margin_per_day().total_cmp(&b.margin_per_day()));
+
+        egui::ScrollArea::vertical()
+            .id_salt("profitability_miners_scroll")
+            .auto_shrink([false, false])
+            .show(ui, |ui| {
+                egui::Grid::new("profitability_miners_grid")
+                    .num_columns(6)
+                    .spacing([16.0, 4.0])
+                    .striped(true)
+                    .show(ui, |ui| {
+                        for header in [
+                            "IP",
+                            "MODEL",
+                            "REVENUE/DAY",
+                            "POWER/DAY",
+                            "MARGIN/DAY",
+                            "BREAKEVEN",
+                        ] {
+                            ui.label(egui::RichText::new(header).size(10.0).strong().monospace());
+                        }
+                        ui.end_row();
+                        for entry in rows {
+                            let margin = entry.margin_per_day();
+                            ui.label(
+                                egui::RichText::new(&entry.ip)
+                                    .size(10.0)
+                                    .color(margin_color(margin))
+                                    .monospace(),
+                            );
+                            ui.label(egui::RichText::new(&entry.model).size(10.0).monospace());
+                            ui.label(
+                                egui::RichText::new(format!("${:.2}", entry.revenue_per_day))
+                                    .size(10.0)
+                                    .monospace(),
+                            );
+                            ui.label(
+                                egui::RichText::new(format!("${:.2}", entry.cost_per_day))
+                                    .size(10.0)
+                                    .monospace(),
+                            );
+                            ui.label(
+                                egui::RichText::new(format!("${margin:.2}"))
+                                    .size(10.0)
+                                    .color(margin_color(margin))
+                                    .monospace(),
+                            );
+                            ui.label(
+                                egui::RichText::new(format!(
+                                    "${:.4}/kWh",
+                                    entry.breakeven_usd_per_kwh
+                                ))
+                                .size(10.0)
+                                .monospace(),
+                            )
+                            .on_hover_text("Average power price at which this miner breaks even");
+                            ui.end_row();
+                        }
+                    });
+            });
+    });
+}
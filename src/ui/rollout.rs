@@ -0,0 +1,129 @@
+use crate::rollout::{RolloutAction, RolloutPhase, StagedRollout};
+use eframe::egui;
+use egui::Color32;
+use std::time::Instant;
+
+pub fn draw_rollout_window(
+    ctx: &egui::Context,
+    rollout: &StagedRollout,
+    action: &mut Option<RolloutAction>,
+) {
+    egui::Window::new(
+        egui::RichText::new(format!("🚦 Staged Rollout - {}", rollout.title))
+            .size(12.0)
+            .monospace(),
+    )
+    .id(egui::Id::new("staged_rollout_window"))
+    .default_width(520.0)
+    .resizable(true)
+    .collapsible(true)
+    .show(ctx, |ui| {
+        let wave_count = rollout.waves.len();
+        let wave_number = (rollout.current_wave + 1).min(wave_count);
+        let wave_label = if rollout.current_wave == 0 {
+            "canary wave".to_string()
+        } else {
+            format!("wave {wave_number}/{wave_count}")
+        };
+
+        ui.label(
+            egui::RichText::new(format!(
+                "{} miner(s) in {} wave(s)  •  soak {} min",
+                rollout.total_targets(),
+                wave_count,
+                rollout.settings.soak_secs / 60
+            ))
+            .size(11.0)
+            .color(Color32::from_rgb(160, 160, 160))
+            .monospace(),
+        );
+        ui.add_space(8.0);
+
+        let (status, color) = match &rollout.phase {
+            RolloutPhase::Applying => (
+                format!("⏳ Applying {wave_label}..."),
+                Color32::from_rgb(100, 200, 255),
+            ),
+            RolloutPhase::Soaking { until, .. } => {
+                let remaining = until.saturating_duration_since(Instant::now()).as_secs();
+                (
+                    format!(
+                        "⏱ Soaking {wave_label}: {}:{:02} remaining",
+                        remaining / 60,
+                        remaining % 60
+                    ),
+                    Color32::from_rgb(255, 200, 100),
+                )
+            }
+            RolloutPhase::Checking { .. } => (
+                format!("🔍 Checking {wave_label} for regressions..."),
+                Color32::from_rgb(100, 200, 255),
+            ),
+            RolloutPhase::Halted(_) => (
+                format!("⛔ Halted after {wave_label}"),
+                Color32::from_rgb(255, 120, 120),
+            ),
+            RolloutPhase::Completed => (
+                "✓ Rollout completed".to_string(),
+                Color32::from_rgb(120, 200, 120),
+            ),
+            RolloutPhase::Aborted => (
+                "✕ Rollout aborted".to_string(),
+                Color32::from_rgb(255, 165, 0),
+            ),
+        };
+
+        ui.label(
+            egui::RichText::new(status)
+                .size(12.0)
+                .color(color)
+                .strong()
+                .monospace(),
+        );
+
+        if let RolloutPhase::Halted(reason) = &rollout.phase {
+            ui.add_space(6.0);
+            egui::ScrollArea::vertical()
+                .id_salt("rollout_regressions_scroll")
+                .max_height(160.0)
+                .show(ui, |ui| {
+                    for line in reason.lines() {
+                        ui.label(
+                            egui::RichText::new(format!("• {line}"))
+                                .size(10.0)
+                                .color(Color32::from_rgb(255, 150, 150))
+                                .monospace(),
+                        );
+                    }
+                });
+            ui.label(
+                egui::RichText::new("Use 🕘 HISTORY to roll back the applied waves")
+                    .size(10.0)
+                    .color(Color32::from_rgb(130, 130, 130)),
+            );
+        }
+
+        ui.add_space(8.0);
+        ui.horizontal(|ui| {
+            if matches!(rollout.phase, RolloutPhase::Halted(_))
+                && rollout.current_wave + 1 < wave_count
+                && ui.button("Continue Anyway").clicked()
+            {
+                *action = Some(RolloutAction::Continue);
+            }
+
+            if rollout.is_finished() || matches!(rollout.phase, RolloutPhase::Halted(_)) {
+                if ui.button("Close").clicked() {
+                    *action = Some(RolloutAction::Dismiss);
+                }
+            } else if ui
+                .button(egui::RichText::new("✕ Abort").color(Color32::from_rgb(255, 100, 100)))
+                .clicked()
+            {
+                *action = Some(RolloutAction::Abort);
+            }
+        });
+    });
+
+    ctx.request_repaint_after(std::time::Duration::from_secs(1));
+}
@@ -0,0 +1,211 @@
+use crate::models::MinerInfo;
+use crate::safety::{BulkConfirmation, SafetySettings};
+use eframe::egui;
+use egui::Color32;
+use std::collections::HashSet;
+
+/// Sets `decision` to `Some(true)` when the operator confirms, `Some(false)` on cancel.
+pub fn draw_bulk_confirmation_window(
+    ctx: &egui::Context,
+    confirmation: &mut BulkConfirmation,
+    settings: &SafetySettings,
+    decision: &mut Option<bool>,
+) {
+    let mut is_open = true;
+    egui::Window::new(
+        egui::RichText::new(format!("⚠ Confirm {}", confirmation.action.label()))
+            .size(12.0)
+            .monospace(),
+    )
+    .id(egui::Id::new("bulk_confirmation_window"))
+    .default_width(420.0)
+    .resizable(false)
+    .collapsible(false)
+    .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+    .open(&mut is_open)
+    .show(ctx, |ui| {
+        ui.label(
+            egui::RichText::new(format!(
+                "{} {} miner(s)",
+                confirmation.action.label(),
+                confirmation.targets.len()
+            ))
+            .size(13.0)
+            .color(Color32::from_rgb(255, 200, 100))
+            .strong()
+            .monospace(),
+        );
+        for (model, count) in &confirmation.models {
+            ui.label(
+                egui::RichText::new(format!("  {count}× {model}"))
+                    .size(11.0)
+                    .monospace(),
+            );
+        }
+        if !confirmation.skipped_protected.is_empty() {
+            ui.label(
+                egui::RichText::new(format!(
+                    "🛡 {} protected miner(s) will be skipped",
+                    confirmation.skipped_protected.len()
+                ))
+                .size(10.0)
+                .color(Color32::from_rgb(130, 130, 130)),
+            )
+            .on_hover_text(confirmation.skipped_protected.join("\n"));
+        }
+        if confirmation.targets.is_empty() {
+            ui.label(
+                egui::RichText::new("Nothing left to act on")
+                    .size(11.0)
+                    .color(Color32::from_rgb(255, 120, 120)),
+            );
+        }
+        ui.add_space(8.0);
+
+        if settings.requires_typing(confirmation.targets.len()) {
+            let phrase = confirmation.phrase();
+            ui.label(
+                egui::RichText::new(format!("Type {phrase} to confirm"))
+                    .size(11.0)
+                    .monospace(),
+            );
+            ui.add(
+                egui::TextEdit::singleline(&mut confirmation.typed)
+                    .desired_width(160.0)
+                    .font(egui::TextStyle::Monospace),
+            );
+            ui.add_space(6.0);
+        }
+
+        ui.horizontal(|ui| {
+            if ui
+                .add_enabled(
+                    confirmation.is_confirmed(settings),
+                    egui::Button::new(
+                        egui::RichText::new(confirmation.action.label())
+                            .size(11.0)
+                            .color(Color32::WHITE)
+                            .monospace(),
+                    )
+                    .fill(Color32::from_rgb(200, 80, 80))
+                    .corner_radius(5.0),
+                )
+                .clicked()
+            {
+                *decision = Some(true);
+            }
+            if ui.button("Cancel").clicked() {
+                *decision = Some(false);
+            }
+        });
+    });
+    if !is_open {
+        *decision = Some(false);
+    }
+}
+
+pub fn draw_safety_window(
+    ctx: &egui::Context,
+    open: &mut bool,
+    settings: &mut SafetySettings,
+    protected: &mut HashSet<String>,
+    miners: &[MinerInfo],
+    selected_miners: &HashSet<String>,
+) {
+    egui::Window::new(egui::RichText::new("🛡 Safety").size(12.0).monospace())
+        .id(egui::Id::new("safety_window"))
+        .default_width(520.0)
+        .default_height(420.0)
+        .resizable(true)
+        .collapsible(true)
+        .open(open)
+        .show(ctx, |ui| {
+            ui.checkbox(
+                &mut settings.confirm_bulk_actions,
+                "Confirm START / STOP / FAULT LIGHT on selected miners",
+            );
+            ui.horizontal(|ui| {
+                ui.label("Type to confirm at");
+                ui.add(
+                    egui::DragValue::new(&mut settings.typed_confirmation_min_miners)
+                        .range(0..=10_000)
+                        .suffix(" miners"),
+                );
+                ui.label(
+                    egui::RichText::new("0 = never")
+                        .size(10.0)
+                        .color(Color32::from_rgb(130, 130, 130)),
+                );
+            });
+            ui.separator();
+
+            ui.label(
+                egui::RichText::new("PROTECTED MINERS")
+                    .size(11.0)
+                    .strong()
+                    .monospace(),
+            );
+            ui.label(
+                egui::RichText::new(
+                    "Bulk actions, fleet-wide applies and schedules skip these. Single-miner actions from the detail view still work.",
+                )
+                .size(10.0)
+                .color(Color32::from_rgb(130, 130, 130)),
+            );
+            let selected: Vec<&MinerInfo> = miners
+                .iter()
+                .filter(|m| selected_miners.contains(&m.ip))
+                .collect();
+            ui.horizontal(|ui| {
+                if ui
+                    .add_enabled(
+                        !selected.is_empty(),
+                        egui::Button::new(format!("Protect Selected ({})", selected.len())),
+                    )
+                    .clicked()
+                {
+                    protected.extend(selected.iter().map(|m| m.identity()));
+                }
+                if ui
+                    .add_enabled(
+                        !selected.is_empty(),
+                        egui::Button::new(format!("Unprotect Selected ({})", selected.len())),
+                    )
+                    .clicked()
+                {
+                    for miner in &selected {
+                        protected.remove(&miner.identity());
+                    }
+                }
+            });
+            ui.add_space(4.0);
+
+            let mut identities: Vec<String> = protected.iter().cloned().collect();
+            identities.sort();
+            let mut remove = None;
+            egui::ScrollArea::vertical()
+                .id_salt("protected_scroll")
+                .auto_shrink([false, false])
+                .show(ui, |ui| {
+                    if identities.is_empty() {
+                        ui.label("No protected miners");
+                    }
+                    for identity in &identities {
+                        let scanned = miners.iter().find(|m| m.identity() == *identity);
+                        ui.horizontal(|ui| {
+                            let text = match scanned {
+                                Some(miner) => format!("{} - {} ({identity})", miner.ip, miner.model),
+                                None => format!("{identity} (not in last scan)"),
+                            };
+                            ui.label(egui::RichText::new(text).size(10.0).monospace());
+                            if ui.small_button("✕").on_hover_text("Unprotect").clicked() {
+                                remove = Some(identity.clone());
+                            }
+                        });
+                    }
+                });
+            if let Some(identity) = remove {
+                protected.remove(&identity);
+            }
+        });
+}
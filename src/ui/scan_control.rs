@@ -1,7 +1,10 @@
 use crate::models::{
-    FanModeSelection, MinerOptionSettings, MiningModeSelection, PoolInput, SavedRange,
-    ScanProgress, TuningTargetSelection, EPIC_TUNING_ALGO_OPTIONS, HASHRATE_ALGO_OPTIONS,
+    FanModeSelection, MinerOptionSettings, MiningModeSelection, PoolGroupInput, PoolInput,
+    SavedRange, ScanProgress, TuningTargetSelection, EPIC_TUNING_ALGO_OPTIONS,
+    HASHRATE_ALGO_OPTIONS,
 };
+use crate::rollout::RolloutSettings;
+use crate::workers::worker_template_help;
 use eframe::egui;
 use egui::{Color32, FontId, Vec2};
 use std::sync::{Arc, Mutex};
@@ -20,10 +23,119 @@ pub struct ScanControlState {
     pub show_name_error: bool,
 }
 
+/// Editor for an ordered list of pool groups; `widths` are the URL, username
+/// and password field widths.
+pub fn draw_pool_groups_editor(
+    ui: &mut egui::Ui,
+    id_salt: &str,
+    pool_groups: &mut Vec<PoolGroupInput>,
+    widths: [f32; 3],
+) {
+    let [url_width, user_width, pass_width] = widths;
+    let group_count = pool_groups.len();
+    let mut remove_group: Option<usize> = None;
+    let mut raise_group: Option<usize> = None;
+
+    for (group_idx, group) in pool_groups.iter_mut().enumerate() {
+        ui.horizontal_wrapped(|ui| {
+            ui.label(
+                egui::RichText::new(format!("G{}", group_idx + 1))
+                    .color(Color32::from_rgb(100, 200, 255))
+                    .monospace(),
+            );
+            ui.add(
+                egui::TextEdit::singleline(&mut group.name)
+                    .id_salt(format!("{id_salt}_pool_group_name_{group_idx}"))
+                    .desired_width(110.0)
+                    .hint_text(if group_idx == 0 { "Primary" } else { "Backup" }),
+            );
+            ui.add(
+                egui::DragValue::new(&mut group.quota)
+                    .range(1..=100)
+                    .suffix(" %"),
+            );
+            if group_idx > 0
+                && ui
+                    .small_button("▲")
+                    .on_hover_text("Move up in failover order")
+                    .clicked()
+            {
+                raise_group = Some(group_idx);
+            }
+            if group_count > 1 && ui.small_button("✕").on_hover_text("Remove group").clicked() {
+                remove_group = Some(group_idx);
+            }
+        });
+
+        ui.indent(format!("{id_salt}_pool_group_{group_idx}"), |ui| {
+            let mut remove_idx: Option<usize> = None;
+            let can_remove_pool = group.pools.len() > 1;
+
+            for (idx, pool) in group.pools.iter_mut().enumerate() {
+                ui.horizontal_wrapped(|ui| {
+                    ui.label(format!("{}.", idx + 1));
+                    ui.add(
+                        egui::TextEdit::singleline(&mut pool.url)
+                            .desired_width(url_width)
+                            .hint_text("stratum+tcp://pool:3333"),
+                    );
+                    ui.add(
+                        egui::TextEdit::singleline(&mut pool.username)
+                            .desired_width(user_width)
+                            .hint_text("wallet.worker"),
+                    );
+                    ui.add(
+                        egui::TextEdit::singleline(&mut pool.password)
+                            .desired_width(pass_width)
+                            .hint_text("x"),
+                    );
+
+                    if ui
+                        .add_enabled(can_remove_pool, egui::Button::new("-"))
+                        .clicked()
+                    {
+                        remove_idx = Some(idx);
+                    }
+                });
+            }
+
+            if let Some(idx) = remove_idx {
+                group.pools.remove(idx);
+            }
+
+            ui.horizontal_wrapped(|ui| {
+                if ui.button("+ Add Pool").clicked() {
+                    group.pools.push(PoolInput::default());
+                }
+                ui.label(
+                    egui::RichText::new(format!("{} configured", group.pools.len()))
+                        .size(10.0)
+                        .color(Color32::from_rgb(130, 130, 130)),
+                );
+            });
+        });
+    }
+
+    if let Some(idx) = remove_group {
+        pool_groups.remove(idx);
+    }
+    if let Some(idx) = raise_group {
+        pool_groups.swap(idx - 1, idx);
+    }
+
+    if ui.button("+ Add Backup Group").clicked() {
+        pool_groups.push(PoolGroupInput {
+            name: format!("Backup {}", pool_groups.len()),
+            ..Default::default()
+        });
+    }
+}
+
 #[allow(clippy::too_many_arguments)]
 pub fn draw_global_options_card(
     ui: &mut egui::Ui,
     global_options: &mut MinerOptionSettings,
+    rollout_settings: &mut RolloutSettings,
     selected_count: usize,
     miner_count: usize,
     show_epic_tuning_presets: bool,
@@ -284,66 +396,36 @@ pub fn draw_global_options_card(
             ui.horizontal_wrapped(|ui| {
                 ui.checkbox(&mut global_options.apply_pool_config, "");
                 ui.label("Pools");
-                ui.label("Group");
-                ui.add(
-                    egui::TextEdit::singleline(&mut global_options.pool_group_name)
-                        .desired_width(110.0)
-                        .hint_text("Primary"),
-                );
-                ui.add(
-                    egui::DragValue::new(&mut global_options.pool_group_quota)
-                        .range(1..=100)
-                        .suffix(" %"),
+                ui.label(
+                    egui::RichText::new("groups fail over top to bottom")
+                        .size(10.0)
+                        .color(Color32::from_rgb(130, 130, 130)),
                 );
             });
 
-            let mut remove_idx: Option<usize> = None;
-            let can_remove_pool = global_options.pool_inputs.len() > 1;
             let pool_url_width = (ui.available_width() * 0.47).clamp(140.0, 260.0);
             let pool_user_width = (ui.available_width() * 0.28).clamp(100.0, 160.0);
             let pool_pass_width = 58.0;
 
-            for (idx, pool) in global_options.pool_inputs.iter_mut().enumerate() {
-                ui.horizontal_wrapped(|ui| {
-                    ui.label(format!("{}.", idx + 1));
-                    ui.add(
-                        egui::TextEdit::singleline(&mut pool.url)
-                            .desired_width(pool_url_width)
-                            .hint_text("stratum+tcp://pool:3333"),
-                    );
-                    ui.add(
-                        egui::TextEdit::singleline(&mut pool.username)
-                            .desired_width(pool_user_width)
-                            .hint_text("wallet.worker"),
-                    );
-                    ui.add(
-                        egui::TextEdit::singleline(&mut pool.password)
-                            .desired_width(pool_pass_width)
-                            .hint_text("x"),
-                    );
-
-                    if ui
-                        .add_enabled(can_remove_pool, egui::Button::new("-"))
-                        .clicked()
-                    {
-                        remove_idx = Some(idx);
-                    }
-                });
-            }
-
-            if let Some(idx) = remove_idx {
-                global_options.pool_inputs.remove(idx);
-            }
+            draw_pool_groups_editor(
+                ui,
+                "global",
+                &mut global_options.pool_groups,
+                [pool_url_width, pool_user_width, pool_pass_width],
+            );
 
             ui.horizontal_wrapped(|ui| {
-                if ui.button("+ Add Pool").clicked() {
-                    global_options.pool_inputs.push(PoolInput::default());
-                }
-                ui.label(
-                    egui::RichText::new(format!("{} configured", global_options.pool_inputs.len()))
-                        .size(10.0)
-                        .color(Color32::from_rgb(130, 130, 130)),
-                );
+                ui.label("Account");
+                ui.add(
+                    egui::TextEdit::singleline(&mut global_options.pool_account)
+                        .desired_width(pool_user_width)
+                        .hint_text("account"),
+                )
+                .on_hover_text(worker_template_help());
+                ui.checkbox(&mut global_options.verify_pools, "Pre-flight")
+                    .on_hover_text(
+                        "Connect to each pool and send mining.subscribe before applying",
+                    );
             });
 
             if global_options.apply_pool_config {
@@ -362,6 +444,64 @@ pub fn draw_global_options_card(
                 }
             }
 
+            ui.add_space(8.0);
+            ui.separator();
+            ui.add_space(8.0);
+
+            ui.horizontal_wrapped(|ui| {
+                ui.checkbox(&mut rollout_settings.enabled, "")
+                    .on_hover_text("Apply to canaries first and roll out in waves");
+                ui.label("Staged");
+                ui.add_enabled_ui(rollout_settings.enabled, |ui| {
+                    ui.add(
+                        egui::DragValue::new(&mut rollout_settings.canary_count)
+                            .range(1..=50)
+                            .speed(1)
+                            .prefix("canaries "),
+                    );
+                    ui.add(
+                        egui::DragValue::new(&mut rollout_settings.wave_size)
+                            .range(1..=500)
+                            .speed(1)
+                            .prefix("wave "),
+                    );
+                    let mut soak_mins = rollout_settings.soak_secs / 60;
+                    if ui
+                        .add(
+                            egui::DragValue::new(&mut soak_mins)
+                                .range(1..=240)
+                                .speed(1)
+                                .prefix("soak ")
+                                .suffix(" min"),
+                        )
+                        .changed()
+                    {
+                        rollout_settings.soak_secs = soak_mins * 60;
+                    }
+                });
+            });
+
+            if rollout_settings.enabled {
+                ui.horizontal_wrapped(|ui| {
+                    ui.label("Halt if");
+                    ui.add(
+                        egui::DragValue::new(&mut rollout_settings.max_hashrate_drop_percent)
+                            .range(0.0..=100.0)
+                            .speed(0.5)
+                            .prefix("hashrate -")
+                            .suffix(" %"),
+                    );
+                    ui.label("or");
+                    ui.add(
+                        egui::DragValue::new(&mut rollout_settings.max_temp_rise_c)
+                            .range(0.0..=50.0)
+                            .speed(0.5)
+                            .prefix("temp +")
+                            .suffix(" C"),
+                    );
+                });
+            }
+
             ui.add_space(8.0);
             ui.horizontal(|ui| {
                 if ui
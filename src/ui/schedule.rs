@@ -0,0 +1,344 @@
+use crate::actions::MinerAction;
+use crate::models::{MinerInfo, OptionProfile, SavedRange};
+use crate::profiles;
+use crate::schedule::{CronSchedule, ScheduleLog, ScheduledAction, ScheduledTask};
+use crate::ui::profiles::draw_match_rules_editor;
+use chrono::{DateTime, Duration, Local, TimeZone};
+use eframe::egui;
+use egui::Color32;
+use std::collections::BTreeMap;
+
+const CALENDAR_DAYS: i64 = 7;
+const MAX_OCCURRENCES_PER_ACTION: usize = 48;
+
+fn task_kind_label(task: &ScheduledTask) -> &'static str {
+    match task {
+        ScheduledTask::Miner(MinerAction::Pause) => "Pause",
+        ScheduledTask::Miner(MinerAction::Resume) => "Resume",
+        ScheduledTask::Miner(MinerAction::Restart) => "Restart",
+        ScheduledTask::ApplyProfile(_) => "Apply Profile",
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn draw_action_card(
+    ui: &mut egui::Ui,
+    idx: usize,
+    action: &mut ScheduledAction,
+    option_profiles: &[OptionProfile],
+    saved_ranges: &[SavedRange],
+    miners: &[MinerInfo],
+    remove: &mut Option<usize>,
+    run_now: &mut Option<usize>,
+) {
+    let target_count = miners
+        .iter()
+        .filter(|m| profiles::rules_match(&action.targets, m, saved_ranges))
+        .count();
+
+    egui::Frame::new()
+        .fill(Color32::from_rgb(28, 28, 28))
+        .stroke(egui::Stroke::new(1.0, Color32::from_rgb(60, 60, 60)))
+        .corner_radius(4.0)
+        .inner_margin(8.0)
+        .show(ui, |ui| {
+            ui.horizontal(|ui| {
+                ui.checkbox(&mut action.enabled, "");
+                ui.add(
+                    egui::TextEdit::singleline(&mut action.name)
+                        .desired_width(160.0)
+                        .hint_text("action name"),
+                );
+                ui.label(
+                    egui::RichText::new(format!("{target_count} miner(s) targeted"))
+                        .size(10.0)
+                        .color(Color32::from_rgb(160, 160, 160))
+                        .monospace(),
+                );
+
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if ui.small_button("🗑").on_hover_text("Delete").clicked() {
+                        *remove = Some(idx);
+                    }
+                    if ui
+                        .small_button("▶ Run Now")
+                        .on_hover_text("Run this action once against its targets")
+                        .clicked()
+                    {
+                        *run_now = Some(idx);
+                    }
+                });
+            });
+
+            ui.horizontal(|ui| {
+                ui.label(egui::RichText::new("Cron").size(10.0));
+                ui.add(
+                    egui::TextEdit::singleline(&mut action.cron)
+                        .desired_width(120.0)
+                        .font(egui::TextStyle::Monospace)
+                        .hint_text("0 17 * * 1-5"),
+                );
+
+                let mut kind = task_kind_label(&action.task);
+                egui::ComboBox::from_id_salt(format!("schedule_task_{idx}"))
+                    .selected_text(kind)
+                    .show_ui(ui, |ui| {
+                        for label in ["Pause", "Resume", "Restart", "Apply Profile"] {
+                            ui.selectable_value(&mut kind, label, label);
+                        }
+                    });
+                if kind != task_kind_label(&action.task) {
+                    action.task = match kind {
+                        "Pause" => ScheduledTask::Miner(MinerAction::Pause),
+                        "Resume" => ScheduledTask::Miner(MinerAction::Resume),
+                        "Restart" => ScheduledTask::Miner(MinerAction::Restart),
+                        _ => ScheduledTask::ApplyProfile(
+                            option_profiles
+                                .first()
+                                .map(|p| p.name.clone())
+                                .unwrap_or_default(),
+                        ),
+                    };
+                }
+
+                if let ScheduledTask::ApplyProfile(profile_name) = &mut action.task {
+                    egui::ComboBox::from_id_salt(format!("schedule_profile_{idx}"))
+                        .selected_text(if profile_name.is_empty() {
+                            "Select profile"
+                        } else {
+                            profile_name.as_str()
+                        })
+                        .show_ui(ui, |ui| {
+                            for profile in option_profiles {
+                                ui.selectable_value(
+                                    profile_name,
+                                    profile.name.clone(),
+                                    &profile.name,
+                                );
+                            }
+                        });
+                }
+            });
+
+            match CronSchedule::parse(&action.cron) {
+                Ok(schedule) => {
+                    if let Some(next) = schedule
+                        .upcoming(Local::now(), Duration::days(366), 1)
+                        .first()
+                    {
+                        ui.label(
+                            egui::RichText::new(format!(
+                                "Next: {}",
+                                next.format("%a %Y-%m-%d %H:%M")
+                            ))
+                            .size(10.0)
+                            .color(Color32::from_rgb(130, 130, 130)),
+                        );
+                    }
+                }
+                Err(err) => {
+                    ui.label(
+                        egui::RichText::new(err)
+                            .size(10.0)
+                            .color(Color32::from_rgb(255, 120, 120)),
+                    );
+                }
+            }
+            if let ScheduledTask::ApplyProfile(name) = &action.task {
+                if !option_profiles.iter().any(|p| &p.name == name) {
+                    ui.label(
+                        egui::RichText::new("Pick an existing option profile")
+                            .size(10.0)
+                            .color(Color32::from_rgb(255, 120, 120)),
+                    );
+                }
+            }
+
+            ui.label(egui::RichText::new("Targets").size(10.0));
+            draw_match_rules_editor(
+                ui,
+                &format!("schedule_{idx}"),
+                &mut action.targets,
+                saved_ranges,
+            );
+        });
+}
+
+fn draw_calendar(ui: &mut egui::Ui, scheduled_actions: &[ScheduledAction]) {
+    let now = Local::now();
+    let mut days: BTreeMap<chrono::NaiveDate, Vec<(DateTime<Local>, &ScheduledAction)>> =
+        BTreeMap::new();
+
+    for action in scheduled_actions.iter().filter(|a| a.enabled) {
+        let Ok(schedule) = CronSchedule::parse(&action.cron) else {
+            continue;
+        };
+        for at in schedule.upcoming(
+            now,
+            Duration::days(CALENDAR_DAYS),
+            MAX_OCCURRENCES_PER_ACTION,
+        ) {
+            days.entry(at.date_naive()).or_default().push((at, action));
+        }
+    }
+
+    if days.is_empty() {
+        ui.label("Nothing scheduled in the next 7 days");
+        return;
+    }
+
+    for (date, mut entries) in days {
+        entries.sort_by_key(|(at, _)| *at);
+        ui.label(
+            egui::RichText::new(date.format("%A %Y-%m-%d").to_string())
+                .size(11.0)
+                .color(Color32::from_rgb(100, 200, 255))
+                .strong()
+                .monospace(),
+        );
+        for (at, action) in entries {
+            ui.label(
+                egui::RichText::new(format!(
+                    "  {}  {}  -  {}",
+                    at.format("%H:%M"),
+                    action.name,
+                    action.task.describe()
+                ))
+                .size(10.0)
+                .monospace(),
+            );
+        }
+        ui.add_space(4.0);
+    }
+}
+
+fn draw_log(ui: &mut egui::Ui, log: &ScheduleLog) {
+    if log.runs.is_empty() {
+        ui.label("No scheduled actions have run yet");
+        return;
+    }
+
+    for run in log.runs.iter().rev() {
+        let when = Local
+            .timestamp_opt(run.at, 0)
+            .single()
+            .map(|t| t.format("%Y-%m-%d %H:%M").to_string())
+            .unwrap_or_default();
+        let ok = run.targets.saturating_sub(run.failures.len());
+        let color = if run.failures.is_empty() {
+            Color32::from_rgb(120, 200, 120)
+        } else {
+            Color32::from_rgb(255, 165, 0)
+        };
+
+        ui.label(
+            egui::RichText::new(format!(
+                "{when}  {}  -  {}  ({ok}/{} ok)",
+                run.action_name, run.task, run.targets
+            ))
+            .size(10.0)
+            .color(color)
+            .monospace(),
+        );
+        for failure in &run.failures {
+            ui.label(
+                egui::RichText::new(format!("    ✗ {failure}"))
+                    .size(10.0)
+                    .color(Color32::from_rgb(255, 120, 120)),
+            );
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn draw_schedule_window(
+    ctx: &egui::Context,
+    open: &mut bool,
+    scheduled_actions: &mut Vec<ScheduledAction>,
+    option_profiles: &[OptionProfile],
+    saved_ranges: &[SavedRange],
+    miners: &[MinerInfo],
+    log: &ScheduleLog,
+    run_now: &mut Option<usize>,
+) {
+    egui::Window::new(
+        egui::RichText::new("⏰ Scheduled Actions")
+            .size(12.0)
+            .monospace(),
+    )
+    .id(egui::Id::new("schedule_window"))
+    .default_width(720.0)
+    .default_height(560.0)
+    .resizable(true)
+    .collapsible(true)
+    .open(open)
+    .show(ctx, |ui| {
+        ui.label(
+            egui::RichText::new(
+                "Cron fields: minute hour day month weekday (0 = Sunday), in local time. Profile changes are recorded in History.",
+            )
+            .size(10.0)
+            .color(Color32::from_rgb(130, 130, 130)),
+        );
+        ui.add_space(6.0);
+
+        if ui.button("+ Add Action").clicked() {
+            scheduled_actions.push(ScheduledAction::default());
+        }
+        ui.add_space(6.0);
+
+        let mut action_to_remove = None;
+        egui::ScrollArea::vertical()
+            .id_salt("schedule_actions_scroll")
+            .max_height(280.0)
+            .auto_shrink([false, true])
+            .show(ui, |ui| {
+                if scheduled_actions.is_empty() {
+                    ui.label("No scheduled actions");
+                }
+                for (idx, action) in scheduled_actions.iter_mut().enumerate() {
+                    draw_action_card(
+                        ui,
+                        idx,
+                        action,
+                        option_profiles,
+                        saved_ranges,
+                        miners,
+                        &mut action_to_remove,
+                        run_now,
+                    );
+                    ui.add_space(4.0);
+                }
+            });
+        if let Some(idx) = action_to_remove {
+            scheduled_actions.remove(idx);
+        }
+
+        ui.add_space(8.0);
+        ui.columns(2, |columns| {
+            columns[0].label(
+                egui::RichText::new("UPCOMING")
+                    .size(11.0)
+                    .strong()
+                    .monospace(),
+            );
+            egui::ScrollArea::vertical()
+                .id_salt("schedule_calendar_scroll")
+                .max_height(200.0)
+                .auto_shrink([false, true])
+                .show(&mut columns[0], |ui| draw_calendar(ui, scheduled_actions));
+
+            columns[1].label(
+                egui::RichText::new("EXECUTION LOG")
+                    .size(11.0)
+                    .strong()
+                    .monospace(),
+            );
+            egui::ScrollArea::vertical()
+                .id_salt("schedule_log_scroll")
+                .max_height(200.0)
+                .auto_shrink([false, true])
+                .show(&mut columns[1], |ui| draw_log(ui, log));
+        });
+    });
+}
@@ -0,0 +1,165 @@
+use crate::secrets::{SecretStore, SecretsForm, SecretsRequest};
+use crate::snapshots::format_timestamp;
+use eframe::egui;
+use egui::Color32;
+
+fn passphrase_field(ui: &mut egui::Ui, label: &str, value: &mut String) -> egui::Response {
+    ui.horizontal(|ui| {
+        ui.add_sized(
+            [110.0, 18.0],
+            egui::Label::new(egui::RichText::new(label).size(10.0).monospace()),
+        );
+        ui.add(
+            egui::TextEdit::singleline(value)
+                .password(true)
+                .desired_width(220.0),
+        )
+    })
+    .inner
+}
+
+/// Passphrase and its confirmation, `Some` once both match.
+fn new_passphrase(ui: &mut egui::Ui, form: &mut SecretsForm) -> Option<String> {
+    passphrase_field(ui, "Passphrase", &mut form.passphrase);
+    passphrase_field(ui, "Confirm", &mut form.confirm);
+    if !form.confirm.is_empty() && form.confirm != form.passphrase {
+        ui.label(
+            egui::RichText::new("Passphrases do not match")
+                .size(10.0)
+                .color(Color32::from_rgb(255, 120, 120)),
+        );
+    }
+    (!form.passphrase.is_empty() && form.passphrase == form.confirm)
+        .then(|| form.passphrase.clone())
+}
+
+pub fn draw_secrets_window(
+    ctx: &egui::Context,
+    open: &mut bool,
+    store: &SecretStore,
+    form: &mut SecretsForm,
+    request: &mut Option<SecretsRequest>,
+) {
+    egui::Window::new(egui::RichText::new("🔐 Secrets").size(12.0).monospace())
+        .id(egui::Id::new("secrets_window"))
+        .default_width(520.0)
+        .default_height(380.0)
+        .resizable(true)
+        .collapsible(true)
+        .open(open)
+        .show(ctx, |ui| {
+            ui.label(
+                egui::RichText::new(
+                    "Pool passwords and miner logins are encrypted with a master passphrase and the config only keeps a reference to them. The passphrase itself is never saved.",
+                )
+                .size(10.0)
+                .color(Color32::from_rgb(130, 130, 130)),
+            );
+            ui.add_space(6.0);
+
+            if !store.is_initialized() {
+                ui.label(
+                    egui::RichText::new("⚠ No master passphrase: passwords are saved in plain text")
+                        .size(11.0)
+                        .color(Color32::from_rgb(255, 200, 100)),
+                );
+                ui.add_space(4.0);
+                let passphrase = new_passphrase(ui, form);
+                if ui
+                    .add_enabled(passphrase.is_some(), egui::Button::new("Set Passphrase"))
+                    .clicked()
+                {
+                    *request = passphrase.map(SecretsRequest::Create);
+                }
+            } else if !store.is_unlocked() {
+                ui.label(
+                    egui::RichText::new("🔒 Locked: stored passwords cannot be used or changed")
+                        .size(11.0)
+                        .color(Color32::from_rgb(255, 200, 100)),
+                );
+                ui.add_space(4.0);
+                let response = passphrase_field(ui, "Passphrase", &mut form.passphrase);
+                let submitted =
+                    response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+                if (ui.button("Unlock").clicked() || submitted) && !form.passphrase.is_empty() {
+                    *request = Some(SecretsRequest::Unlock(form.passphrase.clone()));
+                }
+            } else {
+                ui.horizontal(|ui| {
+                    ui.label(
+                        egui::RichText::new("🔓 Unlocked")
+                            .size(11.0)
+                            .color(Color32::from_rgb(150, 200, 150)),
+                    );
+                    if ui
+                        .button("Lock")
+                        .on_hover_text("Forget the key until the passphrase is entered again")
+                        .clicked()
+                    {
+                        *request = Some(SecretsRequest::Lock);
+                    }
+                });
+                egui::CollapsingHeader::new("Change passphrase")
+                    .id_salt("secrets_change_passphrase")
+                    .show(ui, |ui| {
+                        let passphrase = new_passphrase(ui, form);
+                        if ui
+                            .add_enabled(passphrase.is_some(), egui::Button::new("Re-encrypt"))
+                            .clicked()
+                        {
+                            *request = passphrase.map(SecretsRequest::ChangePassphrase);
+                        }
+                    });
+            }
+
+            if let Some(error) = &form.error {
+                ui.label(
+                    egui::RichText::new(error)
+                        .size(10.0)
+                        .color(Color32::from_rgb(255, 120, 120)),
+                );
+            }
+
+            if !store.is_initialized() {
+                return;
+            }
+            ui.separator();
+            ui.label(
+                egui::RichText::new("STORED SECRETS")
+                    .size(11.0)
+                    .strong()
+                    .monospace(),
+            );
+            egui::ScrollArea::vertical()
+                .id_salt("secrets_scroll")
+                .auto_shrink([false, false])
+                .show(ui, |ui| {
+                    let mut any = false;
+                    egui::Grid::new("secrets_grid")
+                        .num_columns(3)
+                        .spacing([14.0, 4.0])
+                        .striped(true)
+                        .show(ui, |ui| {
+                            for (_, record) in store.records() {
+                                any = true;
+                                ui.label(
+                                    egui::RichText::new(record.kind.label())
+                                        .size(10.0)
+                                        .monospace(),
+                                );
+                                ui.label(egui::RichText::new(&record.label).size(10.0).monospace());
+                                ui.label(
+                                    egui::RichText::new(format_timestamp(record.updated_at))
+                                        .size(10.0)
+                                        .color(Color32::from_rgb(130, 130, 130))
+                                        .monospace(),
+                                );
+                                ui.end_row();
+                            }
+                        });
+                    if !any {
+                        ui.label("Nothing stored yet");
+                    }
+                });
+        });
+}
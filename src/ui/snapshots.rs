@@ -0,0 +1,103 @@
+use crate::snapshots::{format_timestamp, ConfigSnapshot, SnapshotStore};
+use eframe::egui;
+use egui::Color32;
+
+pub enum RollbackRequest {
+    Job(String),
+    Snapshot(Box<ConfigSnapshot>),
+}
+
+pub fn draw_snapshots_window(
+    ctx: &egui::Context,
+    open: &mut bool,
+    store: &SnapshotStore,
+    rollback_request: &mut Option<RollbackRequest>,
+) {
+    egui::Window::new(
+        egui::RichText::new("🕘 Change History")
+            .size(12.0)
+            .monospace(),
+    )
+    .id(egui::Id::new("snapshots_window"))
+    .default_width(640.0)
+    .default_height(460.0)
+    .resizable(true)
+    .collapsible(true)
+    .open(open)
+    .show(ctx, |ui| {
+        ui.label(
+            egui::RichText::new("Settings captured before each apply job, newest first")
+                .size(10.0)
+                .color(Color32::from_rgb(130, 130, 130)),
+        );
+        ui.add_space(8.0);
+
+        if store.jobs.is_empty() {
+            ui.label("No apply jobs recorded yet");
+            return;
+        }
+
+        egui::ScrollArea::vertical()
+            .id_salt("snapshots_scroll")
+            .auto_shrink([false, false])
+            .show(ui, |ui| {
+                for job in store.jobs.iter().rev() {
+                    ui.horizontal(|ui| {
+                        ui.label(
+                            egui::RichText::new(format!(
+                                "{}  •  {}  •  {} miner(s)",
+                                format_timestamp(job.started_at),
+                                job.title,
+                                job.snapshots.len()
+                            ))
+                            .size(11.0)
+                            .color(Color32::from_rgb(220, 220, 220))
+                            .monospace(),
+                        );
+
+                        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                            if ui
+                                .add_enabled(
+                                    !job.snapshots.is_empty(),
+                                    egui::Button::new(
+                                        egui::RichText::new("↩ Rollback Job")
+                                            .color(Color32::from_rgb(255, 165, 0)),
+                                    ),
+                                )
+                                .clicked()
+                            {
+                                *rollback_request = Some(RollbackRequest::Job(job.id.clone()));
+                            }
+                        });
+                    });
+
+                    ui.indent(format!("snapshot_job_{}", job.id), |ui| {
+                        for snapshot in &job.snapshots {
+                            ui.horizontal(|ui| {
+                                ui.label(
+                                    egui::RichText::new(format!(
+                                        "{} ({})",
+                                        snapshot.ip, snapshot.miner_identity
+                                    ))
+                                    .size(10.0)
+                                    .color(Color32::from_rgb(160, 160, 160))
+                                    .monospace(),
+                                );
+                                if ui
+                                    .small_button("↩ Rollback")
+                                    .on_hover_text("Restore this miner to the captured settings")
+                                    .clicked()
+                                {
+                                    *rollback_request =
+                                        Some(RollbackRequest::Snapshot(Box::new(snapshot.clone())));
+                                }
+                            });
+                        }
+                    });
+
+                    ui.add_space(6.0);
+                    ui.separator();
+                }
+            });
+    });
+}
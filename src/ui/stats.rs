@@ -1,8 +1,9 @@
 use crate::models::MinerInfo;
+use crate::profitability::EconomicsTotals;
 use eframe::egui;
 use egui::Color32;
 
-pub fn draw_stats_card(ui: &mut egui::Ui, miners: &[MinerInfo]) {
+pub fn draw_stats_card(ui: &mut egui::Ui, miners: &[&MinerInfo], daily: Option<&EconomicsTotals>) {
     let target_inner_width = (ui.available_width() - 30.0).max(0.0);
     let miner_count = miners.len();
 
@@ -113,6 +114,32 @@ pub fn draw_stats_card(ui: &mut egui::Ui, miners: &[MinerInfo]) {
                     .color(Color32::from_rgb(255, 200, 180))
                     .monospace(),
                 );
+
+                if let Some(daily) = daily {
+                    ui.label(
+                        egui::RichText::new(format!(
+                            "DAY: ${:.2} revenue  •  ${:.2} power  •  ${:.2} margin",
+                            daily.revenue_per_day,
+                            daily.cost_per_day,
+                            daily.margin_per_day()
+                        ))
+                        .size(12.0)
+                        .color(Color32::from_rgb(255, 200, 180))
+                        .monospace(),
+                    );
+                    if daily.unprofitable > 0 {
+                        ui.label(
+                            egui::RichText::new(format!(
+                                "{} UNPROFITABLE AT CURRENT SETTINGS",
+                                daily.unprofitable
+                            ))
+                            .size(10.0)
+                            .color(Color32::WHITE)
+                            .strong()
+                            .monospace(),
+                        );
+                    }
+                }
             });
         });
 }
@@ -1,5 +1,7 @@
+use crate::actions::{self, MinerAction};
 use crate::models::{MinerInfo, ScanProgress, SortColumn, SortDirection};
-use asic_rs::MinerFactory;
+use crate::restarts::{RestartPhase, RestartStatus};
+use crate::safety::BulkAction;
 use eframe::egui;
 use egui::{Color32, FontId};
 use std::collections::{HashMap, HashSet};
@@ -12,20 +14,28 @@ pub fn draw_miners_table(
     search_query: &mut String,
     selected_miners: &mut HashSet<String>,
     detail_view_miners: &mut Vec<MinerInfo>,
+    percent_of_nominal: &HashMap<String, f64>,
+    tenant_scope: Option<&HashSet<String>>,
+    protected_ips: &HashSet<String>,
+    auth_failures: &HashMap<String, String>,
+    restarts: &HashMap<String, RestartStatus>,
     sort_column: Option<SortColumn>,
     sort_direction: SortDirection,
     scan_progress: Arc<Mutex<ScanProgress>>,
+    bulk_request: &mut Option<BulkAction>,
     export_clicked: &mut bool,
 ) -> Option<SortColumn> {
     let mut clicked_column: Option<SortColumn> = None;
 
-    // Filter miners based on search query
+    // Filter miners based on tenant scope and search query
+    let scoped_miners = miners
+        .iter()
+        .filter(|m| tenant_scope.is_none_or(|ips| ips.contains(&m.ip)));
     let filtered_miners: Vec<&MinerInfo> = if search_query.is_empty() {
-        miners.iter().collect()
+        scoped_miners.collect()
     } else {
         let query = search_query.to_lowercase();
-        miners
-            .iter()
+        scoped_miners
             .filter(|m| {
                 m.ip.to_lowercase().contains(&query)
                     || m.hostname.to_lowercase().contains(&query)
@@ -122,111 +132,91 @@ pub fn draw_miners_table(
 
             ui.add_space(10.0);
 
-            let start_btn = egui::Button::new(
-                egui::RichText::new("▶ START")
-                    .size(11.0)
-                    .color(Color32::WHITE)
-                    .monospace(),
-            )
-            .fill(Color32::from_rgb(100, 200, 100))
-            .corner_radius(4.0);
+            if !actions::is_read_only() {
+                let start_btn = egui::Button::new(
+                    egui::RichText::new("▶ START")
+                        .size(11.0)
+                        .color(Color32::WHITE)
+                        .monospace(),
+                )
+                .fill(Color32::from_rgb(100, 200, 100))
+                .corner_radius(4.0);
 
-            if ui
-                .add_enabled(!selected_miners.is_empty(), start_btn)
-                .on_hover_text("Start selected miners")
-                .clicked()
-            {
-                let selected_ips = selected_miners.clone();
-                crate::runtime::spawn(async move {
-                    for ip in selected_ips {
-                        let factory = MinerFactory::new();
-                        if let Ok(Some(miner)) = factory.get_miner(ip.parse().unwrap()).await {
-                            match miner.resume(None).await {
-                                Ok(_) => println!("✓ Started miner: {ip}"),
-                                Err(e) => eprintln!("✗ Failed to start {ip}: {e}"),
-                            }
-                        }
-                    }
-                });
-            }
+                if ui
+                    .add_enabled(!selected_miners.is_empty(), start_btn)
+                    .on_hover_text("Start selected miners")
+                    .clicked()
+                {
+                    *bulk_request = Some(BulkAction::Miner(MinerAction::Resume));
+                }
 
-            ui.add_space(5.0);
+                ui.add_space(5.0);
 
-            let stop_btn = egui::Button::new(
-                egui::RichText::new("■ STOP")
-                    .size(11.0)
-                    .color(Color32::WHITE)
-                    .monospace(),
-            )
-            .fill(Color32::from_rgb(255, 100, 100))
-            .corner_radius(4.0);
+                let stop_btn = egui::Button::new(
+                    egui::RichText::new("■ STOP")
+                        .size(11.0)
+                        .color(Color32::WHITE)
+                        .monospace(),
+                )
+                .fill(Color32::from_rgb(255, 100, 100))
+                .corner_radius(4.0);
 
-            if ui
-                .add_enabled(!selected_miners.is_empty(), stop_btn)
-                .on_hover_text("Stop selected miners")
-                .clicked()
-            {
-                let selected_ips = selected_miners.clone();
-                crate::runtime::spawn(async move {
-                    for ip in selected_ips {
-                        let factory = MinerFactory::new();
-                        if let Ok(Some(miner)) = factory.get_miner(ip.parse().unwrap()).await {
-                            match miner.pause(None).await {
-                                Ok(_) => println!("✓ Stopped miner: {ip}"),
-                                Err(e) => eprintln!("✗ Failed to stop {ip}: {e}"),
-                            }
-                        }
-                    }
-                });
-            }
+                if ui
+                    .add_enabled(!selected_miners.is_empty(), stop_btn)
+                    .on_hover_text("Stop selected miners")
+                    .clicked()
+                {
+                    *bulk_request = Some(BulkAction::Miner(MinerAction::Pause));
+                }
 
-            ui.add_space(5.0);
+                ui.add_space(5.0);
 
-            let fault_light_btn = egui::Button::new(
-                egui::RichText::new("💡 FAULT LIGHT")
-                    .size(11.0)
-                    .color(Color32::WHITE)
-                    .monospace(),
-            )
-            .fill(Color32::from_rgb(255, 165, 0))
-            .corner_radius(4.0);
+                let fault_light_btn = egui::Button::new(
+                    egui::RichText::new("💡 FAULT LIGHT")
+                        .size(11.0)
+                        .color(Color32::WHITE)
+                        .monospace(),
+                )
+                .fill(Color32::from_rgb(255, 165, 0))
+                .corner_radius(4.0);
 
-            if ui
-                .add_enabled(!selected_miners.is_empty(), fault_light_btn)
-                .on_hover_text("Toggle fault light on selected miners")
-                .clicked()
-            {
-                let selected_ips = selected_miners.clone();
-                // Collect current states of selected miners
-                let states: HashMap<String, bool> = filtered_miners
+                if ui
+                    .add_enabled(!selected_miners.is_empty(), fault_light_btn)
+                    .on_hover_text("Toggle fault light on selected miners")
+                    .clicked()
+                {
+                    *bulk_request = Some(BulkAction::ToggleFaultLight);
+                }
+
+                ui.add_space(5.0);
+
+                let restart_btn = egui::Button::new(
+                    egui::RichText::new("⟳ RESTART")
+                        .size(11.0)
+                        .color(Color32::WHITE)
+                        .monospace(),
+                )
+                .fill(Color32::from_rgb(120, 140, 220))
+                .corner_radius(4.0);
+
+                let restartable = miners
                     .iter()
-                    .filter(|m| selected_ips.contains(&m.ip))
-                    .map(|m| (m.ip.clone(), m.light_flashing))
-                    .collect();
-
-                crate::runtime::spawn(async move {
-                    for ip in selected_ips {
-                        let current_state = states.get(&ip).copied().unwrap_or(false);
-                        let factory = MinerFactory::new();
-                        if let Ok(Some(miner)) = factory.get_miner(ip.parse().unwrap()).await {
-                            let new_state = !current_state;
-                            match miner.set_fault_light(new_state).await {
-                                Ok(_) => println!(
-                                    "✓ Set fault light to {} on: {}",
-                                    if new_state { "ON" } else { "OFF" },
-                                    ip
-                                ),
-                                Err(e) => {
-                                    eprintln!("✗ Failed to set fault light on {}: {}", ip, e)
-                                }
-                            }
-                        }
-                    }
-                });
+                    .filter(|m| selected_miners.contains(&m.ip) && m.capabilities.restart)
+                    .count();
+                if ui
+                    .add_enabled(!selected_miners.is_empty(), restart_btn)
+                    .on_hover_text(format!(
+                        "Restart selected miners ({restartable} of {} support it)",
+                        selected_miners.len()
+                    ))
+                    .clicked()
+                {
+                    *bulk_request = Some(BulkAction::Miner(MinerAction::Restart));
+                }
+
+                ui.add_space(10.0);
             }
 
-            ui.add_space(10.0);
-
             if ui.button("Select All").clicked() {
                 *selected_miners = filtered_miners.iter().map(|m| m.ip.clone()).collect();
             }
@@ -308,6 +298,7 @@ pub fn draw_miners_table(
                             .column(Column::initial(150.0).resizable(true)) // Control Board
                             .column(Column::initial(90.0).resizable(true)) // Active Boards
                             .column(Column::initial(120.0).resizable(true)) // Hashrate
+                            .column(Column::initial(100.0).resizable(true)) // % of Nominal
                             .column(Column::initial(100.0).resizable(true)) // Wattage
                             .column(Column::initial(100.0).resizable(true)) // Efficiency
                             .column(Column::initial(120.0).resizable(true)) // Temperature
@@ -455,6 +446,25 @@ pub fn draw_miners_table(
                                         clicked_column = Some(SortColumn::Hashrate);
                                     }
                                 });
+                                header.col(|ui| {
+                                    if ui
+                                        .button(
+                                            egui::RichText::new(format!(
+                                                "% NOMINAL{}",
+                                                get_indicator(SortColumn::PercentOfNominal)
+                                            ))
+                                            .size(11.0)
+                                            .color(Color32::from_rgb(255, 87, 51))
+                                            .monospace(),
+                                        )
+                                        .on_hover_text(
+                                            "Hashrate against the model baseline, or the fleet median when none is configured",
+                                        )
+                                        .clicked()
+                                    {
+                                        clicked_column = Some(SortColumn::PercentOfNominal);
+                                    }
+                                });
                                 header.col(|ui| {
                                     if ui
                                         .button(
@@ -609,6 +619,43 @@ pub fn draw_miners_table(
                                                         detail_view_miners.push((*miner).clone());
                                                     }
                                                 }
+                                                if protected_ips.contains(&miner.ip) {
+                                                    ui.label(egui::RichText::new("🛡").size(11.0))
+                                                        .on_hover_text("Protected - bulk actions skip this miner");
+                                                }
+                                                if let Some(error) = auth_failures.get(&miner.ip) {
+                                                    ui.label(
+                                                        egui::RichText::new("🔒 AUTH FAILED")
+                                                            .size(9.0)
+                                                            .color(Color32::from_rgb(255, 120, 120))
+                                                            .monospace(),
+                                                    )
+                                                    .on_hover_text(format!(
+                                                        "{error}\nCheck 🔑 CREDENTIALS for this miner"
+                                                    ));
+                                                }
+                                                if let Some(status) = restarts.get(&miner.ip) {
+                                                    let (text, color) = match status.phase {
+                                                        RestartPhase::WaitingForReboot => {
+                                                            ("⟳ RESTARTING", Color32::from_rgb(255, 200, 100))
+                                                        }
+                                                        RestartPhase::BackOnline { .. } => {
+                                                            ("⟳ BACK", Color32::from_rgb(150, 200, 150))
+                                                        }
+                                                        RestartPhase::TimedOut => {
+                                                            ("⟳ NOT BACK", Color32::from_rgb(255, 120, 120))
+                                                        }
+                                                    };
+                                                    ui.label(
+                                                        egui::RichText::new(text)
+                                                            .size(9.0)
+                                                            .color(color)
+                                                            .monospace(),
+                                                    )
+                                                    .on_hover_text(status.describe(
+                                                        chrono::Local::now().timestamp(),
+                                                    ));
+                                                }
                                             });
                                         });
                                         row.col(|ui| {
@@ -659,6 +706,31 @@ pub fn draw_miners_table(
                                                     .monospace(),
                                             );
                                         });
+                                        row.col(|ui| {
+                                            let (text, color) =
+                                                match percent_of_nominal.get(&miner.ip) {
+                                                    Some(percent) => (
+                                                        format!("{percent:.0}%"),
+                                                        if *percent < 85.0 {
+                                                            Color32::from_rgb(255, 120, 120)
+                                                        } else if *percent < 95.0 {
+                                                            Color32::from_rgb(255, 200, 100)
+                                                        } else {
+                                                            Color32::from_rgb(150, 200, 150)
+                                                        },
+                                                    ),
+                                                    None => (
+                                                        "-".to_string(),
+                                                        Color32::from_rgb(130, 130, 130),
+                                                    ),
+                                                };
+                                            ui.label(
+                                                egui::RichText::new(text)
+                                                    .size(11.0)
+                                                    .color(color)
+                                                    .monospace(),
+                                            );
+                                        });
                                         row.col(|ui| {
                                             ui.label(
                                                 egui::RichText::new(&miner.wattage)
@@ -0,0 +1,229 @@
+use crate::models::SavedRange;
+use crate::tenants::{ReportFormat, ReportPeriod, Tenant, TenantReportRow};
+use crate::ui::profiles::draw_match_rules_editor;
+use eframe::egui;
+use egui::Color32;
+use std::collections::HashMap;
+
+/// Date range editor with quick presets. Returns false, after showing why,
+/// when the period is not usable.
+pub fn draw_period_picker(ui: &mut egui::Ui, period: &mut ReportPeriod) -> bool {
+    ui.horizontal(|ui| {
+        ui.add(egui::TextEdit::singleline(&mut period.from).desired_width(90.0));
+        ui.label("to");
+        ui.add(egui::TextEdit::singleline(&mut period.to).desired_width(90.0));
+        if ui.small_button("This month").clicked() {
+            *period = ReportPeriod::this_month();
+        }
+        if ui.small_button("Last month").clicked() {
+            *period = ReportPeriod::last_month();
+        }
+        if ui.small_button("Last 7 days").clicked() {
+            *period = ReportPeriod::last_days(7);
+        }
+    });
+
+    if let Some(message) = period.validation_message() {
+        ui.colored_label(Color32::from_rgb(255, 120, 120), message);
+        return false;
+    }
+    true
+}
+
+fn draw_report(
+    ui: &mut egui::Ui,
+    period: &mut ReportPeriod,
+    rows: &[TenantReportRow],
+    export: &mut Option<ReportFormat>,
+) {
+    ui.label(
+        egui::RichText::new("REPORT")
+            .size(11.0)
+            .strong()
+            .monospace(),
+    );
+    if !draw_period_picker(ui, period) {
+        return;
+    }
+
+    ui.horizontal(|ui| {
+        if ui
+            .add_enabled(!rows.is_empty(), egui::Button::new("Export CSV"))
+            .clicked()
+        {
+            *export = Some(ReportFormat::Csv);
+        }
+        if ui
+            .add_enabled(!rows.is_empty(), egui::Button::new("Export HTML"))
+            .clicked()
+        {
+            *export = Some(ReportFormat::Html);
+        }
+    });
+
+    if rows.is_empty() {
+        ui.label("No samples recorded in this period");
+        return;
+    }
+
+    egui::Grid::new("tenant_report_grid")
+        .num_columns(5)
+        .spacing([16.0, 4.0])
+        .striped(true)
+        .show(ui, |ui| {
+            for header in ["TENANT", "MINERS", "UPTIME", "AVG TH/s", "kWh"] {
+                ui.label(egui::RichText::new(header).size(10.0).strong().monospace());
+            }
+            ui.end_row();
+            for row in rows {
+                ui.label(egui::RichText::new(&row.tenant).size(10.0).monospace());
+                ui.label(
+                    egui::RichText::new(row.miners.to_string())
+                        .size(10.0)
+                        .monospace(),
+                );
+                ui.label(
+                    egui::RichText::new(format!("{:.2}%", row.uptime_percent()))
+                        .size(10.0)
+                        .monospace(),
+                );
+                ui.label(
+                    egui::RichText::new(format!("{:.2}", row.average_hashrate_th))
+                        .size(10.0)
+                        .monospace(),
+                );
+                ui.label(
+                    egui::RichText::new(format!("{:.1}", row.energy_kwh))
+                        .size(10.0)
+                        .monospace(),
+                );
+                ui.end_row();
+            }
+        });
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn draw_tenants_window(
+    ctx: &egui::Context,
+    open: &mut bool,
+    tenants: &mut Vec<Tenant>,
+    new_tenant_name: &mut String,
+    saved_ranges: &[SavedRange],
+    miner_counts: &HashMap<String, usize>,
+    period: &mut ReportPeriod,
+    report_rows: &[TenantReportRow],
+    export: &mut Option<ReportFormat>,
+) {
+    egui::Window::new(egui::RichText::new("🏢 Tenants").size(12.0).monospace())
+        .id(egui::Id::new("tenants_window"))
+        .default_width(700.0)
+        .default_height(560.0)
+        .resizable(true)
+        .collapsible(true)
+        .open(open)
+        .show(ctx, |ui| {
+            ui.label(
+                egui::RichText::new(
+                    "A miner's tenant tag (set in its detail view) wins; otherwise the first tenant whose rules match claims it",
+                )
+                .size(10.0)
+                .color(Color32::from_rgb(130, 130, 130)),
+            );
+            ui.add_space(6.0);
+
+            ui.horizontal(|ui| {
+                ui.add(
+                    egui::TextEdit::singleline(new_tenant_name)
+                        .desired_width(180.0)
+                        .hint_text("tenant name"),
+                );
+                let name = new_tenant_name.trim().to_string();
+                let name_taken = tenants.iter().any(|t| t.name == name);
+                if ui
+                    .add_enabled(
+                        !name.is_empty() && !name_taken,
+                        egui::Button::new("+ Add Tenant"),
+                    )
+                    .clicked()
+                {
+                    tenants.push(Tenant {
+                        name,
+                        assign_by_rules: false,
+                        rules: Default::default(),
+                    });
+                    new_tenant_name.clear();
+                }
+            });
+            ui.add_space(6.0);
+
+            let mut remove = None;
+            egui::ScrollArea::vertical()
+                .id_salt("tenants_scroll")
+                .max_height(240.0)
+                .auto_shrink([false, true])
+                .show(ui, |ui| {
+                    if tenants.is_empty() {
+                        ui.label("No tenants yet");
+                    }
+                    for (idx, tenant) in tenants.iter_mut().enumerate() {
+                        egui::Frame::new()
+                            .fill(Color32::from_rgb(28, 28, 28))
+                            .stroke(egui::Stroke::new(1.0, Color32::from_rgb(60, 60, 60)))
+                            .corner_radius(4.0)
+                            .inner_margin(8.0)
+                            .show(ui, |ui| {
+                                ui.horizontal(|ui| {
+                                    ui.label(
+                                        egui::RichText::new(format!("{}. {}", idx + 1, tenant.name))
+                                            .size(11.0)
+                                            .color(Color32::from_rgb(100, 200, 255))
+                                            .strong()
+                                            .monospace(),
+                                    );
+                                    ui.label(
+                                        egui::RichText::new(format!(
+                                            "{} miner(s)",
+                                            miner_counts.get(&tenant.name).copied().unwrap_or(0)
+                                        ))
+                                        .size(10.0)
+                                        .color(Color32::from_rgb(160, 160, 160))
+                                        .monospace(),
+                                    );
+                                    ui.with_layout(
+                                        egui::Layout::right_to_left(egui::Align::Center),
+                                        |ui| {
+                                            if ui.small_button("🗑").on_hover_text("Delete").clicked() {
+                                                remove = Some(idx);
+                                            }
+                                        },
+                                    );
+                                });
+                                ui.checkbox(&mut tenant.assign_by_rules, "Also claim miners matching:");
+                                ui.add_enabled_ui(tenant.assign_by_rules, |ui| {
+                                    draw_match_rules_editor(
+                                        ui,
+                                        &format!("tenant_{idx}"),
+                                        &mut tenant.rules,
+                                        saved_ranges,
+                                    );
+                                });
+                            });
+                        ui.add_space(4.0);
+                    }
+                });
+            if let Some(idx) = remove {
+                tenants.remove(idx);
+            }
+
+            if let Some(unassigned) = miner_counts.get(crate::tenants::UNASSIGNED) {
+                ui.label(
+                    egui::RichText::new(format!("{unassigned} miner(s) without a tenant"))
+                        .size(10.0)
+                        .color(Color32::from_rgb(255, 200, 100)),
+                );
+            }
+
+            ui.separator();
+            draw_report(ui, period, report_rows, export);
+        });
+}
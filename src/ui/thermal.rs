@@ -0,0 +1,245 @@
+use crate::snapshots::format_timestamp;
+use crate::thermal::{ThermalSettings, ThermalState, ThermalStep};
+use eframe::egui;
+use egui::Color32;
+
+fn step_kind_label(step: &ThermalStep) -> &'static str {
+    match step {
+        ThermalStep::MaxFans => "Fans 100%",
+        ThermalStep::LowerPower { .. } => "Lower power",
+        ThermalStep::LowMiningMode => "Low mining mode",
+        ThermalStep::Pause => "Pause",
+    }
+}
+
+fn draw_steps_editor(ui: &mut egui::Ui, steps: &mut Vec<ThermalStep>) {
+    let mut step_to_remove = None;
+    let mut step_to_raise = None;
+
+    for (idx, step) in steps.iter_mut().enumerate() {
+        ui.horizontal(|ui| {
+            ui.label(egui::RichText::new(format!("{}.", idx + 1)).monospace());
+
+            let mut kind = step_kind_label(step);
+            egui::ComboBox::from_id_salt(format!("thermal_step_{idx}"))
+                .selected_text(kind)
+                .show_ui(ui, |ui| {
+                    for label in ["Fans 100%", "Lower power", "Low mining mode", "Pause"] {
+                        ui.selectable_value(&mut kind, label, label);
+                    }
+                });
+            if kind != step_kind_label(step) {
+                *step = match kind {
+                    "Fans 100%" => ThermalStep::MaxFans,
+                    "Lower power" => ThermalStep::LowerPower { percent: 15.0 },
+                    "Low mining mode" => ThermalStep::LowMiningMode,
+                    _ => ThermalStep::Pause,
+                };
+            }
+
+            if let ThermalStep::LowerPower { percent } = step {
+                ui.add(
+                    egui::DragValue::new(percent)
+                        .range(1.0..=80.0)
+                        .speed(1.0)
+                        .suffix(" %"),
+                );
+            }
+
+            if idx > 0 && ui.small_button("▲").on_hover_text("Earlier").clicked() {
+                step_to_raise = Some(idx);
+            }
+            if ui.small_button("✕").on_hover_text("Remove step").clicked() {
+                step_to_remove = Some(idx);
+            }
+        });
+    }
+
+    if let Some(idx) = step_to_remove {
+        steps.remove(idx);
+    }
+    if let Some(idx) = step_to_raise {
+        steps.swap(idx - 1, idx);
+    }
+    if ui.small_button("+ Add Step").clicked() {
+        steps.push(ThermalStep::Pause);
+    }
+}
+
+pub fn draw_thermal_window(
+    ctx: &egui::Context,
+    open: &mut bool,
+    settings: &mut ThermalSettings,
+    state: &ThermalState,
+    revert_identity: &mut Option<String>,
+    forget_identity: &mut Option<String>,
+) {
+    egui::Window::new(
+        egui::RichText::new("🌡 Thermal Protection")
+            .size(12.0)
+            .monospace(),
+    )
+    .id(egui::Id::new("thermal_window"))
+    .default_width(640.0)
+    .default_height(560.0)
+    .resizable(true)
+    .collapsible(true)
+    .open(open)
+    .show(ctx, |ui| {
+        ui.label(
+            egui::RichText::new(
+                "Uses the hottest of the average and board temperatures. Every further run of hot scans takes the next step. Option changes are recorded in History.",
+            )
+            .size(10.0)
+            .color(Color32::from_rgb(130, 130, 130)),
+        );
+        ui.add_space(6.0);
+
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut settings.enabled, "Enabled");
+            ui.label("Limit");
+            ui.add(
+                egui::DragValue::new(&mut settings.limit_c)
+                    .range(40.0..=120.0)
+                    .speed(0.5)
+                    .suffix(" C"),
+            );
+            ui.label("for");
+            ui.add(
+                egui::DragValue::new(&mut settings.consecutive_refreshes)
+                    .range(1..=20)
+                    .speed(1)
+                    .suffix(" scans"),
+            );
+        });
+
+        ui.add_space(6.0);
+        ui.label(
+            egui::RichText::new("ESCALATION")
+                .size(11.0)
+                .strong()
+                .monospace(),
+        );
+        draw_steps_editor(ui, &mut settings.steps);
+
+        ui.add_space(8.0);
+        ui.label(
+            egui::RichText::new(format!("PROTECTED MINERS ({})", state.escalations.len()))
+                .size(11.0)
+                .strong()
+                .monospace(),
+        );
+        egui::ScrollArea::vertical()
+            .id_salt("thermal_escalations_scroll")
+            .max_height(150.0)
+            .auto_shrink([false, true])
+            .show(ui, |ui| {
+                let mut escalations: Vec<_> = state.escalations.iter().collect();
+                escalations.sort_by(|(_, a), (_, b)| a.ip.cmp(&b.ip));
+
+                egui::Grid::new("thermal_escalations_grid")
+                    .num_columns(4)
+                    .spacing([16.0, 4.0])
+                    .striped(true)
+                    .show(ui, |ui| {
+                        for (identity, escalation) in escalations {
+                            ui.label(egui::RichText::new(&escalation.ip).size(10.0).monospace());
+                            let last_step = settings
+                                .steps
+                                .get(escalation.level.saturating_sub(1))
+                                .map(|s| s.describe())
+                                .unwrap_or_else(|| format!("step {}", escalation.level));
+                            ui.label(
+                                egui::RichText::new(format!(
+                                    "{}{}",
+                                    last_step,
+                                    if escalation.paused { " (paused)" } else { "" }
+                                ))
+                                .size(10.0)
+                                .color(Color32::from_rgb(255, 165, 0))
+                                .monospace(),
+                            );
+                            ui.label(
+                                egui::RichText::new(format!(
+                                    "since {}",
+                                    format_timestamp(escalation.started_at)
+                                ))
+                                .size(10.0)
+                                .monospace(),
+                            );
+                            let busy = state.in_flight.contains(identity);
+                            ui.horizontal(|ui| {
+                                if ui
+                                    .add_enabled(!busy, egui::Button::new("↩ Revert"))
+                                    .on_hover_text("Resume and restore the original fan, power and tuning settings")
+                                    .clicked()
+                                {
+                                    *revert_identity = Some(identity.clone());
+                                }
+                                if escalation.original_unknown() {
+                                    ui.label(
+                                        egui::RichText::new("⚠ originals unknown")
+                                            .size(10.0)
+                                            .color(Color32::from_rgb(255, 120, 120)),
+                                    );
+                                    if ui
+                                        .add_enabled(!busy, egui::Button::new("✕ Forget"))
+                                        .on_hover_text("Restore the settings by hand first, then stop tracking this miner")
+                                        .clicked()
+                                    {
+                                        *forget_identity = Some(identity.clone());
+                                    }
+                                }
+                            });
+                            ui.end_row();
+                        }
+                    });
+            });
+
+        ui.add_space(8.0);
+        ui.label(
+            egui::RichText::new("ACTION LOG")
+                .size(11.0)
+                .strong()
+                .monospace(),
+        );
+        egui::ScrollArea::vertical()
+            .id_salt("thermal_log_scroll")
+            .max_height(180.0)
+            .auto_shrink([false, true])
+            .show(ui, |ui| {
+                if state.log.is_empty() {
+                    ui.label("No thermal actions taken yet");
+                }
+                for entry in state.log.iter().rev() {
+                    let temperature = entry
+                        .temperature_c
+                        .map(|t| format!(" at {t:.1}°C"))
+                        .unwrap_or_default();
+                    let (text, color) = match &entry.error {
+                        None => (
+                            format!(
+                                "{}  {}  {}{}",
+                                format_timestamp(entry.at),
+                                entry.ip,
+                                entry.action,
+                                temperature
+                            ),
+                            Color32::from_rgb(200, 200, 200),
+                        ),
+                        Some(err) => (
+                            format!(
+                                "{}  {}  {} failed: {}",
+                                format_timestamp(entry.at),
+                                entry.ip,
+                                entry.action,
+                                err
+                            ),
+                            Color32::from_rgb(255, 120, 120),
+                        ),
+                    };
+                    ui.label(egui::RichText::new(text).size(10.0).color(color).monospace());
+                }
+            });
+    });
+}
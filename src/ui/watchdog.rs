@@ -0,0 +1,190 @@
+use crate::snapshots::format_timestamp;
+use crate::watchdog::{RecoveryMethod, WatchdogSettings, WatchdogState};
+use eframe::egui;
+use egui::Color32;
+
+pub fn draw_watchdog_window(
+    ctx: &egui::Context,
+    open: &mut bool,
+    settings: &mut WatchdogSettings,
+    state: &WatchdogState,
+    reset_identity: &mut Option<String>,
+) {
+    egui::Window::new(
+        egui::RichText::new("🩺 Recovery Watchdog")
+            .size(12.0)
+            .monospace(),
+    )
+    .id(egui::Id::new("watchdog_window"))
+    .default_width(640.0)
+    .default_height(560.0)
+    .resizable(true)
+    .collapsible(true)
+    .open(open)
+    .show(ctx, |ui| {
+        ui.label(
+            egui::RichText::new(
+                "Checked after every scan. Miners paused or curtailed by the power cap or thermal protection are left alone.",
+            )
+            .size(10.0)
+            .color(Color32::from_rgb(130, 130, 130)),
+        );
+        ui.add_space(6.0);
+
+        egui::Grid::new("watchdog_settings_grid")
+            .num_columns(2)
+            .spacing([12.0, 6.0])
+            .show(ui, |ui| {
+                ui.label("Enabled");
+                ui.checkbox(&mut settings.enabled, "");
+                ui.end_row();
+
+                ui.label("Dead boards");
+                ui.checkbox(&mut settings.detect_dead_boards, "active < total boards");
+                ui.end_row();
+
+                ui.label("Low hashrate");
+                ui.horizontal(|ui| {
+                    ui.checkbox(&mut settings.detect_low_hashrate, "below");
+                    ui.add(
+                        egui::DragValue::new(&mut settings.min_percent_of_peers)
+                            .range(10.0..=99.0)
+                            .speed(1.0)
+                            .suffix(" %"),
+                    );
+                    ui.label("of model median");
+                });
+                ui.end_row();
+
+                ui.label("Persisting for");
+                ui.add(
+                    egui::DragValue::new(&mut settings.window_mins)
+                        .range(0..=1440)
+                        .speed(1)
+                        .suffix(" min"),
+                );
+                ui.end_row();
+
+                ui.label("Cooldown");
+                ui.add(
+                    egui::DragValue::new(&mut settings.cooldown_mins)
+                        .range(1..=10080)
+                        .speed(5)
+                        .suffix(" min"),
+                );
+                ui.end_row();
+
+                ui.label("Max attempts");
+                ui.add(
+                    egui::DragValue::new(&mut settings.max_attempts)
+                        .range(1..=20)
+                        .speed(1),
+                );
+                ui.end_row();
+
+                ui.label("Method");
+                ui.horizontal(|ui| {
+                    ui.selectable_value(
+                        &mut settings.method,
+                        RecoveryMethod::RestartWhenSupported,
+                        "Restart if supported",
+                    );
+                    ui.selectable_value(
+                        &mut settings.method,
+                        RecoveryMethod::PauseResume,
+                        "Pause/Resume",
+                    );
+                    ui.add(
+                        egui::DragValue::new(&mut settings.pause_secs)
+                            .range(1..=600)
+                            .speed(1)
+                            .suffix(" s paused"),
+                    );
+                });
+                ui.end_row();
+            });
+
+        ui.add_space(8.0);
+        ui.label(
+            egui::RichText::new(format!("TRACKED MINERS ({})", state.records.len()))
+                .size(11.0)
+                .strong()
+                .monospace(),
+        );
+        egui::ScrollArea::vertical()
+            .id_salt("watchdog_records_scroll")
+            .max_height(150.0)
+            .auto_shrink([false, true])
+            .show(ui, |ui| {
+                let mut records: Vec<_> = state.records.iter().collect();
+                records.sort_by(|(_, a), (_, b)| a.ip.cmp(&b.ip));
+
+                egui::Grid::new("watchdog_records_grid")
+                    .num_columns(4)
+                    .spacing([16.0, 4.0])
+                    .striped(true)
+                    .show(ui, |ui| {
+                        for (identity, record) in records {
+                            ui.label(egui::RichText::new(&record.ip).size(10.0).monospace());
+                            let (status, color) = if record.gave_up {
+                                ("gave up".to_string(), Color32::from_rgb(255, 120, 120))
+                            } else {
+                                (
+                                    format!("{}/{} attempts", record.attempts, settings.max_attempts),
+                                    Color32::from_rgb(255, 200, 100),
+                                )
+                            };
+                            ui.label(egui::RichText::new(status).size(10.0).color(color).monospace());
+                            ui.label(
+                                egui::RichText::new(if record.last_attempt_at > 0 {
+                                    format!("last {}", format_timestamp(record.last_attempt_at))
+                                } else {
+                                    "-".to_string()
+                                })
+                                .size(10.0)
+                                .monospace(),
+                            );
+                            if ui
+                                .small_button("Reset")
+                                .on_hover_text("Forget attempts so the watchdog may try again")
+                                .clicked()
+                            {
+                                *reset_identity = Some(identity.clone());
+                            }
+                            ui.end_row();
+                        }
+                    });
+            });
+
+        ui.add_space(8.0);
+        ui.label(egui::RichText::new("ACTION LOG").size(11.0).strong().monospace());
+        egui::ScrollArea::vertical()
+            .id_salt("watchdog_log_scroll")
+            .max_height(180.0)
+            .auto_shrink([false, true])
+            .show(ui, |ui| {
+                if state.log.is_empty() {
+                    ui.label("No recovery actions yet");
+                }
+                for entry in state.log.iter().rev() {
+                    let (text, color) = match &entry.error {
+                        None => (
+                            format!("{}  {}  {}", format_timestamp(entry.at), entry.ip, entry.message),
+                            Color32::from_rgb(200, 200, 200),
+                        ),
+                        Some(err) => (
+                            format!(
+                                "{}  {}  {} failed: {}",
+                                format_timestamp(entry.at),
+                                entry.ip,
+                                entry.message,
+                                err
+                            ),
+                            Color32::from_rgb(255, 120, 120),
+                        ),
+                    };
+                    ui.label(egui::RichText::new(text).size(10.0).color(color).monospace());
+                }
+            });
+    });
+}
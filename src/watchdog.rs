@@ -0,0 +1,454 @@
+use crate::actions::{self, MinerAction};
+use crate::audit::{self, AuditEntry, AuditLog};
+use crate::models::MinerInfo;
+use chrono::Local;
+use futures::stream::{self, StreamExt};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+const WATCHDOG_STATE_FILE: &str = "watchdog_state.json";
+const MAX_LOG_ENTRIES: usize = 500;
+// Peers needed before a model median is trusted for the low-hashrate rule
+const MIN_MODEL_PEERS: usize = 3;
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub enum RecoveryMethod {
+    PauseResume,
+    /// Restart where the miner supports it, pause/resume otherwise.
+    RestartWhenSupported,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct WatchdogSettings {
+    pub enabled: bool,
+    pub detect_dead_boards: bool,
+    pub detect_low_hashrate: bool,
+    /// Hashrate below this share of the model's median counts as underperforming.
+    pub min_percent_of_peers: f64,
+    /// How long a problem must persist before recovery is attempted.
+    pub window_mins: u64,
+    pub cooldown_mins: u64,
+    pub max_attempts: u32,
+    pub method: RecoveryMethod,
+    pub pause_secs: u64,
+}
+
+impl Default for WatchdogSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            detect_dead_boards: true,
+            detect_low_hashrate: true,
+            min_percent_of_peers: 70.0,
+            window_mins: 15,
+            cooldown_mins: 60,
+            max_attempts: 3,
+            method: RecoveryMethod::RestartWhenSupported,
+            pause_secs: 15,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Underperformance {
+    DeadBoards {
+        active: usize,
+        total: usize,
+    },
+    LowHashrate {
+        hashrate_th: f64,
+        peer_median_th: f64,
+    },
+}
+
+impl Underperformance {
+    pub fn describe(&self) -> String {
+        match self {
+            Underperformance::DeadBoards { active, total } => {
+                format!("{active}/{total} boards active")
+            }
+            Underperformance::LowHashrate {
+                hashrate_th,
+                peer_median_th,
+            } => format!("{hashrate_th:.2} TH/s vs model median {peer_median_th:.2} TH/s"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct DetectedProblem {
+    pub identity: String,
+    pub ip: String,
+    pub problem: Underperformance,
+    pub can_restart: bool,
+}
+
+pub fn median(values: &mut [f64]) -> Option<f64> {
+    if values.is_empty() {
+        return None;
+    }
+    values.sort_by(f64::total_cmp);
+    let mid = values.len() / 2;
+    Some(if values.len().is_multiple_of(2) {
+        (values[mid - 1] + values[mid]) / 2.0
+    } else {
+        values[mid]
+    })
+}
+
+/// Median reported hashrate per model, for models with enough hashing peers.
+pub fn median_hashrate_by_model(miners: &[MinerInfo]) -> HashMap<String, f64> {
+    let mut by_model: HashMap<&str, Vec<f64>> = HashMap::new();
+    for miner in miners {
+        if let Some(hashrate) = miner.hashrate_th.filter(|h| *h > 0.0) {
+            by_model.entry(&miner.model).or_default().push(hashrate);
+        }
+    }
+
+    by_model
+        .into_iter()
+        .filter(|(_, values)| values.len() >= MIN_MODEL_PEERS)
+        .filter_map(|(model, mut values)| median(&mut values).map(|m| (model.to_string(), m)))
+        .collect()
+}
+
+fn is_mining(miner: &MinerInfo) -> bool {
+    miner.full_data.as_ref().is_none_or(|data| data.is_mining)
+}
+
+/// Miners currently showing dead boards or hashrate well below their model's
+/// peers. Paused miners and identities in `excluded` (held down on purpose by
+/// other automations) are ignored.
+pub fn detect_underperformers(
+    settings: &WatchdogSettings,
+    miners: &[MinerInfo],
+    excluded: &HashSet<String>,
+) -> Vec<DetectedProblem> {
+    let medians = median_hashrate_by_model(miners);
+
+    miners
+        .iter()
+        .filter(|miner| is_mining(miner))
+        .filter_map(|miner| {
+            let identity = miner.identity();
+            if excluded.contains(&identity) {
+                return None;
+            }
+
+            let dead_boards = match (miner.active_boards_count, miner.total_boards_count) {
+                (Some(active), Some(total)) if settings.detect_dead_boards && active < total => {
+                    Some(Underperformance::DeadBoards { active, total })
+                }
+                _ => None,
+            };
+            let low_hashrate = || {
+                let peer_median_th = *medians.get(&miner.model)?;
+                let hashrate_th = miner.hashrate_th.unwrap_or(0.0);
+                (settings.detect_low_hashrate
+                    && hashrate_th < peer_median_th * settings.min_percent_of_peers / 100.0)
+                    .then_some(Underperformance::LowHashrate {
+                        hashrate_th,
+                        peer_median_th,
+                    })
+            };
+
+            dead_boards
+                .or_else(low_hashrate)
+                .map(|problem| DetectedProblem {
+                    identity,
+                    ip: miner.ip.clone(),
+                    problem,
+                    can_restart: miner.capabilities.restart,
+                })
+        })
+        .collect()
+}
+
+/// Recovery history for one miner, keyed by `MinerInfo::identity()`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RecoveryRecord {
+    pub ip: String,
+    pub attempts: u32,
+    pub last_attempt_at: i64,
+    pub gave_up: bool,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct WatchdogLogEntry {
+    pub at: i64,
+    pub ip: String,
+    pub message: String,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct WatchdogState {
+    pub records: HashMap<String, RecoveryRecord>,
+    pub log: Vec<WatchdogLogEntry>,
+    #[serde(skip)]
+    pub problem_since: HashMap<String, i64>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct RecoveryTrigger {
+    pub ip: String,
+    pub action: MinerAction,
+    pub reason: String,
+    pub attempt: u32,
+}
+
+impl WatchdogState {
+    pub fn load() -> Self {
+        crate::config::app_data_path(WATCHDOG_STATE_FILE)
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+            .unwrap_or_default()
+    }
+
+    fn save(&self) {
+        if let Some(path) = crate::config::app_data_path(WATCHDOG_STATE_FILE) {
+            if let Ok(json) = serde_json::to_string_pretty(self) {
+                if let Err(e) = fs::write(path, json) {
+                    eprintln!("✗ Failed to save watchdog state: {e}");
+                }
+            }
+        }
+    }
+
+    fn push_log(&mut self, ip: &str, message: String, error: Option<String>) {
+        match &error {
+            None => println!("✓ Watchdog: {ip} {message}"),
+            Some(err) => eprintln!("✗ Watchdog: {ip} {message}: {err}"),
+        }
+        self.log.push(WatchdogLogEntry {
+            at: Local::now().timestamp(),
+            ip: ip.to_string(),
+            message,
+            error,
+        });
+        if self.log.len() > MAX_LOG_ENTRIES {
+            self.log.drain(0..self.log.len() - MAX_LOG_ENTRIES);
+        }
+    }
+
+    /// Tracks how long each problem has lasted and returns the recoveries that
+    /// are due, honouring the cooldown and attempt limit. Miners that stay
+    /// healthy past the cooldown have their attempt count cleared.
+    pub fn plan_recoveries(
+        &mut self,
+        settings: &WatchdogSettings,
+        problems: &[DetectedProblem],
+        now: i64,
+    ) -> Vec<RecoveryTrigger> {
+        let window_secs = settings.window_mins as i64 * 60;
+        let cooldown_secs = settings.cooldown_mins as i64 * 60;
+        let problem_ids: HashSet<&String> = problems.iter().map(|p| &p.identity).collect();
+
+        self.problem_since.retain(|id, _| problem_ids.contains(id));
+        let recovered: Vec<String> = self
+            .records
+            .iter()
+            .filter(|(id, record)| {
+                !problem_ids.contains(id) && now - record.last_attempt_at >= cooldown_secs
+            })
+            .map(|(id, _)| id.clone())
+            .collect();
+        for id in recovered {
+            if let Some(record) = self.records.remove(&id) {
+                if !record.gave_up {
+                    self.push_log(&record.ip, "healthy again".to_string(), None);
+                }
+            }
+        }
+
+        let mut triggers = Vec::new();
+        for problem in problems {
+            let since = *self
+                .problem_since
+                .entry(problem.identity.clone())
+                .or_insert(now);
+            if now - since < window_secs {
+                continue;
+            }
+
+            let record = self
+                .records
+                .entry(problem.identity.clone())
+                .or_insert(RecoveryRecord {
+                    ip: problem.ip.clone(),
+                    attempts: 0,
+                    last_attempt_at: 0,
+                    gave_up: false,
+                });
+            record.ip = problem.ip.clone();
+            let cooling_down = record.attempts > 0 && now - record.last_attempt_at < cooldown_secs;
+            if record.gave_up || cooling_down {
+                continue;
+            }
+            if record.attempts >= settings.max_attempts {
+                record.gave_up = true;
+                let ip = record.ip.clone();
+                self.push_log(
+                    &ip,
+                    format!(
+                        "giving up after {} attempt(s): {}",
+                        settings.max_attempts,
+                        problem.problem.describe()
+                    ),
+                    None,
+                );
+                continue;
+            }
+
+            record.attempts += 1;
+            record.last_attempt_at = now;
+            triggers.push(RecoveryTrigger {
+                ip: problem.ip.clone(),
+                action: match settings.method {
+                    RecoveryMethod::RestartWhenSupported if problem.can_restart => {
+                        MinerAction::Restart
+                    }
+                    _ => MinerAction::Pause,
+                },
+                reason: problem.problem.describe(),
+                attempt: record.attempts,
+            });
+        }
+
+        triggers
+    }
+}
+
+/// Issues the recoveries in the background. `Pause` means a pause/resume cycle.
+/// The batch is audited once every recovery has finished.
+pub fn execute_recoveries(
+    state: Arc<Mutex<WatchdogState>>,
+    triggers: Vec<RecoveryTrigger>,
+    pause_secs: u64,
+    audit_log: Arc<Mutex<AuditLog>>,
+    audit_entry: AuditEntry,
+) {
+    crate::runtime::spawn(async move {
+        let concurrency = triggers.len().clamp(1, 24);
+        let results: Vec<(RecoveryTrigger, Result<(), String>)> = stream::iter(triggers)
+            .map(|trigger| async move {
+                let result = match trigger.action {
+                    MinerAction::Pause => {
+                        match actions::run_action(&trigger.ip, MinerAction::Pause).await {
+                            Ok(()) => {
+                                tokio::time::sleep(Duration::from_secs(pause_secs)).await;
+                                actions::run_action(&trigger.ip, MinerAction::Resume).await
+                            }
+                            Err(err) => Err(err),
+                        }
+                    }
+                    action => actions::run_action(&trigger.ip, action).await,
+                };
+                (trigger, result)
+            })
+            .buffer_unordered(concurrency)
+            .collect()
+            .await;
+        let outcomes: Vec<(String, Result<(), String>)> = results
+            .iter()
+            .map(|(trigger, result)| (trigger.ip.clone(), result.clone()))
+            .collect();
+        audit::record(&audit_log, audit_entry.with_results(&outcomes));
+
+        let mut state = state.lock().unwrap();
+        for (trigger, result) in results {
+            let method = match trigger.action {
+                MinerAction::Restart => "restart",
+                _ => "pause/resume",
+            };
+            state.push_log(
+                &trigger.ip,
+                format!("{method} attempt {} ({})", trigger.attempt, trigger.reason),
+                result.err(),
+            );
+        }
+        state.save();
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        detect_underperformers, DetectedProblem, Underperformance, WatchdogSettings, WatchdogState,
+    };
+    use crate::actions::MinerAction;
+    use crate::models::MinerInfo;
+    use std::collections::HashSet;
+
+    fn miner(ip: &str, hashrate: f64, active: usize) -> MinerInfo {
+        MinerInfo {
+            ip: ip.to_string(),
+            model: "S19".to_string(),
+            hashrate_th: Some(hashrate),
+            active_boards_count: Some(active),
+            total_boards_count: Some(3),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn detects_dead_boards_and_low_hashrate_against_peers() {
+        let miners = vec![
+            miner("10.0.0.1", 100.0, 3),
+            miner("10.0.0.2", 98.0, 3),
+            miner("10.0.0.3", 40.0, 3),
+            miner("10.0.0.4", 66.0, 2),
+        ];
+        let problems =
+            detect_underperformers(&WatchdogSettings::default(), &miners, &HashSet::new());
+        let ips: Vec<&str> = problems.iter().map(|p| p.ip.as_str()).collect();
+        assert_eq!(ips, vec!["10.0.0.3", "10.0.0.4"]);
+        assert!(matches!(
+            problems[1].problem,
+            Underperformance::DeadBoards {
+                active: 2,
+                total: 3
+            }
+        ));
+
+        let excluded = HashSet::from(["10.0.0.3".to_string()]);
+        let problems = detect_underperformers(&WatchdogSettings::default(), &miners, &excluded);
+        assert_eq!(problems.len(), 1);
+    }
+
+    #[test]
+    fn recoveries_respect_window_cooldown_and_attempt_limit() {
+        let settings = WatchdogSettings {
+            window_mins: 10,
+            cooldown_mins: 30,
+            max_attempts: 2,
+            ..Default::default()
+        };
+        let problem = DetectedProblem {
+            identity: "aa".to_string(),
+            ip: "10.0.0.9".to_string(),
+            problem: Underperformance::DeadBoards {
+                active: 1,
+                total: 3,
+            },
+            can_restart: true,
+        };
+        let problems = vec![problem];
+        let mut state = WatchdogState::default();
+
+        assert!(state.plan_recoveries(&settings, &problems, 0).is_empty());
+        let first = state.plan_recoveries(&settings, &problems, 600);
+        assert_eq!(first.len(), 1);
+        assert_eq!(first[0].action, MinerAction::Restart);
+        // Still cooling down
+        assert!(state.plan_recoveries(&settings, &problems, 1200).is_empty());
+        assert_eq!(state.plan_recoveries(&settings, &problems, 2400).len(), 1);
+        // Attempts exhausted
+        assert!(state.plan_recoveries(&settings, &problems, 4200).is_empty());
+        assert!(state.records["aa"].gave_up);
+    }
+}
@@ -0,0 +1,188 @@
+use crate::models::{MinerLocation, MinerOptionSettings};
+use asic_rs_core::data::miner::MinerData;
+use std::collections::HashMap;
+
+pub const WORKER_TEMPLATE_VARIABLES: [&str; 7] = [
+    "account",
+    "hostname",
+    "ip_last_octet",
+    "mac_short",
+    "rack",
+    "slot",
+    "serial",
+];
+
+/// Per-miner values available to `{variable}` placeholders in pool usernames.
+#[derive(Clone, Debug, Default)]
+pub struct WorkerNameVars {
+    pub account: String,
+    pub hostname: Option<String>,
+    pub ip: String,
+    pub mac: Option<String>,
+    pub serial: Option<String>,
+    pub rack: Option<String>,
+    pub slot: Option<String>,
+}
+
+impl WorkerNameVars {
+    pub fn from_data(
+        ip: &str,
+        data: &MinerData,
+        account: &str,
+        locations: &HashMap<String, MinerLocation>,
+    ) -> Self {
+        let mac = data.mac.map(|m| m.to_string());
+        let identity = mac.clone().unwrap_or_else(|| ip.to_string());
+        let location = locations.get(&identity);
+
+        Self {
+            account: account.trim().to_string(),
+            hostname: data.hostname.clone(),
+            ip: ip.to_string(),
+            mac,
+            serial: data.serial_number.clone(),
+            rack: location.map(|l| l.rack.trim().to_string()),
+            slot: location.map(|l| l.slot.trim().to_string()),
+        }
+    }
+
+    fn lookup(&self, name: &str) -> Option<String> {
+        let value = match name {
+            "account" => Some(self.account.clone()),
+            "hostname" => self.hostname.clone(),
+            "ip_last_octet" => self.ip.rsplit('.').next().map(str::to_string),
+            "mac_short" => self.mac.as_ref().map(|mac| {
+                let hex: String = mac.chars().filter(char::is_ascii_hexdigit).collect();
+                hex[hex.len().saturating_sub(6)..].to_ascii_lowercase()
+            }),
+            "rack" => self.rack.clone(),
+            "slot" => self.slot.clone(),
+            "serial" => self.serial.clone(),
+            _ => None,
+        };
+        value.filter(|v| !v.trim().is_empty())
+    }
+}
+
+pub fn worker_template_help() -> String {
+    let variables: Vec<String> = WORKER_TEMPLATE_VARIABLES
+        .iter()
+        .map(|name| format!("{{{name}}}"))
+        .collect();
+    format!(
+        "Pool usernames may use {} - resolved per miner when applied",
+        variables.join(" ")
+    )
+}
+
+pub fn has_worker_template(username: &str) -> bool {
+    username.contains('{')
+}
+
+/// Checks placeholder syntax without needing any miner data.
+pub fn worker_template_error(template: &str) -> Option<String> {
+    let mut rest = template;
+    while let Some(open) = rest.find('{') {
+        let Some(close) = rest[open..].find('}') else {
+            return Some(format!("Unclosed '{{' in worker name '{template}'"));
+        };
+        let name = &rest[open + 1..open + close];
+        if !WORKER_TEMPLATE_VARIABLES.contains(&name) {
+            return Some(format!("Unknown worker variable {{{name}}}"));
+        }
+        rest = &rest[open + close + 1..];
+    }
+    None
+}
+
+pub fn resolve_worker_template(template: &str, vars: &WorkerNameVars) -> Result<String, String> {
+    if let Some(err) = worker_template_error(template) {
+        return Err(err);
+    }
+
+    let mut resolved = String::new();
+    let mut rest = template;
+    while let Some(open) = rest.find('{') {
+        let close = open + rest[open..].find('}').unwrap_or(0);
+        let name = &rest[open + 1..close];
+        let value = vars
+            .lookup(name)
+            .ok_or_else(|| format!("{{{name}}} is not known for {}", vars.ip))?;
+        resolved.push_str(&rest[..open]);
+        resolved.push_str(&value);
+        rest = &rest[close + 1..];
+    }
+    resolved.push_str(rest);
+
+    Ok(resolved.trim().to_string())
+}
+
+/// Settings with every templated pool username replaced by this miner's worker name.
+pub fn resolve_worker_names(
+    settings: &MinerOptionSettings,
+    vars: &WorkerNameVars,
+) -> Result<MinerOptionSettings, String> {
+    let mut resolved = settings.clone();
+    if !resolved.apply_pool_config {
+        return Ok(resolved);
+    }
+
+    for pool in resolved
+        .pool_groups
+        .iter_mut()
+        .flat_map(|group| group.pools.iter_mut())
+    {
+        if has_worker_template(&pool.username) {
+            pool.username = resolve_worker_template(&pool.username, vars)?;
+        }
+    }
+    Ok(resolved)
+}
+
+pub fn settings_use_worker_templates(settings: &MinerOptionSettings) -> bool {
+    settings.apply_pool_config
+        && settings
+            .all_pools()
+            .any(|pool| has_worker_template(&pool.username))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{resolve_worker_template, worker_template_error, WorkerNameVars};
+
+    fn vars() -> WorkerNameVars {
+        WorkerNameVars {
+            account: "acme".to_string(),
+            hostname: Some("s21-0042".to_string()),
+            ip: "10.0.81.42".to_string(),
+            mac: Some("AA:BB:CC:DD:EE:FF".to_string()),
+            serial: None,
+            rack: Some("R3".to_string()),
+            slot: Some("07".to_string()),
+        }
+    }
+
+    #[test]
+    fn resolve_worker_template_substitutes_miner_values() {
+        assert_eq!(
+            resolve_worker_template("{account}.{hostname}", &vars()).unwrap(),
+            "acme.s21-0042"
+        );
+        assert_eq!(
+            resolve_worker_template(
+                "{account}.{rack}{slot}x{ip_last_octet}_{mac_short}",
+                &vars()
+            )
+            .unwrap(),
+            "acme.R307x42_ddeeff"
+        );
+    }
+
+    #[test]
+    fn resolve_worker_template_rejects_missing_and_unknown_values() {
+        assert!(resolve_worker_template("{account}.{serial}", &vars()).is_err());
+        assert!(worker_template_error("{account}.{rig}").is_some());
+        assert!(worker_template_error("{account").is_some());
+        assert!(worker_template_error("plain.worker").is_none());
+    }
+}